@@ -0,0 +1,86 @@
+//! Benchmarks for parsing records and single terms.
+//!
+//! Run with `cargo bench --bench parse`. Add `--features bench-regex` to also compare the
+//! parser with the regex based parsing it replaced on the same records and terms.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use decon_spf::mechanism::Mechanism;
+use decon_spf::Spf;
+
+#[cfg(feature = "bench-regex")]
+mod regex_path;
+
+const RECORDS: [(&str, &str); 4] = [
+    ("minimal", "v=spf1 -all"),
+    (
+        "typical",
+        "v=spf1 a mx include:_spf.google.com include:spf.protection.outlook.com ~all",
+    ),
+    (
+        "networks",
+        "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 ip4:203.0.113.10 ip6:2001:db8::/32 \
+         ip6:2001:db8:1::1 a:mail.example.com/24 mx/24//64 -all",
+    ),
+    (
+        "macros",
+        "v=spf1 exists:%{ir}.%{l1r+-}._spf.%{d} include:%{d2}._spf.example.com \
+         ptr:example.com redirect=_spf.example.com",
+    ),
+];
+
+const TERMS: [&str; 4] = [
+    "-a:example.com/24",
+    "mx",
+    "~ptr:example.com",
+    "exists:%{i}._spf.example.com",
+];
+
+fn records(c: &mut Criterion) {
+    let mut group = c.benchmark_group("record");
+    for (name, record) in RECORDS.iter() {
+        group.throughput(Throughput::Bytes(record.len() as u64));
+        group.bench_with_input(BenchmarkId::new("parser", name), record, |b, r| {
+            b.iter(|| black_box(r).parse::<Spf<String>>().unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn terms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("term");
+    for term in TERMS.iter() {
+        group.bench_with_input(BenchmarkId::new("parser", term), term, |b, t| {
+            b.iter(|| black_box(t).parse::<Mechanism<String>>().unwrap())
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "bench-regex")]
+fn compare(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compare");
+    for (name, record) in RECORDS.iter() {
+        group.throughput(Throughput::Bytes(record.len() as u64));
+        group.bench_with_input(BenchmarkId::new("parser", name), record, |b, r| {
+            b.iter(|| black_box(r).parse::<Spf<String>>().unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("regex", name), record, |b, r| {
+            b.iter(|| regex_path::parse_record(black_box(r)).unwrap())
+        });
+    }
+    group.throughput(Throughput::Elements(1));
+    for term in TERMS.iter() {
+        group.bench_with_input(BenchmarkId::new("parser", term), term, |b, t| {
+            b.iter(|| black_box(t).parse::<Mechanism<String>>().unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("regex", term), term, |b, t| {
+            b.iter(|| regex_path::parse_term(black_box(t)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "bench-regex"))]
+criterion_group!(benches, records, terms);
+#[cfg(feature = "bench-regex")]
+criterion_group!(benches, records, terms, compare);
+criterion_main!(benches);
@@ -0,0 +1,162 @@
+//! The regex based parsing used up to version 0.3.3, kept to compare against the parser.
+//!
+//! Terms are dispatched by substring as `Mechanism<String>::from_str()` did. `a`, `mx`, `ptr`
+//! and `exists` are matched by the regular expressions of `core::spf_regex`, and `ip4`/`ip6`
+//! are parsed as an `IpNetwork`. Records are checked and split as `Spf<String>::from_str()`
+//! did.
+use decon_spf::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
+use ipnetwork::IpNetwork;
+use regex::Regex;
+use std::sync::OnceLock;
+
+const SPF1: &str = "v=spf1";
+const MAX_SPF_STRING_LENGTH: usize = 512;
+
+const MECHANISM_A_PATTERN: &str =
+    r"(?i)^(?P<qualifier>[+?~-])?a(?:$|[^a-z.])(?P<mechanism>[a-z0-9].*|\d{1,3})?$";
+const MECHANISM_MX_PATTERN: &str =
+    r"(?i)^(?P<qualifier>[+?~-])?mx(?:$|[^a-z.])(?P<mechanism>[a-z0-9].*|\d{1,3})?$";
+const MECHANISM_PTR_PATTERN: &str = r"(?i)^(?P<qualifier>[+?~-])?ptr(?:$|[^a-z./])(?P<mechanism>(?:[[:word:]]+\.)*[[:word:]]+$\n?)?$";
+const MECHANISM_EXISTS_PATTERN: &str =
+    r"(?i)^(?P<qualifier>[+?~-])?exists:(?P<mechanism>(?:%|\w).*)";
+
+struct Patterns {
+    a: Regex,
+    mx: Regex,
+    ptr: Regex,
+    exists: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        a: Regex::new(MECHANISM_A_PATTERN).unwrap(),
+        mx: Regex::new(MECHANISM_MX_PATTERN).unwrap(),
+        ptr: Regex::new(MECHANISM_PTR_PATTERN).unwrap(),
+        exists: Regex::new(MECHANISM_EXISTS_PATTERN).unwrap(),
+    })
+}
+
+fn char_to_qualifier(c: char) -> Qualifier {
+    match c {
+        '-' => Qualifier::Fail,
+        '~' => Qualifier::SoftFail,
+        '?' => Qualifier::Neutral,
+        _ => Qualifier::Pass,
+    }
+}
+
+// Split the qualifier from a term which does not start with `c` when it has none.
+fn return_and_remove_qualifier(record: &str, c: char) -> (Qualifier, &str) {
+    match record.chars().next() {
+        Some(first) if first != c => (char_to_qualifier(first), &record[1..]),
+        _ => (Qualifier::Pass, record),
+    }
+}
+
+fn invalid(s: &str) -> MechanismError {
+    MechanismError::InvalidMechanismFormat(s.to_string())
+}
+
+// Create a new mechanism for a matched regular expression.
+fn capture_matches(string: &str, kind: Kind) -> Result<Mechanism<String>, MechanismError> {
+    if string.ends_with('/') || string.ends_with(':') {
+        return Err(invalid(string));
+    }
+    let patterns = patterns();
+    let caps = match kind {
+        Kind::A => patterns.a.captures(string),
+        Kind::MX => patterns.mx.captures(string),
+        Kind::Ptr => patterns.ptr.captures(string),
+        Kind::Exists => patterns.exists.captures(string),
+        _ => unreachable!(),
+    };
+    let caps = caps.ok_or_else(|| invalid(string))?;
+    let qualifier = caps
+        .name("qualifier")
+        .and_then(|q| q.as_str().chars().next())
+        .map_or(Qualifier::Pass, char_to_qualifier);
+    let mechanism = match caps.name("mechanism") {
+        Some(value) => {
+            let mut rrdata = value.as_str().to_string();
+            // The patterns match a bare number, which is a cidr length.
+            if rrdata.len() < 4 {
+                if rrdata.parse::<i32>().is_err() {
+                    return Err(invalid(string));
+                }
+                rrdata = format!("/{}", rrdata);
+            }
+            Mechanism::generic_inclusive(kind, qualifier, Some(rrdata))
+        }
+        None => Mechanism::generic_inclusive(kind, qualifier, None),
+    };
+    if mechanism.kind().is_exists() && mechanism.raw().contains('/') {
+        return Err(invalid(string));
+    }
+    Ok(mechanism)
+}
+
+fn parse_ip(s: &str) -> Result<Mechanism<String>, MechanismError> {
+    let (qualifier, term) = return_and_remove_qualifier(s, 'i');
+    let kind = match term {
+        t if t.contains("ip4:") => Kind::IpV4,
+        _ => Kind::IpV6,
+    };
+    let raw_ip = term.splitn(2, ':').last().unwrap_or_default();
+    let ip = raw_ip
+        .parse::<IpNetwork>()
+        .map_err(|e| MechanismError::InvalidIPNetwork(e.into()))?;
+    if ip.is_ipv4() && !kind.is_ip_v4() {
+        return Err(MechanismError::NotIP6Network(ip.to_string()));
+    }
+    if ip.is_ipv6() && !kind.is_ip_v6() {
+        return Err(MechanismError::NotIP4Network(ip.to_string()));
+    }
+    Ok(Mechanism::generic_inclusive(kind, qualifier, Some(ip)).into())
+}
+
+/// Parse a single term.
+pub fn parse_term(s: &str) -> Result<Mechanism<String>, MechanismError> {
+    if s.ends_with(':') || s.ends_with('/') {
+        return Err(invalid(s));
+    }
+    if s.contains("ip4:") || s.contains("ip6:") {
+        return parse_ip(s);
+    }
+    if s.contains("redirect=") {
+        let rrdata = s.rsplit('=').next().unwrap_or_default();
+        return Ok(Mechanism::generic_inclusive(
+            Kind::Redirect,
+            Qualifier::Pass,
+            Some(rrdata.to_string()),
+        ));
+    }
+    if s.contains("include:") {
+        let (qualifier, _) = return_and_remove_qualifier(s, 'i');
+        let rrdata = s.rsplit(':').next().unwrap_or_default();
+        return Ok(Mechanism::generic_inclusive(
+            Kind::Include,
+            qualifier,
+            Some(rrdata.to_string()),
+        ));
+    }
+    if s.ends_with("all") && (s.len() == 3 || s.len() == 4) {
+        let (qualifier, _) = return_and_remove_qualifier(s, 'a');
+        return Ok(Mechanism::generic_inclusive(Kind::All, qualifier, None));
+    }
+    [Kind::A, Kind::MX, Kind::Ptr, Kind::Exists]
+        .iter()
+        .find_map(|kind| capture_matches(s, *kind).ok())
+        .ok_or_else(|| invalid(s))
+}
+
+/// Parse a record into its terms.
+pub fn parse_record(s: &str) -> Result<Vec<Mechanism<String>>, MechanismError> {
+    if !s.starts_with(SPF1) || s.len() > MAX_SPF_STRING_LENGTH {
+        return Err(invalid(s));
+    }
+    s.split_whitespace()
+        .filter(|term| !term.contains(SPF1))
+        .map(parse_term)
+        .collect()
+}
@@ -1,3 +1,4 @@
+#![allow(clippy::to_string_in_format_args)]
 use decon_spf::mechanism::{Kind, Mechanism, ParsedMechanism, Qualifier};
 use decon_spf::{Builder, SpfBuilder};
 
@@ -26,7 +27,7 @@ fn main() {
     println!("New spf 1: >{}<", spf1);
     assert_eq!(
         spf1.to_string(),
-        "v=spf1 mx ip4:203.32.160.0/24 ip4:203.32.166.0/24"
+        "v=spf1 ip4:203.32.160.0/24 ip4:203.32.166.0/24 mx"
     );
 
     let mut spf2: SpfBuilder<Builder> = SpfBuilder::new();
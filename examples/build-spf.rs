@@ -20,7 +20,7 @@ fn main() {
     println!("New spf 1: >{}<", spf1);
     assert_eq!(
         spf1.to_string(),
-        "v=spf1 a:test.com mx ip4:203.32.160.0/24 ip4:203.32.166.0/24"
+        "v=spf1 ip4:203.32.160.0/24 ip4:203.32.166.0/24 mx a:test.com"
     );
 
     let mut spf2 = SpfBuilder::<Builder>::new();
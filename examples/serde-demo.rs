@@ -1,3 +1,4 @@
+#![allow(clippy::single_component_path_imports)]
 use decon_spf::mechanism::{Mechanism, ParsedMechanism, Qualifier};
 use decon_spf::{Builder, SpfBuilder};
 use serde_json;
@@ -21,7 +22,7 @@ fn main() {
     println!("New spf 1: >{}<", spf1);
     assert_eq!(
         spf1.to_string(),
-        "v=spf1 a:test.com mx ip4:203.32.160.0/24 ip4:203.32.166.0/24"
+        "v=spf1 ip4:203.32.160.0/24 ip4:203.32.166.0/24 mx a:test.com"
     );
 
     let spf_as_json = serde_json::to_string_pretty(&spf1).unwrap();
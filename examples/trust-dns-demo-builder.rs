@@ -1,3 +1,9 @@
+#![allow(
+    clippy::assign_op_pattern,
+    clippy::explicit_counter_loop,
+    clippy::needless_borrow,
+    clippy::to_string_in_format_args
+)]
 use decon_spf::Parsed;
 use decon_spf::SpfBuilder;
 use trust_dns_resolver::error::ResolveResult;
@@ -1,3 +1,8 @@
+#![allow(
+    clippy::assign_op_pattern,
+    clippy::explicit_counter_loop,
+    clippy::needless_borrow
+)]
 use decon_spf::Spf;
 use trust_dns_resolver::error::ResolveResult;
 use trust_dns_resolver::Resolver;
@@ -39,21 +44,30 @@ fn main() {
 }
 
 fn display_txt(query: &str, txt_response: &ResolveResult<TxtLookup>) -> Spf<String> {
-    let mut spf_record = Spf::default();
     match txt_response {
-        Err(_) => println!("No TXT Records."),
+        Err(_) => {
+            println!("No TXT Records.");
+            Spf::default()
+        }
         Ok(txt_response) => {
             let mut i = 1;
             println!("List of TXT records found for {}", &query);
             for record in txt_response.iter() {
                 println!("TXT Record {}:", i);
                 println!("{}", &record.to_string());
-                if record.to_string().starts_with("v=spf1") {
-                    spf_record = record.to_string().parse().unwrap();
-                }
                 i = i + 1;
             }
+            match Spf::from_txt_rrset(txt_response.iter().map(|record| record.to_string())) {
+                Ok(Some(spf_record)) => spf_record,
+                Ok(None) => {
+                    println!("No Spf Record.");
+                    Spf::default()
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    Spf::default()
+                }
+            }
         }
     }
-    spf_record
 }
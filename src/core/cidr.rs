@@ -0,0 +1,442 @@
+//! IP networks as ranges of addresses.
+//!
+//! A [`Cidr`] is parsed from the `rrdata` of an `ip4` or `ip6` mechanism using only `core::net`.
+//! This allows networks to be compared when the crate is built without `std`.
+use crate::core::parser;
+use crate::mechanism::{Kind, Mechanism};
+use crate::prelude::*;
+use core::fmt::{Display, Formatter};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// A network given by an address and a prefix length. The address may have host bits set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    /// The network of an `ip4` or `ip6` mechanism.
+    pub(crate) fn from_mechanism(m: &Mechanism<String>) -> Option<Self> {
+        match m.kind() {
+            Kind::IpV4 | Kind::IpV6 => Self::parse(*m.kind(), m.rr_data().as_deref()?),
+            _ => None,
+        }
+    }
+    /// Parse `ip4-network [ ip4-cidr-length ]` or `ip6-network [ ip6-cidr-length ]`.
+    pub(crate) fn parse(kind: Kind, s: &str) -> Option<Self> {
+        parser::parse_network_strict(kind, s).map(|(addr, prefix)| Self { addr, prefix })
+    }
+    pub(crate) fn addr(&self) -> IpAddr {
+        self.addr
+    }
+    pub(crate) fn prefix(&self) -> u8 {
+        self.prefix
+    }
+    pub(crate) fn is_ipv4(&self) -> bool {
+        self.addr.is_ipv4()
+    }
+    /// `true` if the network is a single address.
+    pub(crate) fn is_host(&self) -> bool {
+        self.prefix == max_prefix(&self.addr)
+    }
+    /// `true` if the address has bits set beyond the prefix length.
+    pub(crate) fn has_host_bits(&self) -> bool {
+        to_bits(&self.addr) & self.host_mask() != 0
+    }
+    /// The same network with the host bits cleared.
+    pub(crate) fn network(&self) -> Cidr {
+        Cidr {
+            addr: from_bits(self.is_ipv4(), self.first()),
+            prefix: self.prefix,
+        }
+    }
+    /// The first address of the network.
+    pub(crate) fn first(&self) -> u128 {
+        to_bits(&self.addr) & !self.host_mask()
+    }
+    /// The last address of the network.
+    pub(crate) fn last(&self) -> u128 {
+        self.first() | self.host_mask()
+    }
+    /// `true` if every address of `other` is within this network.
+    pub(crate) fn contains(&self, other: &Cidr) -> bool {
+        self.is_ipv4() == other.is_ipv4()
+            && self.prefix <= other.prefix
+            && self.first() <= other.first()
+            && other.last() <= self.last()
+    }
+    /// `true` if the networks have at least one address in common.
+    pub(crate) fn overlaps(&self, other: &Cidr) -> bool {
+        self.is_ipv4() == other.is_ipv4()
+            && self.first() <= other.last()
+            && other.first() <= self.last()
+    }
+    /// `true` if one network ends immediately before the other begins.
+    pub(crate) fn is_adjacent(&self, other: &Cidr) -> bool {
+        self.is_ipv4() == other.is_ipv4()
+            && (self.last().checked_add(1) == Some(other.first())
+                || other.last().checked_add(1) == Some(self.first()))
+    }
+    fn host_mask(&self) -> u128 {
+        let host_bits = max_prefix(&self.addr) - self.prefix;
+        match host_bits {
+            128 => u128::MAX,
+            n => (1u128 << n) - 1,
+        }
+    }
+}
+
+/// Displays as it would appear in a mechanism. The prefix is left out for a single address.
+impl Display for Cidr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.is_host() {
+            true => write!(f, "{}", self.addr),
+            false => write!(f, "{}/{}", self.addr, self.prefix),
+        }
+    }
+}
+
+pub(crate) fn max_prefix(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn to_bits(addr: &IpAddr) -> u128 {
+    match addr {
+        IpAddr::V4(ip) => u32::from(*ip) as u128,
+        IpAddr::V6(ip) => u128::from(*ip),
+    }
+}
+
+fn from_bits(ipv4: bool, bits: u128) -> IpAddr {
+    match ipv4 {
+        true => IpAddr::V4(Ipv4Addr::from(bits as u32)),
+        false => IpAddr::V6(Ipv6Addr::from(bits)),
+    }
+}
+
+/// The smallest set of networks which covers exactly the same addresses as `networks`.
+///
+/// Every network must be of the same family. The result is ordered by address and has no host
+/// bits set.
+pub(crate) fn aggregate(networks: &[Cidr]) -> Vec<Cidr> {
+    let ipv4 = match networks.first() {
+        Some(cidr) => cidr.is_ipv4(),
+        None => return Vec::new(),
+    };
+    blocks(
+        ipv4,
+        merge(networks.iter().map(|c| (c.first(), c.last())).collect()),
+    )
+}
+
+/// The addresses of `networks` which are not within any of `remove`, as the smallest set of
+/// networks. Networks of both families may be given. ip4 networks are returned first.
+pub(crate) fn difference(networks: &[Cidr], remove: &[Cidr]) -> Vec<Cidr> {
+    let mut result = Vec::new();
+    for ipv4 in [true, false] {
+        let ranges = |networks: &[Cidr]| {
+            merge(
+                networks
+                    .iter()
+                    .filter(|c| c.is_ipv4() == ipv4)
+                    .map(|c| (c.first(), c.last()))
+                    .collect(),
+            )
+        };
+        let remove = ranges(remove);
+        let mut kept = Vec::new();
+        for (first, last) in ranges(networks) {
+            let mut start = Some(first);
+            for &(r_first, r_last) in &remove {
+                let s = match start {
+                    Some(s) if r_first <= last => s,
+                    _ => break,
+                };
+                if r_last < s {
+                    continue;
+                }
+                if r_first > s {
+                    kept.push((s, r_first - 1));
+                }
+                start = match r_last >= last {
+                    true => None,
+                    false => Some(r_last + 1),
+                };
+            }
+            if let Some(s) = start {
+                kept.push((s, last));
+            }
+        }
+        result.extend(blocks(ipv4, kept));
+    }
+    result
+}
+
+// Sort `ranges` and merge those which overlap or are adjacent.
+fn merge(mut ranges: Vec<(u128, u128)>) -> Vec<(u128, u128)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u128, u128)> = Vec::with_capacity(ranges.len());
+    for (first, last) in ranges {
+        match merged.last_mut() {
+            Some(prev) if prev.1 == u128::MAX || first <= prev.1 + 1 => prev.1 = prev.1.max(last),
+            _ => merged.push((first, last)),
+        }
+    }
+    merged
+}
+
+// The smallest set of networks covering the sorted and disjoint `ranges`.
+fn blocks(ipv4: bool, ranges: Vec<(u128, u128)>) -> Vec<Cidr> {
+    let max = if ipv4 { 32 } else { 128 };
+    let mut result = Vec::new();
+    for (mut first, last) in ranges {
+        loop {
+            // The largest block which starts at `first` and ends no later than `last`.
+            let aligned = first.trailing_zeros().min(max);
+            let fits = match (last - first).checked_add(1) {
+                Some(count) => 127 - count.leading_zeros(),
+                None => 128,
+            };
+            let host_bits = aligned.min(fits);
+            result.push(Cidr {
+                addr: from_bits(ipv4, first),
+                prefix: (max - host_bits) as u8,
+            });
+            let block_last = match host_bits {
+                128 => u128::MAX,
+                n => first + ((1u128 << n) - 1),
+            };
+            if block_last >= last {
+                break;
+            }
+            first = block_last + 1;
+        }
+    }
+    result
+}
+
+/// Merge overlapping and adjacent `ip4` and `ip6` mechanisms of `terms` into the smallest set of
+/// networks, without changing which result any address receives.
+///
+/// Networks are only merged with later networks of the same kind and qualifier. A merged network
+/// takes the place of the first network it replaces. A group stops at a mechanism with a
+/// different qualifier which may match some of the addresses that would be moved before it.
+/// Terms after `all` are left unchanged, as are groups which cannot be made smaller.
+#[cfg(any(feature = "builder", test))]
+pub(crate) fn aggregate_terms(terms: &[Mechanism<String>]) -> Vec<Mechanism<String>> {
+    let end = terms
+        .iter()
+        .position(|m| m.kind().is_all())
+        .unwrap_or(terms.len());
+    let networks: Vec<Option<Cidr>> = terms.iter().map(Cidr::from_mechanism).collect();
+    let mut slots: Vec<Vec<Mechanism<String>>> = terms.iter().map(|m| vec![m.clone()]).collect();
+    let mut grouped = vec![false; terms.len()];
+    for start in 0..end {
+        let cidr = match networks[start] {
+            Some(cidr) if !grouped[start] => cidr,
+            _ => continue,
+        };
+        let m = &terms[start];
+        let mut group = vec![start];
+        let mut barriers: Vec<Cidr> = Vec::new();
+        for idx in start + 1..end {
+            let other = &terms[idx];
+            let same_qualifier = other.qualifier() == m.qualifier();
+            match networks[idx] {
+                Some(net) if grouped[idx] || net.is_ipv4() != cidr.is_ipv4() => {}
+                Some(net) if !same_qualifier => barriers.push(net),
+                Some(net) if barriers.iter().any(|b| b.overlaps(&net)) => break,
+                Some(_) => group.push(idx),
+                None if other.kind().is_modifier() || same_qualifier => {}
+                None => break,
+            }
+        }
+        let members: Vec<Cidr> = group.iter().filter_map(|idx| networks[*idx]).collect();
+        let merged = aggregate(&members);
+        for idx in &group {
+            grouped[*idx] = true;
+        }
+        if merged.len() < group.len() {
+            slots[start] = merged
+                .iter()
+                .map(|net| {
+                    Mechanism::generic_inclusive(*m.kind(), *m.qualifier(), Some(net.to_string()))
+                })
+                .collect();
+            for idx in &group[1..] {
+                slots[*idx].clear();
+            }
+        }
+    }
+    slots.into_iter().flatten().collect()
+}
+
+/// Parse a network which is known to be valid. For use with constant tables.
+pub(crate) fn net(s: &str) -> Cidr {
+    let kind = match s.contains(':') {
+        true => Kind::IpV6,
+        false => Kind::IpV4,
+    };
+    Cidr::parse(kind, s).expect("valid network")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range() {
+        let c = net("192.0.2.55/24");
+        assert_eq!(
+            c.first(),
+            u32::from(core::net::Ipv4Addr::new(192, 0, 2, 0)) as u128
+        );
+        assert_eq!(c.last() - c.first(), 255);
+        assert_eq!(net("::/0").last(), u128::MAX);
+        assert_eq!(net("0.0.0.0/0").last(), u32::MAX as u128);
+        assert_eq!(net("2001:db8::1/128").to_string(), "2001:db8::1");
+    }
+    #[test]
+    fn contains() {
+        assert!(net("10.0.0.0/23").contains(&net("10.0.1.0/24")));
+        assert!(!net("10.0.0.0/24").contains(&net("10.0.0.0/23")));
+        // An ip4 network never contains an ip6 network.
+        assert!(!net("0.0.0.0/0").contains(&net("::ffff:10.0.0.0/120")));
+        assert!(!net("10.0.0.0/24").contains(&net("10.0.1.0/24")));
+    }
+    #[test]
+    fn host_bits() {
+        assert!(net("192.0.2.55/24").has_host_bits());
+        assert_eq!(net("192.0.2.55/24").network().to_string(), "192.0.2.0/24");
+        assert!(!net("192.0.2.55").has_host_bits());
+        assert!(!net("0.0.0.0/0").has_host_bits());
+        assert!(net("2001:db8::1/64").has_host_bits());
+        assert_eq!(net("2001:db8::1/64").network(), net("2001:db8::/64"));
+    }
+    #[test]
+    fn overlaps_and_adjacent() {
+        assert!(net("10.0.0.0/23").overlaps(&net("10.0.1.0/24")));
+        assert!(!net("10.0.0.0/24").overlaps(&net("10.0.1.0/24")));
+        assert!(net("10.0.0.0/24").is_adjacent(&net("10.0.1.0/24")));
+        assert!(net("10.0.2.0/24").is_adjacent(&net("10.0.1.0/24")));
+        assert!(!net("10.0.0.0/24").is_adjacent(&net("10.0.2.0/24")));
+        assert!(!net("255.255.255.255").is_adjacent(&net("::/0")));
+    }
+    #[test]
+    fn difference_networks() {
+        let nets = |s: &[&str]| s.iter().map(|s| net(s)).collect::<Vec<_>>();
+        let strings = |c: Vec<Cidr>| c.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            strings(difference(&nets(&["10.0.0.0/22"]), &nets(&["10.0.1.0/24"]))),
+            ["10.0.0.0/24", "10.0.2.0/23"]
+        );
+        assert_eq!(
+            strings(difference(
+                &nets(&["10.0.0.0/24", "2001:db8::/32"]),
+                &nets(&["10.0.0.0/23", "2001:db8::/33"])
+            )),
+            ["2001:db8:8000::/33"]
+        );
+        assert_eq!(
+            strings(difference(
+                &nets(&["0.0.0.0/0"]),
+                &nets(&["128.0.0.0/1", "::/0"])
+            )),
+            ["0.0.0.0/1"]
+        );
+        assert_eq!(
+            strings(difference(&nets(&["::/0"]), &nets(&["::/1"]))),
+            ["8000::/1"]
+        );
+        assert!(difference(&nets(&["10.0.0.7"]), &nets(&["10.0.0.0/29"])).is_empty());
+    }
+    #[test]
+    fn aggregate_networks() {
+        let nets = |s: &[&str]| s.iter().map(|s| net(s)).collect::<Vec<_>>();
+        let strings = |c: Vec<Cidr>| c.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            strings(aggregate(&nets(&["10.0.0.0/24", "10.0.0.0/23"]))),
+            ["10.0.0.0/23"]
+        );
+        assert_eq!(
+            strings(aggregate(&nets(&[
+                "10.0.3.0/24",
+                "10.0.1.0/24",
+                "10.0.2.0/24",
+                "10.0.0.7/24"
+            ]))),
+            ["10.0.0.0/22"]
+        );
+        assert_eq!(
+            strings(aggregate(&nets(&[
+                "10.0.1.0/24",
+                "10.0.2.0/24",
+                "10.0.3.1"
+            ]))),
+            ["10.0.1.0/24", "10.0.2.0/24", "10.0.3.1"]
+        );
+        assert_eq!(
+            strings(aggregate(&nets(&["0.0.0.0/1", "128.0.0.0/1"]))),
+            ["0.0.0.0/0"]
+        );
+        assert_eq!(
+            strings(aggregate(&nets(&["::/1", "8000::/1", "::1"]))),
+            ["::/0"]
+        );
+        assert_eq!(
+            strings(aggregate(&nets(&["2001:db8::/48", "2001:db8:1::/48"]))),
+            ["2001:db8::/47"]
+        );
+    }
+    #[test]
+    fn aggregate_mechanisms() {
+        let aggregated = |s: &str| {
+            let spf: crate::Spf<String> = format!("v=spf1 {}", s).parse().unwrap();
+            aggregate_terms(crate::lint::Record::from(&spf).terms())
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!(
+            aggregated("ip4:10.0.0.0/24 a ip4:10.0.0.0/23 ip6:2001:db8::1 -all ip4:10.0.2.0/23"),
+            "ip4:10.0.0.0/23 a ip6:2001:db8::1 -all ip4:10.0.2.0/23"
+        );
+        // Qualifiers are kept apart.
+        assert_eq!(
+            aggregated("ip4:10.0.0.0/24 ~ip4:10.0.1.0/24 -all"),
+            "ip4:10.0.0.0/24 ~ip4:10.0.1.0/24 -all"
+        );
+        // 10.0.1.5 must still fail.
+        assert_eq!(
+            aggregated(
+                "ip4:10.0.0.0/24 -ip4:10.0.1.5 ip4:10.0.1.0/24 ip4:10.0.2.0/24 ip4:10.0.3.0/24"
+            ),
+            "ip4:10.0.0.0/24 -ip4:10.0.1.5 ip4:10.0.1.0/24 ip4:10.0.2.0/23"
+        );
+        // A mechanism with a different qualifier may match any address.
+        assert_eq!(
+            aggregated("ip4:10.0.0.0/24 -include:a.com ip4:10.0.1.0/24"),
+            "ip4:10.0.0.0/24 -include:a.com ip4:10.0.1.0/24"
+        );
+        assert_eq!(
+            aggregated("ip4:10.0.0.0/24 -ip4:192.0.2.1 include:a.com ip4:10.0.1.0/24 ~all"),
+            "ip4:10.0.0.0/23 -ip4:192.0.2.1 include:a.com ~all"
+        );
+    }
+    #[test]
+    fn from_mechanism() {
+        let m: Mechanism<String> = Mechanism::generic_inclusive(
+            Kind::IpV4,
+            Default::default(),
+            Some("192.0.2.0/24".to_string()),
+        );
+        assert_eq!(Cidr::from_mechanism(&m), Some(net("192.0.2.0/24")));
+        let a: Mechanism<String> = "a:example.com/24".parse().unwrap();
+        assert_eq!(Cidr::from_mechanism(&a), None);
+    }
+}
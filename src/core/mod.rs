@@ -1,8 +1,10 @@
-use crate::spf::mechanism::{Mechanism, Qualifier};
+use crate::prelude::*;
+use crate::spf::mechanism::Mechanism;
+#[cfg(feature = "std")]
 use ipnetwork::IpNetwork;
-use lazy_static::lazy_static;
-use regex::Regex;
-pub(crate) mod spf_regex;
+pub(crate) mod cidr;
+pub(crate) mod parser;
+pub(crate) mod txt;
 
 /// This is the maximum number of characters that an Spf Record can store.
 pub(crate) const MAX_SPF_STRING_LENGTH: usize = 512;
@@ -11,119 +13,20 @@ pub(crate) const DNS_LOOKUP_LIMIT: usize = 10;
 /// Spf Versions
 pub(crate) const SPF1: &str = "v=spf1";
 
-/// SPF2 is only for an initial check and we are just checking that the string
-/// starts with spf2.0
-#[allow(dead_code)]
-pub(crate) const SPF2: &str = "spf2.0";
-pub(crate) const SPF2_PRA: &str = "spf2.0/pra";
-pub(crate) const SPF2_MFROM: &str = "spf2.0/mfrom";
-pub(crate) const SPF2_PRA_MFROM: &str = "spf2.0/pra,mfrom";
-pub(crate) const SPF2_MFROM_PRA: &str = "spf2.0/mfrom,pra";
-
-/// Mechanism Const Slices
-pub(crate) const REDIRECT: &str = "redirect=";
-pub(crate) const INCLUDE: &str = "include:";
-pub(crate) const ALL: &str = "all";
-pub(crate) const IP4: &str = "ip4:";
-pub(crate) const IP6: &str = "ip6:";
-
 /// Check for white space in spf record.
 ///
 /// Return true if there is a space at the end of the string or
 /// if there are two consecutive spaces within the string.
 pub(crate) fn spf_check_whitespace(s: &str) -> bool {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"\s{2,}").unwrap();
-        static ref ENDING_SPACE: Regex = Regex::new(r"\s$").unwrap();
-    }
-    RE.is_match(s) || ENDING_SPACE.is_match(s)
-}
-
-pub(crate) fn char_to_qualifier(c: char) -> Qualifier {
-    match c {
-        '+' => Qualifier::Pass,
-        '-' => Qualifier::Fail,
-        '~' => Qualifier::SoftFail,
-        '?' => Qualifier::Neutral,
-        _ => Qualifier::Pass,
-    }
-}
-
-#[doc(hidden)]
-// Check if the initial character in the string `record` matches `c`
-// If they do no match then return the initial character
-// if c matches first character of record, we can `+`, a blank modifier equates to `+`
-pub(crate) fn return_and_remove_qualifier(record: &str, c: char) -> (Qualifier, &str) {
-    // Returns a tuple of (qualifier, &str)
-    // &str will have had the qualifier character removed if it existed. The &str will be unchanged
-    // if the qualifier was not present
-    if c != record.chars().next().unwrap() {
-        // qualifier exists. return tuple of qualifier and `record` with qualifier removed.
-        (
-            char_to_qualifier(record.chars().next().unwrap()),
-            remove_qualifier(record),
-        )
-    } else {
-        // qualifier does not exist, default to `+` and return unmodified `record`
-        (Qualifier::Pass, record)
-    }
-}
-
-#[cfg(test)]
-mod return_and_remove_qualifier_tests {
-    use super::*;
-    #[test]
-    fn return_and_remove_qualifier_no_qualifier() {
-        let source = "no prefix";
-        let (c, new_str) = return_and_remove_qualifier(source, 'n');
-        assert_eq!(Qualifier::Pass, c);
-        assert_eq!(source, new_str);
-    }
-
-    #[test]
-    fn return_and_remove_qualifier_pass() {
-        let source = "+prefix";
-        let (c, new_str) = return_and_remove_qualifier(source, 'n');
-        assert_eq!(Qualifier::Pass, c);
-        assert_eq!("prefix", new_str);
-    }
-
-    #[test]
-    fn return_and_remove_qualifier_fail() {
-        let source = "-prefix";
-        let (c, new_str) = return_and_remove_qualifier(source, 'n');
-        assert_eq!(Qualifier::Fail, c);
-        assert_eq!("prefix", new_str);
-    }
-
-    #[test]
-    fn return_and_remove_qualifier_softfail() {
-        let source = "~prefix";
-        let (c, new_str) = return_and_remove_qualifier(source, 'n');
-        assert_eq!(Qualifier::SoftFail, c);
-        assert_eq!("prefix", new_str);
-    }
-
-    #[test]
-    fn return_and_remove_qualifier_neutral() {
-        let source = "?prefix";
-        let (c, new_str) = return_and_remove_qualifier(source, 'n');
-        assert_eq!(Qualifier::Neutral, c);
-        assert_eq!("prefix", new_str);
+    let mut previous = false;
+    for c in s.chars() {
+        let current = c.is_whitespace();
+        if previous && current {
+            return true;
+        }
+        previous = current;
     }
-}
-#[doc(hidden)]
-pub(crate) fn remove_qualifier(record: &str) -> &str {
-    // Remove leading (+,-,~,?) character and return an updated str
-    let mut chars = record.chars();
-    chars.next();
-    chars.as_str()
-}
-#[test]
-fn test_remove_qualifier() {
-    let test_str = "abc";
-    let result = remove_qualifier(test_str);
-    assert_eq!(result, "bc");
+    previous
 }
 
 // builds a string representation of the mechanisms stored in the Vec<Mechanism<String>>
@@ -136,6 +39,7 @@ pub(crate) fn build_spf_str(str: &[Mechanism<String>]) -> String {
     partial_spf
 }
 
+#[cfg(feature = "std")]
 #[allow(dead_code)]
 // builds a string representation of the mechanisms stored in the Vec<Mechanism<IpNetwork>>
 pub(crate) fn build_spf_str_from_ip(str: &[Mechanism<IpNetwork>]) -> String {
@@ -148,21 +52,17 @@ pub(crate) fn build_spf_str_from_ip(str: &[Mechanism<IpNetwork>]) -> String {
 
 #[cfg(feature = "strict-dns")]
 pub(crate) mod dns {
+    use crate::spf::mechanism::DomainSpec;
     use addr::parse_dns_name;
 
-    /// If there is a string preceding the '/' character. This string should be returned.
+    /// Checks the domain of a `domain-spec` using [is_dns_suffix_valid].
     ///
-    /// Example:
-    /// bogus.com/24
-    ///
-    /// Returns "bogus.com"
-    /// If there is no '/' or the string starts with a '/' than an unmodified `str`
-    /// is returned.
-    pub(crate) fn get_domain_before_slash(s: &str) -> &str {
-        if !s.starts_with('/') && s.contains('/') {
-            s.split('/').next().unwrap()
-        } else {
-            s
+    /// Returns `true` if there is no domain, or if it contains macros. These are only known
+    /// at evaluation time and can not be checked.
+    pub(crate) fn is_domain_spec_valid(spec: Option<DomainSpec>) -> bool {
+        match spec.as_ref().and_then(DomainSpec::as_literal) {
+            Some(name) => is_dns_suffix_valid(name),
+            None => true,
         }
     }
 
@@ -184,6 +84,7 @@ pub(crate) mod dns {
     #[cfg(feature = "strict-dns")]
     pub(crate) mod warn {
         use crate::core::dns::is_dns_suffix_valid;
+        use crate::prelude::*;
 
         #[allow(dead_code)]
         pub(crate) fn check_for_dns_warning(warning_vec: &mut Vec<String>, name: &str) {
@@ -193,42 +94,40 @@ pub(crate) mod dns {
         }
         #[cfg(test)]
         mod test {
-            use crate::core::dns::{get_domain_before_slash, is_dns_suffix_valid};
+            use crate::core::dns::{is_dns_suffix_valid, is_domain_spec_valid};
 
             #[test]
-            fn start_with_slash() {
-                let input = "/24";
-                assert_eq!(get_domain_before_slash(input), "/24");
+            fn domain_spec_without_domain() {
+                assert!(is_domain_spec_valid(None));
             }
             #[test]
-            fn domain_contains_slash() {
-                let input = "test.com/24";
-                assert_eq!(get_domain_before_slash(input), "test.com");
+            fn domain_spec_with_macro() {
+                assert!(is_domain_spec_valid("%{d}.t.xx".parse().ok()));
             }
             #[test]
-            fn domain_without_slash() {
-                let input = "test.com";
-                assert_eq!(get_domain_before_slash(input), "test.com");
+            fn domain_spec_literal() {
+                assert!(is_domain_spec_valid("test.com".parse().ok()));
+                assert!(!is_domain_spec_valid("t.xx".parse().ok()));
             }
             #[test]
             fn invalid_tld() {
-                assert_eq!(is_dns_suffix_valid("t.xx"), false);
+                assert!(!is_dns_suffix_valid("t.xx"));
             }
             #[test]
             fn valid_domain() {
-                assert_eq!(is_dns_suffix_valid("test.com"), true);
+                assert!(is_dns_suffix_valid("test.com"));
             }
             #[test]
             fn valid_a() {
-                assert_eq!(is_dns_suffix_valid("a"), true);
+                assert!(is_dns_suffix_valid("a"));
             }
             #[test]
             fn valid_mx() {
-                assert_eq!(is_dns_suffix_valid("mx"), true);
+                assert!(is_dns_suffix_valid("mx"));
             }
             #[test]
             fn valid_ptr() {
-                assert_eq!(is_dns_suffix_valid("ptr"), true);
+                assert!(is_dns_suffix_valid("ptr"));
             }
         }
     }
@@ -236,3 +135,25 @@ pub(crate) mod dns {
         mod test {}
     }
 }
+
+#[cfg(test)]
+mod whitespace {
+    use super::spf_check_whitespace;
+
+    #[test]
+    fn valid() {
+        assert!(!spf_check_whitespace("v=spf1 a mx -all"));
+        assert!(!spf_check_whitespace(""));
+    }
+    #[test]
+    fn consecutive() {
+        assert!(spf_check_whitespace("v=spf1  a -all"));
+        assert!(spf_check_whitespace("v=spf1 \ta -all"));
+        assert!(spf_check_whitespace("v=spf1\u{a0} a -all"));
+    }
+    #[test]
+    fn trailing() {
+        assert!(spf_check_whitespace("v=spf1 -all "));
+        assert!(spf_check_whitespace("v=spf1 -all\n"));
+    }
+}
@@ -0,0 +1,942 @@
+//! A parser for Spf records which follows the ABNF given in
+//! [RFC7208 Section 12](https://datatracker.ietf.org/doc/html/rfc7208#section-12).
+//!
+//! Mechanism and modifier names are matched without regard to case, as required by
+//! [Section 4.6.1](https://datatracker.ietf.org/doc/html/rfc7208#section-4.6.1).
+//! Every term of a record is handled by [`parse_term`], which is the single point where
+//! strings become a [`ParsedMechanism`].
+use crate::prelude::*;
+use crate::spf::mechanism::{
+    DomainSpec, DualCidr, Kind, MacroExpand, Mechanism, MechanismError, NetworkError,
+    ParsedMechanism, Qualifier, Segment,
+};
+use crate::spf::version::{Scope, Version};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(feature = "std")]
+use ipnetwork::IpNetwork;
+
+pub(crate) const SPF2_PREFIX: &str = "spf2.0/";
+
+/// Parse a complete version term.
+///
+/// ```text
+/// version = "v=spf1"
+/// ```
+/// `spf2.0/` followed by a comma separated list of distinct scopes (`pra`, `mfrom`) is also
+/// accepted. See [RFC4406 Section 3](https://datatracker.ietf.org/doc/html/rfc4406#section-3)
+pub(crate) fn parse_version(s: &str) -> Option<Version> {
+    if s.eq_ignore_ascii_case(crate::core::SPF1) {
+        return Some(Version::V1);
+    }
+    let prefix = s.get(..SPF2_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(SPF2_PREFIX) {
+        return None;
+    }
+    let scopes = s[SPF2_PREFIX.len()..]
+        .split(',')
+        .map(|scope| match scope {
+            _ if scope.eq_ignore_ascii_case(Scope::Pra.as_str()) => Some(Scope::Pra),
+            _ if scope.eq_ignore_ascii_case(Scope::MFrom.as_str()) => Some(Scope::MFrom),
+            _ => None,
+        })
+        .collect::<Option<Vec<Scope>>>()?;
+    Version::v2(&scopes).ok()
+}
+
+/// Returns `true` if `s` is a complete version term. See [`parse_version`].
+pub(crate) fn is_version(s: &str) -> bool {
+    parse_version(s).is_some()
+}
+
+/// Splits a record into its version and the terms which follow it.
+///
+/// ```text
+/// record = version terms *SP
+/// terms  = *( 1*SP ( directive / modifier ) )
+/// ```
+/// The version is not checked. See [`is_version`].
+pub(crate) fn split_record(s: &str) -> (&str, impl Iterator<Item = &str>) {
+    let (version, terms) = s.split_once(' ').unwrap_or((s, ""));
+    (version, terms.split(' ').filter(|t| !t.is_empty()))
+}
+
+/// Parse a single `directive` or `modifier`.
+///
+/// ```text
+/// directive = [ qualifier ] mechanism
+/// modifier  = redirect / explanation / unknown-modifier
+/// ```
+pub(crate) fn parse_term(term: &str) -> Result<ParsedMechanism, MechanismError> {
+    let invalid = || MechanismError::InvalidMechanismFormat(term.to_string());
+
+    if let Some((name, value)) = split_modifier(term) {
+        let kind = if name.eq_ignore_ascii_case("redirect") {
+            Kind::Redirect
+        } else if name.eq_ignore_ascii_case("exp") {
+            Kind::Exp
+        } else {
+            // unknown-modifier = name "=" macro-string
+            if !is_macro_string(value) {
+                return Err(invalid());
+            }
+            return Ok(ParsedMechanism::TXT(Mechanism::generic_inclusive(
+                Kind::UnknownModifier,
+                Qualifier::Pass,
+                Some(term.to_string()),
+            )));
+        };
+        if !is_domain_spec(value) {
+            return Err(invalid());
+        }
+        let m = Mechanism::generic_inclusive(kind, Qualifier::Pass, Some(value.to_string()));
+        return Ok(ParsedMechanism::TXT(check_domain(m)?));
+    }
+
+    let (qualifier, rest) = split_qualifier(term);
+    let name_len = rest
+        .bytes()
+        .take_while(|b| b.is_ascii_alphanumeric())
+        .count();
+    let (name, args) = rest.split_at(name_len);
+    let kind = mechanism_kind(name).ok_or_else(invalid)?;
+
+    let rrdata = match (kind, args.as_bytes().first()) {
+        // all = "all"
+        (Kind::All, None) => None,
+        // ip4 = "ip4" ":" ip4-network [ ip4-cidr-length ]
+        // ip6 = "ip6" ":" ip6-network [ ip6-cidr-length ]
+        (Kind::IpV4 | Kind::IpV6, Some(b':')) => {
+            let ip = parse_network(kind, &args[1..])?;
+            let m = Mechanism::generic_inclusive(kind, qualifier, Some(ip));
+            #[cfg(feature = "std")]
+            return Ok(ParsedMechanism::IP(m));
+            #[cfg(not(feature = "std"))]
+            return Ok(ParsedMechanism::TXT(m));
+        }
+        // a   = "a" [ ":" domain-spec ] [ dual-cidr-length ]
+        // mx  = "mx" [ ":" domain-spec ] [ dual-cidr-length ]
+        // ptr = "ptr" [ ":" domain-spec ]
+        (Kind::A | Kind::MX | Kind::Ptr, None) => None,
+        (Kind::A | Kind::MX, Some(b'/')) => Some(args),
+        // A domain-spec is required after the ':'
+        (Kind::A | Kind::MX, Some(b':')) if args[1..].starts_with('/') => return Err(invalid()),
+        // include = "include" ":" domain-spec
+        // exists  = "exists" ":" domain-spec
+        (Kind::A | Kind::MX | Kind::Ptr | Kind::Include | Kind::Exists, Some(b':')) => {
+            Some(&args[1..])
+        }
+        _ => return Err(invalid()),
+    };
+    if rrdata.is_some_and(|rrdata| !is_valid_rrdata(kind, rrdata)) {
+        return Err(invalid());
+    }
+    let m = Mechanism::generic_inclusive(kind, qualifier, rrdata.map(str::to_string));
+    Ok(ParsedMechanism::TXT(check_domain(m)?))
+}
+
+/// Check that `rrdata` is valid for a Mechanism or Modifier of `kind`. This is the form
+/// stored in `Mechanism<String>`, so `A` and `MX` may hold only a `dual-cidr-length`.
+pub(crate) fn is_valid_rrdata(kind: Kind, rrdata: &str) -> bool {
+    match kind {
+        Kind::Include | Kind::Exists | Kind::Ptr | Kind::Redirect | Kind::Exp => {
+            is_domain_spec(rrdata)
+        }
+        Kind::A | Kind::MX if rrdata.starts_with('/') => is_dual_cidr_length(rrdata),
+        Kind::A | Kind::MX => {
+            let (domain, cidr) = split_dual_cidr_length(rrdata);
+            is_domain_spec(domain) && is_dual_cidr_length(cidr)
+        }
+        Kind::UnknownModifier => split_modifier(rrdata).is_some_and(|(name, value)| {
+            !name.eq_ignore_ascii_case("redirect")
+                && !name.eq_ignore_ascii_case("exp")
+                && is_macro_string(value)
+        }),
+        Kind::All | Kind::IpV4 | Kind::IpV6 => false,
+    }
+}
+
+// When `strict-dns` is enabled the domain held in `rrdata` must have a known suffix.
+fn check_domain(m: Mechanism<String>) -> Result<Mechanism<String>, MechanismError> {
+    #[cfg(feature = "strict-dns")]
+    {
+        if !crate::core::dns::is_domain_spec_valid(m.domain_spec()) {
+            return Err(MechanismError::InvalidDomainHost(m.raw()));
+        }
+    }
+    Ok(m)
+}
+
+fn split_qualifier(term: &str) -> (Qualifier, &str) {
+    // qualifier = "+" / "-" / "?" / "~"
+    let qualifier = match term.as_bytes().first() {
+        Some(b'+') => Qualifier::Pass,
+        Some(b'-') => Qualifier::Fail,
+        Some(b'~') => Qualifier::SoftFail,
+        Some(b'?') => Qualifier::Neutral,
+        _ => return (Qualifier::Pass, term),
+    };
+    (qualifier, &term[1..])
+}
+
+fn mechanism_kind(name: &str) -> Option<Kind> {
+    [
+        Kind::All,
+        Kind::Include,
+        Kind::A,
+        Kind::MX,
+        Kind::Ptr,
+        Kind::IpV4,
+        Kind::IpV6,
+        Kind::Exists,
+    ]
+    .iter()
+    .copied()
+    .find(|kind| {
+        kind.as_str()
+            .trim_end_matches(':')
+            .eq_ignore_ascii_case(name)
+    })
+}
+
+/// Splits `name "=" value` if `term` is a modifier.
+///
+/// ```text
+/// name = ALPHA *( ALPHA / DIGIT / "-" / "_" / "." )
+/// ```
+fn split_modifier(term: &str) -> Option<(&str, &str)> {
+    let bytes = term.as_bytes();
+    if !bytes.first()?.is_ascii_alphabetic() {
+        return None;
+    }
+    let name_len = bytes
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+        .count();
+    match bytes.get(name_len) {
+        Some(b'=') => Some((&term[..name_len], &term[name_len + 1..])),
+        _ => None,
+    }
+}
+
+/// Parse a macro-string into its segments. Returns `None` if `s` is not a valid macro-string.
+///
+/// ```text
+/// macro-string  = *( macro-expand / macro-literal )
+/// macro-expand  = ( "%{" macro-letter transformers *delimiter "}" )
+///                 / "%%" / "%_" / "%-"
+/// macro-literal = %x21-24 / %x26-7E
+/// macro-letter  = "s" / "l" / "o" / "d" / "i" / "p" / "h" /
+///                 "c" / "r" / "t" / "v"
+/// transformers  = *DIGIT [ "r" ]
+/// delimiter     = "." / "-" / "+" / "," / "/" / "_" / "="
+/// ```
+pub(crate) fn parse_macro_string(s: &str) -> Option<Vec<Segment>> {
+    let mut segments = Vec::new();
+    scan_macro_string(s, |segment| segments.push(segment.to_owned()))?;
+    Some(segments)
+}
+
+pub(crate) fn is_macro_string(s: &str) -> bool {
+    scan_macro_string(s, |_| {}).is_some()
+}
+
+// A segment of a macro-string which borrows from the source. Validation only needs these,
+// so no allocation takes place until a `DomainSpec` is requested.
+enum RawSegment<'a> {
+    Literal(&'a str),
+    Escape(u8),
+    Macro {
+        letter: u8,
+        digits: Option<u32>,
+        reverse: bool,
+        delimiters: &'a str,
+    },
+}
+
+impl RawSegment<'_> {
+    fn to_owned(&self) -> Segment {
+        match self {
+            RawSegment::Literal(s) => Segment::Literal(s.to_string()),
+            RawSegment::Escape(c) => Segment::Escape(*c as char),
+            RawSegment::Macro {
+                letter,
+                digits,
+                reverse,
+                delimiters,
+            } => Segment::Macro(MacroExpand::new(
+                *letter as char,
+                *digits,
+                *reverse,
+                delimiters,
+            )),
+        }
+    }
+}
+
+// Scans `s` in a single pass, handing each segment to `emit`.
+// Returns `None` if `s` is not a valid macro-string.
+fn scan_macro_string<'a>(s: &'a str, mut emit: impl FnMut(RawSegment<'a>)) -> Option<()> {
+    let bytes = s.as_bytes();
+    let mut literal_start = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                if let Some(start) = literal_start.take() {
+                    emit(RawSegment::Literal(&s[start..i]));
+                }
+                i += 1;
+                match bytes.get(i)? {
+                    c @ (b'%' | b'_' | b'-') => {
+                        emit(RawSegment::Escape(*c));
+                        i += 1;
+                    }
+                    b'{' => {
+                        i += 1;
+                        let letter = *bytes.get(i)?;
+                        if !b"slodiphcrtv".contains(&letter.to_ascii_lowercase()) {
+                            return None;
+                        }
+                        i += 1;
+                        let digits_start = i;
+                        while bytes.get(i)?.is_ascii_digit() {
+                            i += 1;
+                        }
+                        let digits = match &s[digits_start..i] {
+                            "" => None,
+                            digits => Some(digits.parse::<u32>().ok()?),
+                        };
+                        let reverse = bytes.get(i)?.eq_ignore_ascii_case(&b'r');
+                        if reverse {
+                            i += 1;
+                        }
+                        let delimiters_start = i;
+                        while b".-+,/_=".contains(bytes.get(i)?) {
+                            i += 1;
+                        }
+                        if *bytes.get(i)? != b'}' {
+                            return None;
+                        }
+                        emit(RawSegment::Macro {
+                            letter,
+                            digits,
+                            reverse,
+                            delimiters: &s[delimiters_start..i],
+                        });
+                        i += 1;
+                    }
+                    _ => return None,
+                }
+            }
+            0x21..=0x7e => {
+                literal_start.get_or_insert(i);
+                i += 1;
+            }
+            _ => return None,
+        }
+    }
+    if let Some(start) = literal_start {
+        emit(RawSegment::Literal(&s[start..]));
+    }
+    Some(())
+}
+
+/// ```text
+/// domain-spec = macro-string domain-end
+/// domain-end  = ( "." toplabel [ "." ] ) / macro-expand
+/// ```
+pub(crate) fn parse_domain_spec(s: &str) -> Option<DomainSpec> {
+    match is_domain_spec(s) {
+        true => parse_macro_string(s).map(DomainSpec::from_segments),
+        false => None,
+    }
+}
+
+pub(crate) fn is_domain_spec(s: &str) -> bool {
+    let mut last = None;
+    if scan_macro_string(s, |segment| last = Some(segment)).is_none() {
+        return false;
+    }
+    match last {
+        None => false,
+        Some(RawSegment::Literal(literal)) => {
+            let literal = literal.strip_suffix('.').unwrap_or(literal);
+            literal
+                .rfind('.')
+                .is_some_and(|idx| is_toplabel(&literal[idx + 1..]))
+        }
+        // Ends with a macro-expand
+        Some(RawSegment::Macro { .. } | RawSegment::Escape(_)) => true,
+    }
+}
+
+/// ```text
+/// toplabel = ( *alphanum ALPHA *alphanum ) /
+///            ( 1*alphanum "-" *( alphanum / "-" ) alphanum )
+/// ```
+fn is_toplabel(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    match (bytes.first(), bytes.last()) {
+        (Some(first), Some(last))
+            if first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric() =>
+        {
+            bytes
+                .iter()
+                .all(|b| b.is_ascii_alphanumeric() || *b == b'-')
+                && (bytes.contains(&b'-') || bytes.iter().any(u8::is_ascii_alphabetic))
+        }
+        _ => false,
+    }
+}
+
+/// ```text
+/// ip4-cidr-length  = "/" ("0" / %x31-39 0*1DIGIT)
+/// ip6-cidr-length  = "/" ("0" / %x31-39 0*2DIGIT)
+/// ```
+/// The value must also be a valid prefix length. (32 and 128 respectively)
+fn is_cidr_length(s: &str, max: u8) -> bool {
+    let max_digits = if max > 99 { 3 } else { 2 };
+    !s.is_empty()
+        && s.len() <= max_digits
+        && s.bytes().all(|b| b.is_ascii_digit())
+        && (s == "0" || !s.starts_with('0'))
+        && s.parse::<u8>().is_ok_and(|len| len <= max)
+}
+
+/// ```text
+/// dual-cidr-length = [ ip4-cidr-length ] [ "/" ip6-cidr-length ]
+/// ```
+pub(crate) fn parse_dual_cidr_length(s: &str) -> Option<DualCidr> {
+    let (ip4, ip6) = match s.find("//") {
+        Some(idx) => (&s[..idx], Some(&s[idx + 2..])),
+        None => (s, None),
+    };
+    let ip4 = match ip4 {
+        "" => None,
+        ip4 => Some(parse_cidr_length(ip4.strip_prefix('/')?, 32)?),
+    };
+    let ip6 = match ip6 {
+        None => None,
+        Some(ip6) => Some(parse_cidr_length(ip6, 128)?),
+    };
+    DualCidr::new(ip4, ip6).ok()
+}
+
+fn parse_cidr_length(s: &str, max: u8) -> Option<u8> {
+    match is_cidr_length(s, max) {
+        true => s.parse().ok(),
+        false => None,
+    }
+}
+
+pub(crate) fn is_dual_cidr_length(s: &str) -> bool {
+    parse_dual_cidr_length(s).is_some()
+}
+
+/// Split the trailing `dual-cidr-length` from a domain-spec. The cidr part is empty when
+/// it is not present.
+pub(crate) fn split_dual_cidr_length(s: &str) -> (&str, &str) {
+    let mut end = s.len();
+    if let Some(idx) = s.rfind("//") {
+        if is_cidr_length(&s[idx + 2..], 128) {
+            end = idx;
+        }
+    }
+    if let Some(idx) = s[..end].rfind('/') {
+        if is_cidr_length(&s[idx + 1..end], 32) {
+            end = idx;
+        }
+    }
+    s.split_at(end)
+}
+
+/// ```text
+/// ip4-network = qnum "." qnum "." qnum "." qnum
+/// ip6-network = <as per [RFC4291], section 2.2>
+/// ```
+#[cfg(feature = "std")]
+fn parse_network(kind: Kind, s: &str) -> Result<IpNetwork, MechanismError> {
+    if let Some((addr, prefix)) = parse_network_strict(kind, s) {
+        return Ok(IpNetwork::new(addr, prefix)?);
+    }
+    // The remainder determines which error is returned.
+    let ip = s.parse::<IpNetwork>()?;
+    if ip.is_ipv4() && !kind.is_ip_v4() {
+        return Err(MechanismError::NotIP6Network(ip.to_string()));
+    }
+    if ip.is_ipv6() && !kind.is_ip_v6() {
+        return Err(MechanismError::NotIP4Network(ip.to_string()));
+    }
+    let (addr, len) = match s.split_once('/') {
+        Some((addr, len)) => (addr, Some(len)),
+        None => (s, None),
+    };
+    let valid = match kind {
+        Kind::IpV4 => addr.parse::<Ipv4Addr>().is_ok() && len.is_none_or(|l| is_cidr_length(l, 32)),
+        _ => addr.parse::<Ipv6Addr>().is_ok() && len.is_none_or(|l| is_cidr_length(l, 128)),
+    };
+    match valid {
+        true => Ok(ip),
+        false => Err(MechanismError::InvalidIPNetwork(
+            NetworkError::invalid_addr(s),
+        )),
+    }
+}
+
+/// Without `std` the network is kept as the string `Mechanism<IpNetwork>` would give for it.
+/// The prefix is left out when it covers a single address.
+#[cfg(not(feature = "std"))]
+fn parse_network(kind: Kind, s: &str) -> Result<String, MechanismError> {
+    if let Some((addr, prefix)) = parse_network_strict(kind, s) {
+        return Ok(match (addr, prefix) {
+            (IpAddr::V4(_), 32) | (IpAddr::V6(_), 128) => addr.to_string(),
+            _ => format!("{}/{}", addr, prefix),
+        });
+    }
+    // A network of the other family determines which error is returned.
+    match kind {
+        Kind::IpV4 => match parse_network_strict(Kind::IpV6, s) {
+            Some((addr, prefix)) => Err(MechanismError::NotIP4Network(format!(
+                "{}/{}",
+                addr, prefix
+            ))),
+            None => Err(MechanismError::InvalidIPNetwork(
+                NetworkError::invalid_addr(s),
+            )),
+        },
+        _ => match parse_network_strict(Kind::IpV4, s) {
+            Some((addr, prefix)) => Err(MechanismError::NotIP6Network(format!(
+                "{}/{}",
+                addr, prefix
+            ))),
+            None => Err(MechanismError::InvalidIPNetwork(
+                NetworkError::invalid_addr(s),
+            )),
+        },
+    }
+}
+
+// Parses the address a single time. `None` if `s` is not a network of the family of `kind`.
+pub(crate) fn parse_network_strict(kind: Kind, s: &str) -> Option<(IpAddr, u8)> {
+    let (addr, len) = match s.split_once('/') {
+        Some((addr, len)) => (addr, Some(len)),
+        None => (s, None),
+    };
+    let (addr, max): (IpAddr, u8) = match kind {
+        Kind::IpV4 => (addr.parse::<Ipv4Addr>().ok()?.into(), 32),
+        _ => (addr.parse::<Ipv6Addr>().ok()?.into(), 128),
+    };
+    let prefix = match len {
+        None => max,
+        Some(len) => parse_cidr_length(len, max)?,
+    };
+    Some((addr, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn txt(s: &str) -> Mechanism<String> {
+        parse_term(s).unwrap().txt()
+    }
+
+    mod version {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            for v in [
+                "v=spf1",
+                "V=SPF1",
+                "spf2.0/pra",
+                "spf2.0/mfrom",
+                "spf2.0/pra,mfrom",
+                "SPF2.0/MFROM,PRA",
+            ] {
+                assert!(is_version(v), "{}", v);
+            }
+        }
+        #[test]
+        fn invalid() {
+            for v in [
+                "v=spf10",
+                "v=spf",
+                "v=spf1a",
+                "spf2.0",
+                "spf2.0/",
+                "spf2.0/foo",
+                "spf2.0/pra,",
+                "spf2.0/pra,pra",
+                "spf2.0/pra mfrom",
+            ] {
+                assert!(!is_version(v), "{}", v);
+            }
+        }
+        #[test]
+        fn record() {
+            let (v, terms) = split_record("v=spf1  a  -all ");
+            assert_eq!(v, "v=spf1");
+            assert_eq!(terms.collect::<Vec<_>>(), ["a", "-all"]);
+            assert!(!is_version(split_record("v=spf10 -all").0));
+            assert!(!is_version(split_record(" v=spf1 -all").0));
+            assert!(!is_version(split_record("v=spf1\t-all").0));
+        }
+    }
+
+    mod qualifier {
+        use super::*;
+
+        #[test]
+        fn all_qualifiers() {
+            assert_eq!(txt("all").qualifier(), &Qualifier::Pass);
+            assert_eq!(txt("+all").qualifier(), &Qualifier::Pass);
+            assert_eq!(txt("-all").qualifier(), &Qualifier::Fail);
+            assert_eq!(txt("~all").qualifier(), &Qualifier::SoftFail);
+            assert_eq!(txt("?all").qualifier(), &Qualifier::Neutral);
+        }
+        #[test]
+        fn modifiers_have_no_qualifier() {
+            assert!(parse_term("-redirect=_spf.example.com").is_err());
+            assert!(parse_term("?exp=explain.example.com").is_err());
+        }
+    }
+
+    mod case_insensitive {
+        use super::*;
+
+        #[test]
+        fn mechanisms() {
+            let m = txt("INCLUDE:_spf.Example.com");
+            assert_eq!(m.kind(), &Kind::Include);
+            assert_eq!(m.to_string(), "include:_spf.Example.com");
+            assert_eq!(txt("-ALL").to_string(), "-all");
+            assert_eq!(txt("Mx:example.com/24").to_string(), "mx:example.com/24");
+            assert!(parse_term("IP4:192.0.2.0/24").unwrap().is_network());
+        }
+        #[test]
+        fn modifiers() {
+            assert_eq!(txt("REDIRECT=_spf.example.com").kind(), &Kind::Redirect);
+            assert_eq!(txt("Exp=explain.example.com").kind(), &Kind::Exp);
+        }
+        #[test]
+        fn macros() {
+            assert_eq!(
+                txt("exists:%{IR}.%{l1R}._spf.example.com").kind(),
+                &Kind::Exists
+            );
+        }
+    }
+
+    mod dispatch {
+        use super::*;
+
+        #[test]
+        fn exists_containing_include() {
+            let m = txt("exists:%{i}.include.example.com");
+            assert_eq!(m.kind(), &Kind::Exists);
+            assert_eq!(m.raw(), "%{i}.include.example.com");
+        }
+        #[test]
+        fn include_containing_redirect() {
+            let m = txt("include:redirect=.example.com");
+            assert_eq!(m.kind(), &Kind::Include);
+        }
+        #[test]
+        fn a_prefixed_names() {
+            assert!(parse_term("ab.com").is_err());
+            assert!(parse_term("all:example.com").is_err());
+            assert!(parse_term("+mx.com").is_err());
+            assert!(parse_term("ip:192.0.2.1").is_err());
+        }
+        #[test]
+        fn unknown_modifier() {
+            let m = txt("moo=%{s}.example.com");
+            assert_eq!(m.kind(), &Kind::UnknownModifier);
+            assert_eq!(m.raw(), "moo=%{s}.example.com");
+            assert_eq!(m.to_string(), "moo=%{s}.example.com");
+            assert_eq!(txt("op.x_-=").to_string(), "op.x_-=");
+        }
+        #[test]
+        fn unknown_mechanism() {
+            assert_eq!(
+                parse_term("redirect:_spf.example.com").unwrap_err(),
+                MechanismError::InvalidMechanismFormat("redirect:_spf.example.com".to_string())
+            );
+            assert!(parse_term("exp:explain.example.com").is_err());
+        }
+    }
+
+    mod domain_spec {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            for s in [
+                "example.com",
+                "example.com.",
+                "_spf.example.com",
+                "mail-1.example.co-uk",
+                "example.123-4",
+                "%{d}",
+                "%{ir}.%{v}._spf.%{d2}",
+                "%{l1r+-}.example.com",
+                "%%%_%-.example.com",
+                "foo:bar.example.com",
+            ] {
+                assert!(is_domain_spec(s), "{}", s);
+            }
+        }
+        #[test]
+        fn invalid() {
+            for s in [
+                "",
+                "example",
+                "example.",
+                "example.123",
+                "example.-com",
+                "example.com-",
+                "example.com/24",
+                "example.com/",
+                "%{d}.",
+                "%{x}.example.com",
+                "%{d.example.com",
+                "%example.com",
+                "exa mple.com",
+            ] {
+                assert!(!is_domain_spec(s), "{}", s);
+            }
+        }
+    }
+
+    mod cidr {
+        use super::*;
+
+        #[test]
+        fn dual() {
+            for s in ["/0", "/24", "/32", "//64", "//128", "/24//64"] {
+                assert!(is_dual_cidr_length(s), "{}", s);
+            }
+            for s in ["/", "/33", "/024", "//129", "//", "/24/", "/24/64"] {
+                assert!(!is_dual_cidr_length(s), "{}", s);
+            }
+        }
+        #[test]
+        fn split() {
+            assert_eq!(
+                split_dual_cidr_length("example.com/24//64"),
+                ("example.com", "/24//64")
+            );
+            assert_eq!(
+                split_dual_cidr_length("example.com//64"),
+                ("example.com", "//64")
+            );
+            assert_eq!(split_dual_cidr_length("example.com"), ("example.com", ""));
+            assert_eq!(split_dual_cidr_length("%{d}/33"), ("%{d}/33", ""));
+        }
+    }
+
+    mod a {
+        use super::*;
+
+        #[test]
+        fn forms() {
+            assert_eq!(txt("a").raw(), "a");
+            assert_eq!(txt("-a:example.com").raw(), "example.com");
+            assert_eq!(txt("~a/24").raw(), "/24");
+            assert_eq!(txt("+a:example.com/24").raw(), "example.com/24");
+            assert_eq!(txt("a:example.com/24//64").raw(), "example.com/24//64");
+            assert_eq!(txt("a//64").to_string(), "a//64");
+        }
+        #[test]
+        fn invalid() {
+            for s in [
+                "a:",
+                "a/",
+                "a/:",
+                "a:/",
+                "a:/24",
+                "a/33",
+                "a:example.com/33",
+            ] {
+                assert_eq!(
+                    parse_term(s).unwrap_err(),
+                    MechanismError::InvalidMechanismFormat(s.to_string())
+                );
+            }
+        }
+    }
+
+    mod mx {
+        use super::*;
+
+        #[test]
+        fn forms() {
+            assert_eq!(txt("mx").raw(), "mx");
+            assert_eq!(txt("-mx:example.com").raw(), "example.com");
+            assert_eq!(txt("~mx/24").raw(), "/24");
+            assert_eq!(txt("+mx:example.com/24").to_string(), "mx:example.com/24");
+        }
+        #[test]
+        fn invalid() {
+            for s in ["mx:", "mx/", "mx/:", "mx:/"] {
+                assert!(parse_term(s).is_err(), "{}", s);
+            }
+        }
+    }
+
+    mod ptr {
+        use super::*;
+
+        #[test]
+        fn forms() {
+            assert_eq!(txt("ptr").raw(), "ptr");
+            assert_eq!(txt("ptr:example.com").raw(), "example.com");
+        }
+        #[test]
+        fn invalid() {
+            for s in ["ptr:", "ptr/24", "ptr:example.com/", "ptr:example.com/24"] {
+                assert!(parse_term(s).is_err(), "{}", s);
+            }
+        }
+    }
+
+    mod exists {
+        use super::*;
+
+        #[test]
+        fn forms() {
+            assert_eq!(txt("exists:a.example.com").raw(), "a.example.com");
+            assert_eq!(txt("exists:%{ir}.%{l1r+-}._spf.%{d}").kind(), &Kind::Exists);
+        }
+        #[test]
+        fn invalid() {
+            for s in [
+                "exists",
+                "exists:",
+                "exists:a.example.com/",
+                "exists:a.example.com/32",
+            ] {
+                assert!(parse_term(s).is_err(), "{}", s);
+            }
+        }
+    }
+
+    mod ip {
+        use super::*;
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn ip4() {
+            let m = parse_term("-ip4:192.0.2.0/24").unwrap().network();
+            assert_eq!(m.kind(), &Kind::IpV4);
+            assert_eq!(m.to_string(), "-ip4:192.0.2.0/24");
+        }
+        #[cfg(feature = "std")]
+        #[test]
+        fn ip6() {
+            let m = parse_term("ip6:2001:db8::/32").unwrap().network();
+            assert_eq!(m.kind(), &Kind::IpV6);
+            assert_eq!(
+                parse_term("ip6:::ffff:192.0.2.1").unwrap().to_string(),
+                "ip6:::ffff:192.0.2.1"
+            );
+        }
+        #[test]
+        fn invalid() {
+            for s in [
+                "ip4:",
+                "ip4",
+                "ip4:192.0.2",
+                "ip4:192.0.2.0/024",
+                "ip4:192.0.2.0/",
+                "ip6:2001:db8::/0128",
+            ] {
+                assert!(parse_term(s).is_err(), "{}", s);
+            }
+            assert_eq!(
+                parse_term("ip6:192.0.2.0/24").unwrap_err(),
+                MechanismError::NotIP6Network("192.0.2.0/24".to_string())
+            );
+        }
+        #[cfg(not(feature = "std"))]
+        #[test]
+        fn without_std() {
+            let m = parse_term("-ip4:192.0.2.1/32").unwrap().txt();
+            assert_eq!(m.kind(), &Kind::IpV4);
+            assert_eq!(m.raw(), "192.0.2.1");
+            let m = parse_term("ip6:2001:db8::/32").unwrap();
+            assert!(m.is_network());
+            assert_eq!(m.to_string(), "ip6:2001:db8::/32");
+            assert_eq!(
+                parse_term("ip4:192.0.2.0/33").unwrap_err(),
+                MechanismError::InvalidIPNetwork(NetworkError::invalid_addr("192.0.2.0/33"))
+            );
+            assert_eq!(
+                parse_term("ip4:2001:db8::").unwrap_err(),
+                MechanismError::NotIP4Network("2001:db8::/128".to_string())
+            );
+        }
+    }
+
+    // Examples taken from RFC7208 Appendix A & Section 7.4
+    mod rfc_examples {
+        use super::*;
+
+        #[test]
+        fn appendix_a() {
+            for record in [
+                "v=spf1 +all",
+                "v=spf1 a -all",
+                "v=spf1 a:example.org -all",
+                "v=spf1 mx -all",
+                "v=spf1 mx:example.org -all",
+                "v=spf1 mx mx:example.org -all",
+                "v=spf1 mx/30 mx:example.org/30 -all",
+                "v=spf1 ptr -all",
+                "v=spf1 ip4:192.0.2.128/28 -all",
+                "v=spf1 include:example.com include:example.net -all",
+                "v=spf1 redirect=example.org",
+                "v=spf1 mx -all exp=explain._spf.%{d}",
+                "v=spf1 -ptr +all",
+                "v=spf1 -mx redirect=_spf.example.com",
+                "v=spf1 exists:%{ir}.%{l1r+-}._spf.%{d} -all",
+                "v=spf1 exists:%{l1r+-}._spf.%{d} -all",
+                "v=spf1 a/24 mx a:offsite.example.com/24 -all",
+                "v=spf1 mx ?exists:%{ir}.whitelist.example.org -all",
+            ] {
+                let (version, terms) = split_record(record);
+                assert!(is_version(version));
+                for term in terms {
+                    assert!(parse_term(term).is_ok(), "{} in {}", term, record);
+                }
+            }
+        }
+        #[test]
+        fn section_7_4_macros() {
+            for spec in [
+                "%{s}",
+                "%{o}",
+                "%{d}",
+                "%{d4}",
+                "%{d3}",
+                "%{d2}",
+                "%{d1}",
+                "%{dr}",
+                "%{d2r}",
+                "%{l}",
+                "%{l-}",
+                "%{lr}",
+                "%{lr-}",
+                "%{l1r-}",
+                "%{ir}.%{v}._spf.%{d2}",
+                "%{lr-}.lp._spf.%{d2}",
+                "%{lr-}.lp.%{ir}.%{v}._spf.%{d2}",
+                "%{ir}.%{v}.%{l1r-}.lp._spf.%{d2}",
+                "%{d2}.trusted-domains.example.net",
+            ] {
+                assert!(is_domain_spec(spec), "{}", spec);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Handling of TXT RDATA.
+//!
+//! A TXT record is made up of one or more *character-strings* of at most 255 octets.
+//! [RFC7208 Section 3.3](https://datatracker.ietf.org/doc/html/rfc7208#section-3.3) requires
+//! these to be concatenated without adding spaces.
+//!
+//! In a zone file each *character-string* is written in the presentation format given in
+//! [RFC1035 Section 5.1](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1).
+use crate::prelude::*;
+use crate::SpfError;
+use core::convert::TryFrom;
+
+/// The maximum number of octets in a single *character-string*.
+pub(crate) const MAX_CHARACTER_STRING_LENGTH: usize = 255;
+
+fn invalid(reason: &str) -> SpfError {
+    SpfError::InvalidTxtRdata(reason.to_string())
+}
+
+/// Concatenate raw *character-strings* into a single string.
+pub(crate) fn concatenate<S: AsRef<[u8]>>(strings: &[S]) -> Result<String, SpfError> {
+    let mut rdata = Vec::new();
+    for s in strings {
+        if s.as_ref().len() > MAX_CHARACTER_STRING_LENGTH {
+            return Err(invalid("character-string exceeds 255 octets"));
+        }
+        rdata.extend_from_slice(s.as_ref());
+    }
+    String::from_utf8(rdata).map_err(|_| invalid("character-strings are not valid UTF-8"))
+}
+
+/// Read the *character-strings* written in presentation format.
+///
+/// Strings are separated by white space and may be quoted. Within a string `\X` stands
+/// for the character `X` and `\DDD` for the octet with the decimal value `DDD`.
+pub(crate) fn parse_presentation(s: &str) -> Result<Vec<Vec<u8>>, SpfError> {
+    let bytes = s.as_bytes();
+    let mut strings = Vec::new();
+    let mut i = 0;
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            break;
+        }
+        let quoted = bytes[i] == b'"';
+        if quoted {
+            i += 1;
+        }
+        let mut string = Vec::new();
+        loop {
+            match bytes.get(i) {
+                None if quoted => return Err(invalid("unterminated quoted string")),
+                None => break,
+                Some(b'"') if quoted => {
+                    i += 1;
+                    match bytes.get(i) {
+                        Some(c) if !c.is_ascii_whitespace() => {
+                            return Err(invalid("quoted string must be followed by white space"))
+                        }
+                        _ => break,
+                    }
+                }
+                Some(b'"') => return Err(invalid("unexpected quote in unquoted string")),
+                Some(c) if !quoted && c.is_ascii_whitespace() => break,
+                Some(b'\\') => {
+                    let (octet, len) = unescape(&bytes[i + 1..])?;
+                    string.push(octet);
+                    i += 1 + len;
+                }
+                Some(c) => {
+                    string.push(*c);
+                    i += 1;
+                }
+            }
+        }
+        if string.len() > MAX_CHARACTER_STRING_LENGTH {
+            return Err(invalid("character-string exceeds 255 octets"));
+        }
+        strings.push(string);
+    }
+    if strings.is_empty() {
+        return Err(invalid("no character-strings found"));
+    }
+    Ok(strings)
+}
+
+// Returns the escaped octet and the number of bytes consumed after the `\`.
+fn unescape(bytes: &[u8]) -> Result<(u8, usize), SpfError> {
+    match bytes {
+        [a, b, c, ..] if a.is_ascii_digit() && b.is_ascii_digit() && c.is_ascii_digit() => {
+            let value = u16::from(a - b'0') * 100 + u16::from(b - b'0') * 10 + u16::from(c - b'0');
+            match u8::try_from(value) {
+                Ok(octet) => Ok((octet, 3)),
+                Err(_) => Err(invalid("escaped value exceeds 255")),
+            }
+        }
+        [a, ..] if a.is_ascii_digit() => Err(invalid("\\DDD escape requires three digits")),
+        [c, ..] => Ok((*c, 1)),
+        [] => Err(invalid("string ends with an escape character")),
+    }
+}
+
+/// Split `s` into *character-strings* of at most 255 octets.
+///
+/// Where possible a string is split after a space so that terms are kept whole.
+pub(crate) fn split(s: &str) -> Vec<&str> {
+    let mut strings = Vec::new();
+    let mut rest = s;
+    while rest.len() > MAX_CHARACTER_STRING_LENGTH {
+        let mut end = MAX_CHARACTER_STRING_LENGTH;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if let Some(idx) = rest[..end].rfind(' ') {
+            end = idx + 1;
+        }
+        let (string, remainder) = rest.split_at(end);
+        strings.push(string);
+        rest = remainder;
+    }
+    strings.push(rest);
+    strings
+}
+
+/// Write `s` as one or more quoted and escaped *character-strings*.
+pub(crate) fn to_presentation(s: &str) -> String {
+    let mut presentation = String::with_capacity(s.len() + 2);
+    for (i, string) in split(s).into_iter().enumerate() {
+        if i > 0 {
+            presentation.push(' ');
+        }
+        presentation.push('"');
+        for octet in string.bytes() {
+            match octet {
+                b'"' | b'\\' => {
+                    presentation.push('\\');
+                    presentation.push(octet as char);
+                }
+                0x20..=0x7e => presentation.push(octet as char),
+                _ => presentation.push_str(&format!("\\{:03}", octet)),
+            }
+        }
+        presentation.push('"');
+    }
+    presentation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concatenate_without_separator() {
+        let strings: [&[u8]; 2] = [b"v=spf1 ip4:192.0.2.0/24", b" -all"];
+        assert_eq!(
+            concatenate(&strings).unwrap(),
+            "v=spf1 ip4:192.0.2.0/24 -all"
+        );
+    }
+    #[test]
+    fn concatenate_too_long() {
+        let long = vec![b'a'; 256];
+        assert!(concatenate(&[long]).is_err());
+    }
+    #[test]
+    fn concatenate_invalid_utf8() {
+        let strings: [&[u8]; 1] = [&[0xff, 0xfe]];
+        assert!(concatenate(&strings).is_err());
+    }
+    #[test]
+    fn presentation_quoted() {
+        let strings = parse_presentation(r#""v=spf1 a " "mx -all""#).unwrap();
+        assert_eq!(strings, vec![b"v=spf1 a ".to_vec(), b"mx -all".to_vec()]);
+    }
+    #[test]
+    fn presentation_unquoted() {
+        let strings = parse_presentation("v=spf1\t-all").unwrap();
+        assert_eq!(strings, vec![b"v=spf1".to_vec(), b"-all".to_vec()]);
+    }
+    #[test]
+    fn presentation_escapes() {
+        let strings = parse_presentation(r#""a\"b\\c\032d""#).unwrap();
+        assert_eq!(strings, vec![b"a\"b\\c d".to_vec()]);
+    }
+    #[test]
+    fn presentation_errors() {
+        for s in [
+            "",
+            "  ",
+            r#""v=spf1"#,
+            r#""v=spf1""-all""#,
+            r#"v=spf1"a"#,
+            r#""\25""#,
+            r#""\256""#,
+            r#""v=spf1\"#,
+        ] {
+            assert!(parse_presentation(s).is_err(), "{}", s);
+        }
+    }
+    #[test]
+    fn split_at_space() {
+        let record = format!("v=spf1 {}-all", "ip4:192.0.2.0/24 ".repeat(20));
+        let strings = split(&record);
+        assert_eq!(strings.len(), 2);
+        assert!(strings[0].len() <= MAX_CHARACTER_STRING_LENGTH);
+        assert!(strings[0].ends_with(' '));
+        assert_eq!(strings.concat(), record);
+    }
+    #[test]
+    fn split_without_space() {
+        let record = "a".repeat(600);
+        let strings = split(&record);
+        assert_eq!(
+            strings.iter().map(|s| s.len()).collect::<Vec<_>>(),
+            [255, 255, 90]
+        );
+    }
+    #[test]
+    fn presentation_round_trip() {
+        let record = format!("v=spf1 {}\"\\ -all", "include:example.com ".repeat(15));
+        let presentation = to_presentation(&record);
+        assert!(presentation.starts_with("\"v=spf1 "));
+        let strings = parse_presentation(&presentation).unwrap();
+        assert_eq!(concatenate(&strings).unwrap(), record);
+    }
+}
@@ -1,6 +1,7 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 //! This crate is responsible for providing tools to parse and validate Spf Records.
 //! When the `builder` features is enabled access to the [SpfBuilder] struct and related
 //! functions is provided.
@@ -16,30 +17,53 @@
 //!     - [`Mechanism`](mechanism::Mechanism)
 //!     - [`Mechanism::Qualifier`](mechanism::Mechanism::is_pass)
 //!     - [`Mechanism::Kind`](mechanism::Mechanism::kind)
+//! - Lint records with configurable rules. See [`lint`]
 //!
 //! # Feature Flags:
+//! - `std` (Enabled by default.)\
+//!   Without `std` the crate is `no_std` and only requires `alloc`. Parsing, [`Spf`],
+//!   [`Mechanism`](mechanism::Mechanism) and validation remain available.
+//!     - `ip4` and `ip6` terms are still validated, but are kept as a `Mechanism<String>`.
+//!       `Mechanism<IpNetwork>` and `ParsedMechanism::IP` require `std`.
+//!     - `std::error::Error` is only implemented for the error types when `std` is enabled.
+//!     - `builder` requires `std`.
 //! - `ptr` (Enabled by default.)\
 //!   The `ptr` mechanism is highly discouraged. If you want to allow it without warnings, then you
-//! should provide the `--no-default-features` option.
+//!   should provide the `--no-default-features` option.
 //! - `strict-dns` (Disabled by default.)
 //!   This enables syntactical checking of Domain Names.
 //!     - When enabled it changes the behaviour of `FromStr` for `Mechanism<String>` and
-//! `ParsedMechanism`. By default, `rrdata` is not checked.\
-//!   When `strict-dns` is enabled an invalid domain host will be seen as **Hard** error.
-//! Any additional parsing will be halted.
+//!       `ParsedMechanism`. By default, `rrdata` is not checked.\
+//!       When `strict-dns` is enabled an invalid domain host will be seen as **Hard** error.
+//!       Any additional parsing will be halted.
 //! - `builder` (Disabled by default.)\
 //!   This enables the use of [SpfBuilder] and its related features\
 //!   You are able to convert and `Spf<String>` `into()` an `SpfBuilder` Struct.
 //! - `spf2` (Disabled by default)  
 //!   This enables the ability to programmatically create Spf2 (SenderID) records. As this
 //!   has become defunct. There is no real need for it. But it remains as an option if desired.
-//! - `serde` (Disabled by default.)
+//! - `serde` (Disabled by default.)\
+//!   This enables serialization and [`output`], which gives findings as SARIF or JSON.
+//! - `zone` (Disabled by default.)\
+//!   This enables [`zone::ZoneScanner`] which finds and parses every Spf record in a zone file.
 //!
+extern crate alloc;
+
 mod core;
+mod prelude;
 mod spf;
 
 #[cfg(feature = "builder")]
 pub use crate::spf::builder::{Builder, Parsed, SpfBuilder};
 pub use crate::spf::errors::SpfErrors;
-pub use crate::spf::{Spf, SpfError};
+pub use crate::spf::{Scope, Spf, SpfError, ValidationReport, Version};
+pub use spf::diff;
+pub use spf::lint;
 pub use spf::mechanism::{self};
+#[cfg(feature = "builder")]
+pub use spf::merge;
+#[cfg(feature = "serde")]
+pub use spf::output;
+pub use spf::size;
+#[cfg(feature = "zone")]
+pub use spf::zone;
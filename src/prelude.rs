@@ -0,0 +1,11 @@
+//! The items of the `std` prelude which come from `alloc`. These are not in scope when the
+//! crate is built without `std`.
+#[allow(unused_imports)]
+pub(crate) use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
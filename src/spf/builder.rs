@@ -1,7 +1,9 @@
-use crate::core::{DNS_LOOKUP_LIMIT, SPF1, SPF2};
-use crate::spf::mechanism::{builder::All, Kind, Mechanism, MechanismError};
-use crate::spf::validate::{self, Validate};
-use crate::{Spf, SpfError};
+use crate::core::{cidr, parser};
+use crate::size::WireSize;
+use crate::spf::errors::SpfErrors;
+use crate::spf::mechanism::{builder::All, Kind, Mechanism, ParsedMechanism, Qualifier};
+use crate::spf::validate::{self, Validate, ValidationReport};
+use crate::{Spf, SpfError, Version};
 use ipnetwork::IpNetwork;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -31,16 +33,9 @@ pub struct ContainsAll;
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SpfBuilder<State = Builder> {
     // Version is usually v=spf1 but may be spf2.0/...
-    version: String,
-    redirect: Option<Mechanism<String>>,
-    a: Option<Vec<Mechanism<String>>>,
-    mx: Option<Vec<Mechanism<String>>>,
-    include: Option<Vec<Mechanism<String>>>,
-    ip4: Option<Vec<Mechanism<IpNetwork>>>,
-    ip6: Option<Vec<Mechanism<IpNetwork>>>,
-    ptr: Option<Mechanism<String>>,
-    exists: Option<Vec<Mechanism<String>>>,
-    all: Option<Mechanism<All>>,
+    version: Version,
+    // Mechanisms and modifiers in the order they appear in the record.
+    terms: Vec<Mechanism<String>>,
     #[cfg_attr(feature = "serde", serde(skip))]
     state: PhantomData<State>,
 }
@@ -54,16 +49,8 @@ impl Modifiable for ContainsAll {}
 impl<State> Default for SpfBuilder<State> {
     fn default() -> Self {
         Self {
-            version: "".to_string(),
-            redirect: None,
-            a: None,
-            mx: None,
-            include: None,
-            ip4: None,
-            ip6: None,
-            ptr: None,
-            exists: None,
-            all: None,
+            version: Version::default(),
+            terms: Vec::new(),
             state: Default::default(),
         }
     }
@@ -105,8 +92,8 @@ mod string_to_builder {
             .unwrap();
         let builder: SpfBuilder<Builder> = SpfBuilder::from(spf);
         assert_eq!(builder.version, "v=spf1");
-        assert!(builder.mx.is_some());
-        assert!(builder.ip4.is_some());
+        assert!(builder.mx().is_some());
+        assert!(builder.ip4().is_some());
         assert!(builder.a().is_none());
     }
 }
@@ -147,53 +134,19 @@ impl FromStr for SpfBuilder<Parsed> {
 
         // Basic Checks are ok.
         let mut spf = SpfBuilder::new();
-        // Setup Vectors
-        let records = source.split_whitespace();
-
-        for record in records {
-            // Consider ensuring we do this once at least and then skip
-            if record.contains(SPF1) || record.starts_with(SPF2) {
-                spf.version = record.to_string();
-            } else if record.contains(crate::core::REDIRECT) {
-                if spf.redirect.is_some() {
-                    return Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect));
+        let (version, terms) = parser::split_record(&source);
+        spf.version = version.parse()?;
+
+        for (idx, term) in terms.enumerate() {
+            let m: Mechanism<String> = match parser::parse_term(term)? {
+                ParsedMechanism::IP(m) => m.into(),
+                ParsedMechanism::TXT(m) => m,
+            };
+            match m.kind() {
+                kind @ (Kind::Redirect | Kind::Exp | Kind::Ptr) if spf.term_of(*kind).is_some() => {
+                    return Err(SpfError::ModifierMayOccurOnlyOnce(*kind, idx));
                 }
-                let m: Mechanism<String> = record.parse()?;
-                spf.redirect = Some(m);
-            } else if record.contains(crate::core::INCLUDE) {
-                let m: Mechanism<String> = record.parse()?;
-                spf.append_string_mechanism(m);
-            } else if record.contains(crate::core::IP4) || record.contains(crate::core::IP6) {
-                let m = record.parse::<Mechanism<IpNetwork>>()?;
-                spf.append_ip_mechanism(m);
-            } else if record.ends_with(crate::core::ALL) && (record.len() == 3 || record.len() == 4)
-            {
-                spf.all = Some(Mechanism::all_with_qualifier(
-                    crate::core::return_and_remove_qualifier(record, 'a').0,
-                ));
-                // Handle A, MX, Exists and PTR types.
-            } else if let Ok(a_mechanism) = crate::core::spf_regex::capture_matches(record, Kind::A)
-            {
-                spf.append_string_mechanism(a_mechanism);
-            } else if let Ok(mx_mechanism) =
-                crate::core::spf_regex::capture_matches(record, Kind::MX)
-            {
-                spf.append_string_mechanism(mx_mechanism);
-            } else if let Ok(ptr_mechanism) =
-                crate::core::spf_regex::capture_matches(record, Kind::Ptr)
-            {
-                if spf.ptr.is_some() {
-                    return Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Ptr));
-                }
-                spf.ptr = Some(ptr_mechanism);
-            } else if let Ok(exists_mechanism) =
-                crate::core::spf_regex::capture_matches(record, Kind::Exists)
-            {
-                spf.append_string_mechanism(exists_mechanism);
-            } else {
-                return Err(SpfError::InvalidMechanism(
-                    MechanismError::InvalidMechanismFormat(record.to_string()),
-                ));
+                _ => spf.push_term(m),
             }
         }
         Ok(spf)
@@ -224,8 +177,8 @@ impl<State> SpfBuilder<State> {
     pub fn new() -> Self {
         SpfBuilder::default()
     }
-    /// Access the version attribute
-    pub fn version(&self) -> &String {
+    /// Access the version attribute. This is `v=spf1` unless another version has been set.
+    pub fn version(&self) -> &Version {
         &self.version
     }
 }
@@ -254,7 +207,20 @@ where
 {
     /// Set version to `v=spf1`
     pub fn set_v1(&mut self) -> &mut Self {
-        self.version = String::from(SPF1);
+        self.version = Version::V1;
+        self
+    }
+    /// Set the version.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Scope, SpfBuilder, Version};
+    /// let mut spf: SpfBuilder = SpfBuilder::new();
+    /// spf.set_version(Version::v2(&[Scope::MFrom]).unwrap());
+    /// assert_eq!(spf.version(), "spf2.0/mfrom");
+    /// ```
+    pub fn set_version(&mut self, version: Version) -> &mut Self {
+        self.version = version;
         self
     }
     /// Add an a mechanism
@@ -276,36 +242,24 @@ where
 }
 impl SpfBuilder<Builder> {
     /// Append a Redirect Mechanism to the Spf Struct. This also changes the struct's `State`
-    pub fn add_redirect(mut self, mechanism: Mechanism<String>) -> SpfBuilder<Redirected> {
-        SpfBuilder {
-            version: self.version.to_owned(),
-            redirect: Some(mechanism),
-            a: self.a.take(),
-            mx: self.mx.take(),
-            include: self.include.take(),
-            ip4: self.ip4.take(),
-            ip6: self.ip6.take(),
-            ptr: self.ptr.take(),
-            exists: self.exists.take(),
-            all: self.all.take(),
+    pub fn add_redirect(self, mechanism: Mechanism<String>) -> SpfBuilder<Redirected> {
+        let mut spf = SpfBuilder {
+            version: self.version,
+            terms: self.terms,
             state: PhantomData::<Redirected>,
-        }
+        };
+        spf.append_string_mechanism(mechanism);
+        spf
     }
     /// Add a Mechanism<All> to the SpfBuilder struct. This also changes the `State` to `ContainsAll`
-    pub fn add_all(mut self, mechanism: Mechanism<All>) -> SpfBuilder<ContainsAll> {
-        SpfBuilder {
-            version: self.version.to_owned(),
-            redirect: self.redirect.take(),
-            a: self.a.take(),
-            mx: self.mx.take(),
-            include: self.include.take(),
-            ip4: self.ip4.take(),
-            ip6: self.ip6.take(),
-            ptr: self.ptr.take(),
-            exists: self.exists.take(),
-            all: Some(mechanism),
+    pub fn add_all(self, mechanism: Mechanism<All>) -> SpfBuilder<ContainsAll> {
+        let mut spf = SpfBuilder {
+            version: self.version,
+            terms: self.terms,
             state: PhantomData::<ContainsAll>,
-        }
+        };
+        spf.append_string_mechanism(mechanism.into());
+        spf
     }
 }
 #[cfg_attr(docsrs, doc(cfg(feature = "spf2")))]
@@ -316,28 +270,35 @@ where
 {
     /// Set version to `spf2.0/pra`
     pub fn set_v2_pra(&mut self) -> &mut Self {
-        self.version = String::from(crate::core::SPF2_PRA);
+        self.version = Version::V2 {
+            scopes: vec![crate::Scope::Pra],
+        };
         self
     }
     /// Set version to `spf2.0/mfrom`
     pub fn set_v2_mfrom(&mut self) -> &mut Self {
-        self.version = String::from(crate::core::SPF2_MFROM);
+        self.version = Version::V2 {
+            scopes: vec![crate::Scope::MFrom],
+        };
         self
     }
     /// Set version to `spf2.0/pra,mfrom`
     pub fn set_v2_pra_mfrom(&mut self) -> &mut Self {
-        self.version = String::from(crate::core::SPF2_PRA_MFROM);
+        self.version = Version::V2 {
+            scopes: vec![crate::Scope::Pra, crate::Scope::MFrom],
+        };
         self
     }
     /// Set version to `spf2.0/mfrom,pra`
     pub fn set_v2_mfrom_pra(&mut self) -> &mut Self {
-        self.version = String::from(crate::core::SPF2_MFROM_PRA);
+        self.version = Version::V2 {
+            scopes: vec![crate::Scope::MFrom, crate::Scope::Pra],
+        };
         self
     }
     /// Check that version is v2
     pub fn is_v2(&self) -> bool {
-        self.version.starts_with(crate::core::SPF2_PRA)
-            || self.version.starts_with(crate::core::SPF2_MFROM)
+        self.version.is_v2()
     }
 }
 impl<State> SpfBuilder<State> {
@@ -364,134 +325,331 @@ impl<State> SpfBuilder<State> {
     where
         State: Modifiable,
     {
-        match kind {
-            Kind::Redirect => self.redirect = None,
-            Kind::A => self.a = None,
-            Kind::MX => self.mx = None,
-            Kind::Include => self.include = None,
-            Kind::IpV4 => self.ip4 = None,
-            Kind::IpV6 => self.ip6 = None,
-            Kind::Exists => self.exists = None,
-            Kind::Ptr => self.ptr = None,
-            Kind::All => self.all = None,
-        }
+        self.terms.retain(|m| *m.kind() != kind);
     }
 
-    fn append_mechanism_of_redirect(&mut self, mechanism: Mechanism<String>) -> &mut Self {
-        self.redirect = Some(mechanism);
-        self
+    // The index at which a term of `kind` is appended. Mechanisms are placed before `all`,
+    // `all` after the last mechanism, and modifiers at the end of the record.
+    fn append_index(&self, kind: Kind) -> usize {
+        let mechanisms_end = self
+            .terms
+            .iter()
+            .rposition(|m| !m.kind().is_modifier())
+            .map_or(0, |idx| idx + 1);
+        match kind {
+            _ if kind.is_modifier() => self.terms.len(),
+            Kind::All => mechanisms_end,
+            _ => self.index_of(Kind::All).unwrap_or(mechanisms_end),
+        }
     }
-    fn append_mechanism_of_a(&mut self, mechanism: Mechanism<String>) -> &mut Self {
-        if let Some(m_vec) = &mut self.a {
-            let exists = Self::check_mechanism_in_vec(&mechanism, m_vec);
-            if !exists {
-                m_vec.push(mechanism);
+    // A single `redirect`, `exp`, `ptr` or `all` is kept and is replaced in place. Any other
+    // Mechanism is not appended if it already exists to avoid duplication.
+    fn append_string_mechanism(&mut self, mechanism: Mechanism<String>) -> &mut Self {
+        let kind = *mechanism.kind();
+        match self.index_of(kind) {
+            Some(idx) if occurs_once(kind) => self.terms[idx] = mechanism,
+            _ if self.terms.contains(&mechanism) => {}
+            _ => {
+                let idx = self.append_index(kind);
+                self.terms.insert(idx, mechanism);
             }
-        } else {
-            self.a = Some(vec![mechanism]);
         }
         self
     }
-
-    // Before adding a Mechanism to its Vec we check to make sure the same Mechanism does not already
-    // exist. If it exists it is not appended to avoid duplication.
-    fn check_mechanism_in_vec<T>(mechanism: &Mechanism<T>, m_vec: &Vec<Mechanism<T>>) -> bool
-    where
-        T: PartialEq,
-    {
-        let mut exists = false;
-        for m in m_vec.iter() {
-            exists = m == mechanism;
+    // Used when the order of the source must be kept.
+    fn push_term(&mut self, mechanism: Mechanism<String>) {
+        if !self.terms.contains(&mechanism) {
+            self.terms.push(mechanism);
         }
-        exists
     }
-
-    fn append_mechanism_of_mx(&mut self, mechanism: Mechanism<String>) -> &mut Self {
-        if let Some(m_vec) = &mut self.mx {
-            let exists = Self::check_mechanism_in_vec(&mechanism, m_vec);
-            if !exists {
-                m_vec.push(mechanism);
-            }
-        } else {
-            self.mx = Some(vec![mechanism]);
+    fn append_ip_mechanism(&mut self, mechanism: Mechanism<IpNetwork>) -> &mut Self {
+        self.append_string_mechanism(mechanism.into())
+    }
+    // Insert at `idx` unless the term may only occur once and is already present.
+    fn insert_term(&mut self, idx: usize, mechanism: Mechanism<String>) -> Result<(), SpfError> {
+        let kind = *mechanism.kind();
+        if occurs_once(kind) && self.index_of(kind).is_some() {
+            return Err(SpfError::ModifierMayOccurOnlyOnce(kind, idx));
         }
-        self
+        if !self.terms.contains(&mechanism) {
+            self.terms.insert(idx, mechanism);
+        }
+        Ok(())
     }
-    fn append_mechanism_of_include(&mut self, mechanism: Mechanism<String>) -> &mut Self {
-        if let Some(m_vec) = &mut self.include {
-            let exists = Self::check_mechanism_in_vec(&mechanism, m_vec);
-            if !exists {
-                m_vec.push(mechanism);
-            }
-        } else {
-            self.include = Some(vec![mechanism]);
+    /// Insert a Mechanism or Modifier immediately before the term at `index`.
+    ///
+    /// As with [append_mechanism()](SpfBuilder::append_mechanism()) a Mechanism which is
+    /// already present is not added again.
+    ///
+    /// # Errors:
+    /// - [IndexOutOfRange](SpfError::IndexOutOfRange) if there is no term at `index`.
+    /// - [ModifierMayOccurOnlyOnce](SpfError::ModifierMayOccurOnlyOnce) if a `redirect`, `exp`,
+    ///   `ptr` or `all` is already present.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::{Builder, SpfBuilder};
+    /// let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+    /// spf.append_mechanism(Mechanism::include(Qualifier::Pass, "good.com").unwrap())
+    ///    .append_mechanism(Mechanism::all());
+    /// let bad = Mechanism::include(Qualifier::Fail, "bad.com").unwrap();
+    /// spf.insert_before(0, bad).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 -include:bad.com include:good.com -all");
+    /// assert!(spf.insert_before(3, Mechanism::a(Qualifier::Pass)).is_err());
+    /// ```
+    pub fn insert_before<T>(
+        &mut self,
+        index: usize,
+        mechanism: Mechanism<T>,
+    ) -> Result<&mut Self, SpfError>
+    where
+        State: Modifiable,
+        Mechanism<T>: Into<Mechanism<String>>,
+    {
+        if index >= self.terms.len() {
+            return Err(SpfError::IndexOutOfRange(index));
         }
-        self
+        self.insert_term(index, mechanism.into())?;
+        Ok(self)
     }
-    fn append_mechanism_of_ip4(&mut self, mechanism: Mechanism<IpNetwork>) -> &mut Self {
-        if let Some(m_vec) = &mut self.ip4 {
-            let exists = Self::check_mechanism_in_vec(&mechanism, m_vec);
-            if !exists {
-                m_vec.push(mechanism);
-            }
-        } else {
-            self.ip4 = Some(vec![mechanism]);
+    /// Insert a Mechanism or Modifier immediately after the term at `index`.
+    ///
+    /// See [insert_before()](SpfBuilder::insert_before()) for the possible errors.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::{Builder, SpfBuilder};
+    /// let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+    /// spf.append_mechanism(Mechanism::a(Qualifier::Pass))
+    ///    .append_mechanism(Mechanism::all());
+    /// spf.insert_after(0, Mechanism::ip_from_string("ip4:192.0.2.0/24").unwrap()).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a ip4:192.0.2.0/24 -all");
+    /// ```
+    pub fn insert_after<T>(
+        &mut self,
+        index: usize,
+        mechanism: Mechanism<T>,
+    ) -> Result<&mut Self, SpfError>
+    where
+        State: Modifiable,
+        Mechanism<T>: Into<Mechanism<String>>,
+    {
+        if index >= self.terms.len() {
+            return Err(SpfError::IndexOutOfRange(index));
         }
-        self
+        self.insert_term(index + 1, mechanism.into())?;
+        Ok(self)
     }
-    fn append_mechanism_of_ip6(&mut self, mechanism: Mechanism<IpNetwork>) -> &mut Self {
-        if let Some(m_vec) = &mut self.ip6 {
-            let exists = Self::check_mechanism_in_vec(&mechanism, m_vec);
-            if !exists {
-                m_vec.push(mechanism);
-            }
-        } else {
-            self.ip6 = Some(vec![mechanism]);
+    /// Move the term at `from` so that it is found at `to`.
+    ///
+    /// # Errors:
+    /// - [IndexOutOfRange](SpfError::IndexOutOfRange) if either index is not that of a term.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Builder, SpfBuilder};
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+    /// spf.append_mechanism(Mechanism::include(Qualifier::Pass, "good.com").unwrap())
+    ///    .append_mechanism(Mechanism::include(Qualifier::Fail, "bad.com").unwrap());
+    /// spf.move_term(1, 0).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 -include:bad.com include:good.com");
+    /// ```
+    pub fn move_term(&mut self, from: usize, to: usize) -> Result<&mut Self, SpfError>
+    where
+        State: Modifiable,
+    {
+        let len = self.terms.len();
+        if let Some(index) = [from, to].iter().copied().find(|&idx| idx >= len) {
+            return Err(SpfError::IndexOutOfRange(index));
         }
-        self
+        let term = self.terms.remove(from);
+        self.terms.insert(to, term);
+        Ok(self)
     }
-    fn append_mechanism_of_exists(&mut self, mechanism: Mechanism<String>) -> &mut Self {
-        if let Some(m_vec) = &mut self.exists {
-            let exists = Self::check_mechanism_in_vec(&mechanism, m_vec);
-            if !exists {
-                m_vec.push(mechanism);
+    /// Merge overlapping and adjacent `ip4` and `ip6` networks into the smallest set of networks
+    /// and return the number of terms removed.
+    ///
+    /// Only networks with the same qualifier are merged, and every address receives the same
+    /// result as before. A merged network takes the place of the first network it replaces. A
+    /// network is not moved before a term with a different qualifier which may match it, and
+    /// terms after `all` are left unchanged.
+    ///
+    /// Use [overlapping_networks()](crate::lint::overlapping_networks) to find the networks
+    /// without changing the record.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Builder, Spf, SpfBuilder};
+    /// let spf: Spf<String> =
+    ///     "v=spf1 ip4:10.0.0.0/24 mx ip4:10.0.0.0/23 ip4:10.0.2.0/23 ip6:2001:db8::/33 \
+    ///      ip6:2001:db8:8000::/33 -all"
+    ///         .parse()
+    ///         .unwrap();
+    /// let mut builder = SpfBuilder::<Builder>::from(spf);
+    /// assert_eq!(builder.aggregate_networks(), 3);
+    /// assert_eq!(builder.to_string(), "v=spf1 ip4:10.0.0.0/22 mx ip6:2001:db8::/32 -all");
+    /// ```
+    pub fn aggregate_networks(&mut self) -> usize
+    where
+        State: Modifiable,
+    {
+        let before = self.terms.len();
+        self.terms = cidr::aggregate_terms(&self.terms);
+        before - self.terms.len()
+    }
+    /// Remove `mechanism` and return `true` if it was present.
+    ///
+    /// Only a term equal to `mechanism`, including its qualifier, is removed. Use
+    /// [clear_mechanism()](SpfBuilder::clear_mechanism()) to remove every term of a kind.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::{Builder, Spf, SpfBuilder};
+    /// let spf: Spf<String> = "v=spf1 include:_spf.esp1.com include:_spf.esp2.com -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// let mut builder = SpfBuilder::<Builder>::from(spf);
+    /// let esp = Mechanism::include(Qualifier::Pass, "_spf.esp1.com").unwrap();
+    /// assert!(builder.remove_mechanism(esp.clone()));
+    /// assert!(!builder.remove_mechanism(esp));
+    /// assert_eq!(builder.to_string(), "v=spf1 include:_spf.esp2.com -all");
+    /// ```
+    pub fn remove_mechanism<T>(&mut self, mechanism: Mechanism<T>) -> bool
+    where
+        State: Modifiable,
+        Mechanism<T>: Into<Mechanism<String>>,
+    {
+        let mechanism = mechanism.into();
+        match self.position(&mechanism) {
+            Some(idx) => {
+                self.terms.remove(idx);
+                true
             }
-        } else {
-            self.exists = Some(vec![mechanism]);
+            None => false,
         }
-        self
     }
-    fn append_mechanism_of_ptr(&mut self, mechanism: Mechanism<String>) -> &mut Self {
-        self.ptr = Some(mechanism);
-        self
+    /// Replace `old` with `new` in the same position and return `true` if `old` was present.
+    ///
+    /// If `new` is already present elsewhere, only the earlier of the two terms is kept.
+    ///
+    /// # Errors:
+    /// - [ModifierMayOccurOnlyOnce](SpfError::ModifierMayOccurOnlyOnce) if `new` is a `redirect`,
+    ///   `exp`, `ptr` or `all` and another term of that kind is present.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::{Builder, Spf, SpfBuilder};
+    /// let spf: Spf<String> = "v=spf1 include:_spf.esp1.com mx -all".parse().unwrap();
+    /// let mut builder = SpfBuilder::<Builder>::from(spf);
+    /// let old = Mechanism::include(Qualifier::Pass, "_spf.esp1.com").unwrap();
+    /// let new = Mechanism::include(Qualifier::Pass, "_spf.esp2.com").unwrap();
+    /// assert!(builder.replace_mechanism(old, new).unwrap());
+    /// assert_eq!(builder.to_string(), "v=spf1 include:_spf.esp2.com mx -all");
+    /// assert!(builder.replace_mechanism(Mechanism::mx(Qualifier::Pass), Mechanism::ptr(Qualifier::Pass)).unwrap());
+    /// assert!(builder.replace_mechanism(Mechanism::ptr(Qualifier::Pass), Mechanism::all()).is_err());
+    /// ```
+    pub fn replace_mechanism<T, U>(
+        &mut self,
+        old: Mechanism<T>,
+        new: Mechanism<U>,
+    ) -> Result<bool, SpfError>
+    where
+        State: Modifiable,
+        Mechanism<T>: Into<Mechanism<String>>,
+        Mechanism<U>: Into<Mechanism<String>>,
+    {
+        let (old, new) = (old.into(), new.into());
+        let idx = match self.position(&old) {
+            Some(idx) => idx,
+            None => return Ok(false),
+        };
+        let kind = *new.kind();
+        if occurs_once(kind) && self.index_of(kind).is_some_and(|other| other != idx) {
+            return Err(SpfError::ModifierMayOccurOnlyOnce(kind, idx));
+        }
+        self.set_term(idx, new);
+        Ok(true)
     }
-    fn append_mechanism_of_all(&mut self, mechanism: Mechanism<All>) -> &mut Self {
-        self.all = Some(mechanism);
-        self
+    /// Keep only the terms for which `keep` returns `true` and return `true` if any term was
+    /// removed.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Builder, Spf, SpfBuilder};
+    /// let spf: Spf<String> =
+    ///     "v=spf1 include:_spf.esp1.com include:mail.esp1.com mx -all".parse().unwrap();
+    /// let mut builder = SpfBuilder::<Builder>::from(spf);
+    /// assert!(builder.retain_mechanisms(|m| !m.raw().ends_with("esp1.com")));
+    /// assert_eq!(builder.to_string(), "v=spf1 mx -all");
+    /// assert!(!builder.retain_mechanisms(|m| !m.kind().is_include()));
+    /// ```
+    pub fn retain_mechanisms<F>(&mut self, keep: F) -> bool
+    where
+        State: Modifiable,
+        F: FnMut(&Mechanism<String>) -> bool,
+    {
+        let before = self.terms.len();
+        self.terms.retain(keep);
+        before != self.terms.len()
     }
-    fn append_string_mechanism(&mut self, mechanism: Mechanism<String>) -> &mut Self {
-        match mechanism.kind() {
-            Kind::Redirect => self.append_mechanism_of_redirect(mechanism),
-            Kind::A => self.append_mechanism_of_a(mechanism),
-            Kind::MX => self.append_mechanism_of_mx(mechanism),
-            Kind::Include => self.append_mechanism_of_include(mechanism),
-            Kind::Exists => self.append_mechanism_of_exists(mechanism),
-            Kind::Ptr => self.append_mechanism_of_ptr(mechanism),
-            Kind::All => {
-                self.append_mechanism_of_all(mechanism.try_into().expect("Not a Mechanism<All>"))
-            }
-            _ => {
-                panic!("What the heck? Unmatched case?")
+    /// Change the qualifier of the term with the same kind and value as `mechanism`, whatever
+    /// its qualifier, and return `true` if the term was changed.
+    ///
+    /// Modifiers have no qualifier and are not changed. If the requalified term is already
+    /// present elsewhere, only the earlier of the two terms is kept.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::{Builder, Spf, SpfBuilder};
+    /// let spf: Spf<String> = "v=spf1 include:_spf.esp1.com mx ~all".parse().unwrap();
+    /// let mut builder = SpfBuilder::<Builder>::from(spf);
+    /// let esp = Mechanism::include(Qualifier::Pass, "_spf.esp1.com").unwrap();
+    /// assert!(builder.set_qualifier(esp, Qualifier::Fail));
+    /// assert!(builder.set_qualifier(Mechanism::all(), Qualifier::Fail));
+    /// assert!(!builder.set_qualifier(Mechanism::all(), Qualifier::Fail));
+    /// assert_eq!(builder.to_string(), "v=spf1 -include:_spf.esp1.com mx -all");
+    /// ```
+    pub fn set_qualifier<T>(&mut self, mechanism: Mechanism<T>, qualifier: Qualifier) -> bool
+    where
+        State: Modifiable,
+        Mechanism<T>: Into<Mechanism<String>>,
+    {
+        let mechanism = mechanism.into();
+        if mechanism.kind().is_modifier() {
+            return false;
+        }
+        let idx = self.terms.iter().position(|m| {
+            m.kind() == mechanism.kind()
+                && m.rr_data() == mechanism.rr_data()
+                && *m.qualifier() != qualifier
+        });
+        match idx {
+            Some(idx) => {
+                let m = &self.terms[idx];
+                let term = Mechanism::generic_inclusive(*m.kind(), qualifier, m.rr_data().clone());
+                self.set_term(idx, term);
+                true
             }
+            None => false,
         }
     }
-    fn append_ip_mechanism(&mut self, mechanism: Mechanism<IpNetwork>) -> &mut Self {
-        match mechanism.kind() {
-            Kind::IpV4 => self.append_mechanism_of_ip4(mechanism),
-            Kind::IpV6 => self.append_mechanism_of_ip6(mechanism),
-            _ => {
-                unreachable!()
+    // Set the term at `idx`. A duplicate is not kept, so only the earlier of the two remains.
+    fn set_term(&mut self, idx: usize, mechanism: Mechanism<String>) {
+        match self.terms.iter().position(|m| *m == mechanism) {
+            Some(other) if other < idx => {
+                self.terms.remove(idx);
+            }
+            Some(other) if other > idx => {
+                self.terms.remove(other);
+                self.terms[idx] = mechanism;
             }
+            _ => self.terms[idx] = mechanism,
         }
     }
     /// This is generic method for adding Mechanism`<T>` to the SpfBuilder struct.
@@ -523,85 +681,132 @@ impl<State> SpfBuilder<State> {
     }
 
     fn build_spf_string(&self) -> String {
-        let mut spf = String::new();
-        spf.push_str(self.version());
-        if let Some(a) = self.a() {
-            spf.push_str(crate::core::build_spf_str(a).as_str());
-        };
-        if let Some(mx) = self.mx() {
-            spf.push_str(crate::core::build_spf_str(mx).as_str());
-        };
-        if let Some(includes) = self.includes() {
-            spf.push_str(crate::core::build_spf_str(includes).as_str());
-        }
-        if let Some(ip4) = self.ip4() {
-            spf.push_str(crate::core::build_spf_str_from_ip(ip4).as_str());
-        }
-        if let Some(ip6) = self.ip6() {
-            spf.push_str(crate::core::build_spf_str_from_ip(ip6).as_str());
-        }
-        if let Some(exists) = self.exists() {
-            spf.push_str(crate::core::build_spf_str(exists).as_str());
-        }
-        if let Some(ptr) = self.ptr() {
-            spf.push(' ');
-            spf.push_str(ptr.to_string().as_str());
-        }
-        if self.redirect.is_some() {
-            spf.push(' ');
-            spf.push_str(
-                self.redirect()
-                    .expect("Should not fail")
-                    .to_string()
-                    .as_str(),
-            );
-        }
-        // All can only be used if this is not a redirect.
-        if self.redirect.is_none() && self.all().is_some() {
+        let mut spf = self.version().to_string();
+        for m in &self.terms {
             spf.push(' ');
-            spf.push_str(self.all().expect("Should not fail.").to_string().as_str());
+            spf.push_str(m.to_string().as_str());
         }
         spf
     }
+    fn index_of(&self, kind: Kind) -> Option<usize> {
+        self.terms.iter().position(|m| *m.kind() == kind)
+    }
+    fn term_of(&self, kind: Kind) -> Option<&Mechanism<String>> {
+        self.index_of(kind).map(|idx| &self.terms[idx])
+    }
+    fn terms_of(&self, kind: Kind) -> Option<Vec<&Mechanism<String>>> {
+        let terms: Vec<&Mechanism<String>> =
+            self.terms.iter().filter(|m| *m.kind() == kind).collect();
+        (!terms.is_empty()).then_some(terms)
+    }
+    fn ip_terms_of(&self, kind: Kind) -> Option<Vec<Mechanism<IpNetwork>>> {
+        self.terms_of(kind).map(|terms| {
+            terms
+                .into_iter()
+                .map(|m| {
+                    m.clone()
+                        .try_into()
+                        .expect("Mechanism is not a valid IP address. Should never happen")
+                })
+                .collect()
+        })
+    }
     /// True if there is a redirect present in the spf record.
     pub fn is_redirect(&self) -> bool {
-        self.redirect.is_some()
+        self.redirect().is_some()
     }
     /// Returns a reference to the `Redirect` Mechanism
     pub fn redirect(&self) -> Option<&Mechanism<String>> {
-        self.redirect.as_ref()
+        self.term_of(Kind::Redirect)
     }
-    /// Returns a reference to the a `Vec` of `Mechanism<String>` for `Include`
-    pub fn includes(&self) -> Option<&Vec<Mechanism<String>>> {
-        self.include.as_ref()
+    /// Returns the Mechanisms and Modifiers in the order they appear in the record.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Parsed, SpfBuilder};
+    /// let spf: SpfBuilder<Parsed> = "v=spf1 -include:bad.com include:good.com ip4:192.0.2.1 -all"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(spf.terms()[0].to_string(), "-include:bad.com");
+    /// assert_eq!(spf.includes().unwrap().len(), 2);
+    /// assert_eq!(spf.to_string(), "v=spf1 -include:bad.com include:good.com ip4:192.0.2.1 -all");
+    /// ```
+    pub fn terms(&self) -> &[Mechanism<String>] {
+        &self.terms
+    }
+    /// Returns the index of `mechanism` within [terms()](SpfBuilder::terms()) if it is present.
+    pub fn position(&self, mechanism: &Mechanism<String>) -> Option<usize> {
+        self.terms.iter().position(|m| m == mechanism)
     }
-    /// Returns a reference to a `Vec` of `Mechanism<String>` for `A`
-    pub fn a(&self) -> Option<&Vec<Mechanism<String>>> {
-        self.a.as_ref()
+    /// Returns a `Vec` of references to the `Include` Mechanisms in record order.
+    pub fn includes(&self) -> Option<Vec<&Mechanism<String>>> {
+        self.terms_of(Kind::Include)
     }
-    /// Returns a reference to a `Vec` of `Mechanism<String>` for `MX`
-    pub fn mx(&self) -> Option<&Vec<Mechanism<String>>> {
-        self.mx.as_ref()
+    /// Returns a `Vec` of references to the `A` Mechanisms in record order.
+    pub fn a(&self) -> Option<Vec<&Mechanism<String>>> {
+        self.terms_of(Kind::A)
     }
-    /// Returns a reference to a `Vec` of `Mechanism<IpNetwork>` for `IP4`
-    pub fn ip4(&self) -> Option<&Vec<Mechanism<IpNetwork>>> {
-        self.ip4.as_ref()
+    /// Returns a `Vec` of references to the `MX` Mechanisms in record order.
+    pub fn mx(&self) -> Option<Vec<&Mechanism<String>>> {
+        self.terms_of(Kind::MX)
     }
-    /// Returns a reference to a `Vec` of `Mechanism<IpNetwork>` for `IP6`
-    pub fn ip6(&self) -> Option<&Vec<Mechanism<IpNetwork>>> {
-        self.ip6.as_ref()
+    /// Returns a `Vec` of the `IP4` Mechanisms as `Mechanism<IpNetwork>` in record order.
+    pub fn ip4(&self) -> Option<Vec<Mechanism<IpNetwork>>> {
+        self.ip_terms_of(Kind::IpV4)
     }
-    /// Returns a reference to a `Vec` of `Mechanism<String>` for `Exists`
-    pub fn exists(&self) -> Option<&Vec<Mechanism<String>>> {
-        self.exists.as_ref()
+    /// Returns a `Vec` of the `IP6` Mechanisms as `Mechanism<IpNetwork>` in record order.
+    pub fn ip6(&self) -> Option<Vec<Mechanism<IpNetwork>>> {
+        self.ip_terms_of(Kind::IpV6)
     }
-    /// Returns a reference to a `Vec` of `Mechanism<String>` for `Ptr`
+    /// Returns a `Vec` of references to the `Exists` Mechanisms in record order.
+    pub fn exists(&self) -> Option<Vec<&Mechanism<String>>> {
+        self.terms_of(Kind::Exists)
+    }
+    /// Returns a reference to the `Ptr` Mechanism
     pub fn ptr(&self) -> Option<&Mechanism<String>> {
-        self.ptr.as_ref()
+        self.term_of(Kind::Ptr)
+    }
+    /// Returns the `All` Mechanism as a `Mechanism<All>`
+    pub fn all(&self) -> Option<Mechanism<All>> {
+        self.term_of(Kind::All)
+            .map(|m| m.clone().try_into().expect("Not a Mechanism<All>"))
+    }
+    /// Returns a reference to the `Exp` Modifier
+    pub fn exp(&self) -> Option<&Mechanism<String>> {
+        self.term_of(Kind::Exp)
     }
-    /// Returns a reference to `Mechanism<All>` for `All`
-    pub fn all(&self) -> Option<&Mechanism<All>> {
-        self.all.as_ref()
+    /// Returns a `Vec` of references to the modifiers not defined by RFC7208
+    pub fn unknown_modifiers(&self) -> Option<Vec<&Mechanism<String>>> {
+        self.terms_of(Kind::UnknownModifier)
+    }
+    /// Validate the record with the same checks as [Spf::validate()].
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::{Builder, Spf, SpfBuilder, SpfError};
+    /// let mut builder: SpfBuilder<Builder> = SpfBuilder::new();
+    /// builder
+    ///     .append_mechanism(Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap())
+    ///     .append_mechanism(Mechanism::all());
+    /// let errors = builder.validate().unwrap_err();
+    /// assert_eq!(errors.errors(), &[SpfError::RedirectWithAllMechanism]);
+    ///
+    /// let spf: Spf<String> = builder.to_string().parse().unwrap();
+    /// assert_eq!(spf.validate().unwrap_err().errors(), errors.errors());
+    /// ```
+    pub fn validate(&self) -> Result<(), SpfErrors> {
+        self.validate_all()
+    }
+    /// Validate the record and describe each problem found, the same as
+    /// [Spf::validation_report()].
+    pub fn validation_report(&self) -> ValidationReport {
+        self.validate_report()
+    }
+    /// Returns the size of the record on the wire when published at `owner`, the same as
+    /// [Spf::wire_size()].
+    pub fn wire_size(&self, owner: &str) -> WireSize {
+        WireSize::new(&self.to_string(), owner)
     }
     /// Creates a `Spf<String>` from `SpfBuilder`
     /// This function also validates the SpfBuilder struct before returning a Spf<String>
@@ -632,131 +837,47 @@ impl<State> SpfBuilder<State> {
     /// assert!(result.is_spf_error());
     ///
     /// ```
-    pub fn build(mut self) -> Result<Spf<String>, SpfError> {
-        if self.version.is_empty() {
-            self.version = SPF1.to_owned();
-        } else {
-            self.validate_version()?;
-        }
+    pub fn build(self) -> Result<Spf<String>, SpfError> {
+        self.validate_length()?;
         self.validate_lookup_count()?;
         self.validate_ptr()?;
         self.validate_redirect_all()?;
-        let lookup_count: u8 = self.get_lookup_count() as u8;
-
-        let mut redirect_idx = 0;
-        let mut has_redirect = false;
-        let mut all_idx = 0;
-        let mut mechanisms: Vec<Mechanism<String>> = Vec::with_capacity(DNS_LOOKUP_LIMIT + 1); // +1 for Version information
-
-        if let Some(list) = self.a.as_mut() {
-            mechanisms.append(list);
-        }
-        if let Some(list) = self.mx.as_mut() {
-            mechanisms.append(list);
-        }
-        if let Some(ip4) = self.ip4 {
-            for m in ip4.into_iter() {
-                mechanisms.push(m.into());
-            }
-        }
-        if let Some(ip6) = self.ip6 {
-            for m in ip6.into_iter() {
-                mechanisms.push(m.into());
-            }
-        }
-        if let Some(list) = self.include.as_mut() {
-            mechanisms.append(list);
-        }
-        if let Some(list) = self.exists.as_mut() {
-            mechanisms.append(list);
-        }
-        if let Some(all) = self.all {
-            mechanisms.push(all.into());
-            all_idx = mechanisms.len() - 1;
-        }
-        if let Some(redirect) = self.redirect {
-            mechanisms.push(redirect);
-            has_redirect = true;
-            redirect_idx = mechanisms.len() - 1;
+        if let Some(e) = self.validate_modifiers().into_iter().next() {
+            return Err(e);
         }
+        let lookup_count: u8 = validate::lookup_count(&self.terms)
+            .try_into()
+            .unwrap_or(u8::MAX);
+        let redirect_idx = self.index_of(Kind::Redirect);
+        let all_idx = self.index_of(Kind::All);
+
         Ok(Spf::<String> {
             source: "".to_string(),
             version: self.version,
-            redirect_idx,
-            has_redirect,
-            all_idx,
+            redirect_idx: redirect_idx.unwrap_or(0),
+            has_redirect: redirect_idx.is_some(),
+            all_idx: all_idx.unwrap_or(0),
             lookup_count,
-            mechanisms,
+            mechanisms: self.terms,
         })
     }
-
-    pub(crate) fn get_lookup_count(&self) -> usize {
-        let mut count: usize = 0;
-        {
-            if let Some(a) = &self.a {
-                count += a.len();
-            }
-            if let Some(mx) = &self.mx {
-                count += mx.len();
-            }
-            if self.redirect.is_some() {
-                count += 1;
-            }
-            if let Some(exists) = &self.exists {
-                count += exists.len();
-            }
-            if self.ptr.is_some() {
-                count += 1;
-            }
-            if let Some(include) = &self.include {
-                count += include.len();
-            }
-        }
-        count
-    }
 }
 
 impl<State> SpfBuilder<State> {
     /// Allows you to iterate over Mechanisms contained within the SPF record.
     /// # Note: Version string is not included.
     pub fn iter(&self) -> SpfBuilderIterator {
-        let mut m: Vec<Mechanism<String>> = vec![];
-
-        if let Some(r) = &self.redirect {
-            m.push(r.clone());
-        }
-
-        if let Some(m_a) = &self.a {
-            m.extend(m_a.iter().cloned())
-        }
-        if let Some(m_mx) = &self.mx {
-            m.extend(m_mx.iter().cloned())
-        }
-        if let Some(include) = &self.include {
-            m.extend(include.iter().cloned())
-        }
-        if let Some(ip4) = &self.ip4 {
-            m.extend(ip4.iter().map(|v| (*v).into()))
-        }
-        if let Some(ip6) = &self.ip6 {
-            m.extend(ip6.iter().map(|v| (*v).into()))
-        }
-        if let Some(exists) = &self.exists {
-            m.extend(exists.iter().cloned())
-        }
-        if let Some(ptr) = &self.ptr {
-            m.push(ptr.clone())
-        }
-        if let Some(all) = &self.all {
-            m.push((*all).clone().into())
-        }
-
         SpfBuilderIterator {
-            m_iter: m.into_iter(),
+            m_iter: self.terms.clone().into_iter(),
         }
     }
 }
 
+// Kinds of which only a single term is kept.
+fn occurs_once(kind: Kind) -> bool {
+    matches!(kind, Kind::Redirect | Kind::Exp | Kind::Ptr | Kind::All)
+}
+
 pub trait Append<T> {
     fn append(&mut self, mechanism: Mechanism<T>) -> &mut Self;
 }
@@ -774,7 +895,7 @@ impl<State> Append<IpNetwork> for SpfBuilder<State> {
 
 impl<State> Append<All> for SpfBuilder<State> {
     fn append(&mut self, mechanism: Mechanism<All>) -> &mut Self {
-        self.append_mechanism_of_all(mechanism)
+        self.append_string_mechanism(mechanism.into())
     }
 }
 
@@ -801,25 +922,7 @@ fn build_spf<T>(source: Spf<String>) -> SpfBuilder<T> {
     new_spf.version = source.version;
 
     for m in source.mechanisms.into_iter() {
-        match m.kind() {
-            Kind::IpV4 | Kind::IpV6 => {
-                let ip_m = Mechanism::ip_from_string(&*m.to_string())
-                    .expect("Mechanism is not a valid IP address. Should never happen");
-                new_spf.append_mechanism(ip_m);
-            }
-            Kind::All => {
-                new_spf.all = Some(
-                    m.try_into()
-                        .expect("Not All Mechanisms. Should never happen."),
-                );
-            }
-            Kind::Redirect => {
-                new_spf.redirect = Some(m);
-            }
-            _ => {
-                new_spf.append_mechanism(m);
-            }
-        }
+        new_spf.push_term(m);
     }
 
     new_spf
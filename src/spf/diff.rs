@@ -0,0 +1,323 @@
+//! Compare two records by meaning rather than by text.
+//!
+//! [`Spf::diff()`](crate::Spf::diff) matches the terms of two records by kind and value and
+//! reports each [`Change`]. It also gives the addresses which are newly authorized or no longer
+//! authorized by `ip4` and `ip6` terms, and the change in DNS lookups.
+//!
+//! # Example:
+//! ```
+//! use decon_spf::diff::Change;
+//! use decon_spf::mechanism::Qualifier;
+//! use decon_spf::Spf;
+//! let old: Spf<String> = "v=spf1 ip4:192.0.2.0/24 ?mx include:_spf.example.net ~all"
+//!     .parse()
+//!     .unwrap();
+//! let new: Spf<String> = "v=spf1 ip4:192.0.2.0/23 mx include:_spf.example.org -all"
+//!     .parse()
+//!     .unwrap();
+//! let diff = old.diff(&new);
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "`~all` is now `-all`\n\
+//!      removed `ip4:192.0.2.0/24`\n\
+//!      removed `include:_spf.example.net`\n\
+//!      added `ip4:192.0.2.0/23`\n\
+//!      added `include:_spf.example.org`\n\
+//!      `?mx` is now `mx`\n\
+//!      authorizes 192.0.3.0/24\n"
+//! );
+//! assert_eq!(
+//!     diff.changes()[0],
+//!     Change::All {
+//!         from: Some(Qualifier::SoftFail),
+//!         to: Some(Qualifier::Fail)
+//!     }
+//! );
+//! assert_eq!(diff.added_coverage(), ["192.0.3.0/24"]);
+//! assert_eq!(diff.lookup_delta(), 0);
+//! ```
+use crate::core::cidr::{self, Cidr};
+use crate::mechanism::{Mechanism, Qualifier};
+use crate::prelude::*;
+use crate::{Spf, Version};
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// A difference between two records. Indices are those of the terms of the old record for
+/// [`Removed`](Change::Removed) and of the new record otherwise.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Change {
+    /// The version has changed.
+    Version {
+        /// The old version.
+        from: Version,
+        /// The new version.
+        to: Version,
+    },
+    /// The qualifier of `all` has changed, or `all` has been added or removed.
+    All {
+        /// The old qualifier. `None` if there was no `all`.
+        from: Option<Qualifier>,
+        /// The new qualifier. `None` if there is no `all`.
+        to: Option<Qualifier>,
+    },
+    /// A term of the old record is not in the new record.
+    Removed {
+        /// The index of the term in the old record.
+        index: usize,
+        /// The term removed.
+        term: Mechanism<String>,
+    },
+    /// A term of the new record is not in the old record.
+    Added {
+        /// The index of the term in the new record.
+        index: usize,
+        /// The term added.
+        term: Mechanism<String>,
+    },
+    /// A term is in both records with a different qualifier.
+    Requalified {
+        /// The index of the term in the new record.
+        index: usize,
+        /// The term with its new qualifier.
+        term: Mechanism<String>,
+        /// The old qualifier.
+        from: Qualifier,
+    },
+    /// Two mechanisms which may match the same host are evaluated in a different order, so the
+    /// host receives a different result. `term` now precedes `other`.
+    Reordered {
+        /// The index of the term in the new record.
+        index: usize,
+        /// The term which is now evaluated first.
+        term: Mechanism<String>,
+        /// The index of the other term in the new record.
+        other: usize,
+        /// The term which was evaluated first.
+        other_term: Mechanism<String>,
+    },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let all = |q: &Qualifier| format!("{}all", q.as_str());
+        match self {
+            Change::Version { from, to } => write!(f, "`{}` is now `{}`", from, to),
+            Change::All {
+                from: Some(from),
+                to: Some(to),
+            } => write!(f, "`{}` is now `{}`", all(from), all(to)),
+            Change::All { from: Some(q), .. } => write!(f, "removed `{}`", all(q)),
+            Change::All { to: Some(q), .. } => write!(f, "added `{}`", all(q)),
+            Change::All { .. } => Ok(()),
+            Change::Removed { term, .. } => write!(f, "removed `{}`", term),
+            Change::Added { term, .. } => write!(f, "added `{}`", term),
+            Change::Requalified { term, from, .. } => {
+                let old = Mechanism::<String>::new(*term.kind(), *from);
+                let old = match term.rr_data() {
+                    Some(rrdata) => old.with_rrdata(rrdata).map_err(|_| core::fmt::Error)?,
+                    None => old,
+                };
+                write!(f, "`{}` is now `{}`", old, term)
+            }
+            Change::Reordered {
+                term, other_term, ..
+            } => write!(f, "`{}` now precedes `{}`", term, other_term),
+        }
+    }
+}
+
+/// The differences between two records. See [`Spf::diff()`](crate::Spf::diff).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Diff {
+    changes: Vec<Change>,
+    added_coverage: Vec<String>,
+    removed_coverage: Vec<String>,
+    lookups: (u8, u8),
+}
+
+impl Diff {
+    /// Every change, ordered by kind: [`Version`](Change::Version), [`All`](Change::All),
+    /// [`Removed`](Change::Removed), [`Added`](Change::Added),
+    /// [`Requalified`](Change::Requalified) and then [`Reordered`](Change::Reordered). Within
+    /// each kind changes are in record order.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+    /// Returns `true` if the records have the same meaning. Differences in spacing, letter case
+    /// and the order of terms which cannot match the same host are not changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+    /// The networks authorized by `ip4` and `ip6` terms of the new record which were not
+    /// authorized by the old record, in the smallest set of networks.
+    pub fn added_coverage(&self) -> &[String] {
+        &self.added_coverage
+    }
+    /// The networks authorized by `ip4` and `ip6` terms of the old record which are no longer
+    /// authorized.
+    pub fn removed_coverage(&self) -> &[String] {
+        &self.removed_coverage
+    }
+    /// The number of DNS lookups required by the old record.
+    pub fn lookups_before(&self) -> u8 {
+        self.lookups.0
+    }
+    /// The number of DNS lookups required by the new record.
+    pub fn lookups_after(&self) -> u8 {
+        self.lookups.1
+    }
+    /// The change in the number of DNS lookups.
+    pub fn lookup_delta(&self) -> i16 {
+        self.lookups.1 as i16 - self.lookups.0 as i16
+    }
+}
+
+/// One line for each change, followed by the changes in coverage and lookups.
+impl Display for Diff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        for network in &self.added_coverage {
+            writeln!(f, "authorizes {}", network)?;
+        }
+        for network in &self.removed_coverage {
+            writeln!(f, "no longer authorizes {}", network)?;
+        }
+        if self.lookup_delta() != 0 {
+            writeln!(f, "lookups {} -> {}", self.lookups.0, self.lookups.1)?;
+        }
+        Ok(())
+    }
+}
+
+// A term of both records.
+struct Pair<'a> {
+    old: usize,
+    new: usize,
+    old_term: &'a Mechanism<String>,
+    new_term: &'a Mechanism<String>,
+}
+
+// `true` if a host could be matched by both mechanisms.
+fn may_overlap(a: &Mechanism<String>, b: &Mechanism<String>) -> bool {
+    match (Cidr::from_mechanism(a), Cidr::from_mechanism(b)) {
+        (Some(a), Some(b)) => a.overlaps(&b),
+        _ => true,
+    }
+}
+
+// The addresses which receive `pass` from an `ip4` or `ip6` term. An earlier network with a
+// different qualifier takes precedence, and no term after `all` is evaluated.
+fn coverage(terms: &[Mechanism<String>]) -> Vec<Cidr> {
+    let mut matched: Vec<Cidr> = Vec::new();
+    let mut passed = Vec::new();
+    for m in terms.iter().take_while(|m| !m.kind().is_all()) {
+        if let Some(cidr) = Cidr::from_mechanism(m) {
+            if m.qualifier().is_pass() {
+                passed.extend(cidr::difference(&[cidr], &matched));
+            }
+            matched.push(cidr);
+        }
+    }
+    passed
+}
+
+/// Compare `old` with `new`. See [`Spf::diff()`](crate::Spf::diff).
+pub(crate) fn diff(old: &Spf<String>, new: &Spf<String>) -> Diff {
+    let old_terms: Vec<_> = old.iter().collect();
+    let new_terms: Vec<_> = new.iter().collect();
+    let mut changes = Vec::new();
+    if old.version() != new.version() {
+        changes.push(Change::Version {
+            from: old.version().clone(),
+            to: new.version().clone(),
+        });
+    }
+    let (from, to) = (
+        old.all().map(|m| *m.qualifier()),
+        new.all().map(|m| *m.qualifier()),
+    );
+    if from != to {
+        changes.push(Change::All { from, to });
+    }
+    // Match each term of the old record with the first unmatched term of the new record.
+    let mut matched = vec![false; new_terms.len()];
+    let mut pairs = Vec::new();
+    for (old_idx, old_term) in old_terms.iter().enumerate() {
+        if old_term.kind().is_all() {
+            continue;
+        }
+        let found = new_terms
+            .iter()
+            .enumerate()
+            .position(|(idx, m)| !matched[idx] && old_term.is_same_term(m));
+        match found {
+            Some(new_idx) => {
+                matched[new_idx] = true;
+                pairs.push(Pair {
+                    old: old_idx,
+                    new: new_idx,
+                    old_term,
+                    new_term: new_terms[new_idx],
+                });
+            }
+            None => changes.push(Change::Removed {
+                index: old_idx,
+                term: (*old_term).clone(),
+            }),
+        }
+    }
+    for (idx, term) in new_terms.iter().enumerate() {
+        if !matched[idx] && !term.kind().is_all() {
+            changes.push(Change::Added {
+                index: idx,
+                term: (*term).clone(),
+            });
+        }
+    }
+    pairs.sort_by_key(|p| p.new);
+    for pair in &pairs {
+        if pair.old_term.qualifier() != pair.new_term.qualifier() {
+            changes.push(Change::Requalified {
+                index: pair.new,
+                term: pair.new_term.clone(),
+                from: *pair.old_term.qualifier(),
+            });
+        }
+    }
+    // A host matched by both mechanisms of a pair which has swapped places receives the
+    // result of the other one.
+    let mechanisms: Vec<_> = pairs
+        .iter()
+        .filter(|p| !p.new_term.kind().is_modifier())
+        .collect();
+    for (i, first) in mechanisms.iter().enumerate() {
+        for second in &mechanisms[i + 1..] {
+            if first.old > second.old
+                && first.new_term.qualifier() != second.old_term.qualifier()
+                && may_overlap(first.new_term, second.new_term)
+            {
+                changes.push(Change::Reordered {
+                    index: first.new,
+                    term: first.new_term.clone(),
+                    other: second.new,
+                    other_term: second.new_term.clone(),
+                });
+            }
+        }
+    }
+    let (old_coverage, new_coverage) = (coverage(&old.mechanisms), coverage(&new.mechanisms));
+    let strings = |c: Vec<Cidr>| c.iter().map(|c| c.to_string()).collect();
+    Diff {
+        changes,
+        added_coverage: strings(cidr::difference(&new_coverage, &old_coverage)),
+        removed_coverage: strings(cidr::difference(&old_coverage, &new_coverage)),
+        lookups: (old.lookup_count(), new.lookup_count()),
+    }
+}
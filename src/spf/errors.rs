@@ -1,5 +1,7 @@
+use crate::prelude::*;
 use crate::spf::mechanism::Kind;
-use crate::spf::mechanism::MechanismError;
+use crate::spf::mechanism::{MechanismError, NetworkError};
+#[cfg(feature = "std")]
 use ipnetwork::IpNetworkError;
 
 /// A list of expected and possible errors for SPF records.
@@ -24,22 +26,42 @@ pub enum SpfError {
     /// 'ALL' Mechanism, irrespective of relative location.\
     /// [See Section 5.1](https://datatracker.ietf.org/doc/html/rfc7208#section-5.1)
     RedirectWithAllMechanism,
-    /// REDIRECT **SHOULD** be the final item given in an Spf record when present.
-    RedirectNotFinalMechanism,
-    /// Modifiers may only occur once in any Spf Record
-    ModifierMayOccurOnlyOnce(Kind),
+    /// REDIRECT **SHOULD** follow every mechanism in an Spf record when present.
+    /// The index of the `redirect` is given.\
+    /// [See Section 6.1](https://datatracker.ietf.org/doc/html/rfc7208#section-6.1)
+    RedirectNotFinalMechanism(usize),
+    /// Any other modifier **SHOULD** also follow every mechanism. The kind and index of the
+    /// modifier are given.\
+    /// [See Section 6](https://datatracker.ietf.org/doc/html/rfc7208#section-6)
+    ModifierNotFinal(Kind, usize),
+    /// `redirect` and `exp` **MUST NOT** occur more than once in any Spf record. The same applies
+    /// to an unknown modifier name and, in [`SpfBuilder`](crate::SpfBuilder), to `ptr` and `all`.
+    /// The index is that of the repeated occurrence: a term after the first, or the position at
+    /// which a term that would repeat it was to be inserted or replaced.\
+    /// [See Section 6](https://datatracker.ietf.org/doc/html/rfc7208#section-6)
+    ModifierMayOccurOnlyOnce(Kind, usize),
     /// Network Address is not valid
-    InvalidIPAddr(IpNetworkError),
+    InvalidIPAddr(NetworkError),
     /// SpfError for an invalid Mechanism
     InvalidMechanism(MechanismError),
     /// Deprecated `ptr` detected in Spf record.\
     /// According to RFCs `ptr` is obsolete and should not be used.
     /// [See Section: 5.5](https://datatracker.ietf.org/doc/html/rfc7208#section-5.5)
     DeprecatedPtrDetected,
+    /// The TXT RDATA or its presentation format could not be read.
+    /// The reason is given in the `String`.
+    InvalidTxtRdata(String),
+    /// More than one Spf record was found for a single domain.
+    /// According to RFC7208 this results in a *permerror*. Each of the conflicting records is
+    /// given in the `Vec`.\
+    /// [See Section 4.5](https://datatracker.ietf.org/doc/html/rfc7208#section-4.5)
+    MultipleSpfRecords(Vec<String>),
+    /// There is no term at the given index.
+    IndexOutOfRange(usize),
 }
 
-impl std::fmt::Display for SpfError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SpfError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             SpfError::InvalidSource => write!(f, "Source string not valid."),
             SpfError::InvalidVersion => write!(f, "Version string not valid."),
@@ -56,8 +78,19 @@ impl std::fmt::Display for SpfError {
             SpfError::RedirectWithAllMechanism => {
                 write!(f, "Spf record contains both a 'REDIRECT' modifier and 'ALL' mechanism.\nAccording to RFC7208 any redirect MUST be ignored in this case.\n[See Section 5.1](https://datatracker.ietf.org/doc/html/rfc7208#section-5.1)")
             }
-            SpfError::RedirectNotFinalMechanism => write!(f, "Redirect not last mechanism."),
-            SpfError::ModifierMayOccurOnlyOnce(kind) => write!(f, "Mechanism: {} occurred more than once.", kind),
+            SpfError::RedirectNotFinalMechanism(index) => {
+                write!(f, "Redirect at index {} is not after the last mechanism.", index)
+            }
+            SpfError::ModifierNotFinal(kind, index) => write!(
+                f,
+                "Modifier: {} at index {} is not after the last mechanism.",
+                kind, index
+            ),
+            SpfError::ModifierMayOccurOnlyOnce(kind, index) => write!(
+                f,
+                "Mechanism: {} occurred more than once. Repeated at index {}.",
+                kind, index
+            ),
             // Is this even needed?
             SpfError::InvalidIPAddr(err) => write!(f, "{}", err),
             SpfError::InvalidMechanism(err) => write!(f, "{}", err),
@@ -65,13 +98,23 @@ impl std::fmt::Display for SpfError {
                 f,
                 "Deprecated Ptr mechanism detected.\nThe use of this mechanism is highly discouraged"
             ),
+            SpfError::InvalidTxtRdata(reason) => write!(f, "Invalid TXT data: {}", reason),
+            SpfError::MultipleSpfRecords(records) => {
+                write!(f, "Multiple Spf records found:")?;
+                for record in records {
+                    write!(f, "\n{}", record)?;
+                }
+                Ok(())
+            }
+            SpfError::IndexOutOfRange(index) => write!(f, "No term found at index {}.", index),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl From<IpNetworkError> for SpfError {
     fn from(err: IpNetworkError) -> Self {
-        SpfError::InvalidIPAddr(err)
+        SpfError::InvalidIPAddr(err.into())
     }
 }
 
@@ -81,6 +124,7 @@ impl From<MechanismError> for SpfError {
         SpfError::InvalidMechanism(err)
     }
 }
+#[cfg(feature = "std")]
 impl std::error::Error for SpfError {}
 
 impl SpfError {
@@ -146,6 +190,50 @@ impl SpfError {
             Self::InvalidMechanism(MechanismError::InvalidIPNetwork(_))
         )
     }
+    /// Returns `true` if the SpfError indicates the TXT data could not be read.
+    pub fn is_invalid_txt_rdata(&self) -> bool {
+        matches!(self, Self::InvalidTxtRdata(_))
+    }
+    /// Returns `true` if the SpfError indicates more than one Spf record was found.
+    pub fn is_multiple_spf_records(&self) -> bool {
+        matches!(self, Self::MultipleSpfRecords(_))
+    }
+    /// Returns `true` if the SpfError indicates an index which is out of range.
+    pub fn is_index_out_of_range(&self) -> bool {
+        matches!(self, Self::IndexOutOfRange(_))
+    }
+    /// Returns `true` if the SpfError indicates a `redirect` which precedes a mechanism.
+    pub fn is_redirect_not_final_mechanism(&self) -> bool {
+        matches!(self, Self::RedirectNotFinalMechanism(_))
+    }
+    /// Returns `true` if the SpfError indicates a modifier, other than `redirect`, which
+    /// precedes a mechanism.
+    pub fn is_modifier_not_final(&self) -> bool {
+        matches!(self, Self::ModifierNotFinal(..))
+    }
+    /// Returns `true` if the SpfError indicates a repeated modifier.
+    pub fn is_modifier_may_occur_only_once(&self) -> bool {
+        matches!(self, Self::ModifierMayOccurOnlyOnce(..))
+    }
+    /// The index of the term which caused the error, if the error is about a single term of
+    /// the record.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Spf, SpfError};
+    /// let spf: Spf<String> = "v=spf1 a exp=a.example.com exp=b.example.com".parse().unwrap();
+    /// let errors = spf.validate().unwrap_err();
+    /// assert_eq!(errors.errors()[0].index(), Some(2));
+    /// assert_eq!(SpfError::LookupLimitExceeded.index(), None);
+    /// ```
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Self::RedirectNotFinalMechanism(index)
+            | Self::ModifierNotFinal(_, index)
+            | Self::ModifierMayOccurOnlyOnce(_, index) => Some(*index),
+            _ => None,
+        }
+    }
 }
 /// Contains a vector of parsing or validation errors which are represented using
 /// various [SpfError] codes.
@@ -177,6 +265,10 @@ impl SpfErrors {
     pub fn errors(&self) -> &Vec<SpfError> {
         self.errors.as_ref()
     }
+    /// Returns `true` if no errors have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
 }
 
 #[test]
@@ -187,35 +279,59 @@ fn create_spf_errors() {
 #[test]
 fn is_any_spf_error() {
     let err = SpfError::InvalidSource;
-    assert_eq!(err.is_spf_error(), true);
+    assert!(err.is_spf_error());
 }
 #[test]
 fn is_invalid_source() {
     let err = SpfError::InvalidSource;
-    assert_eq!(err.is_invalid_source(), true);
+    assert!(err.is_invalid_source());
 }
 #[test]
 fn is_source_length_exceeded() {
     let err = SpfError::SourceLengthExceeded;
-    assert_eq!(err.is_source_length_exceeded(), true);
+    assert!(err.is_source_length_exceeded());
 }
 #[test]
 fn is_lookup_limit_exceeded() {
     let err = SpfError::LookupLimitExceeded;
-    assert_eq!(err.is_lookup_limit_exceeded(), true)
+    assert!(err.is_lookup_limit_exceeded())
 }
 #[test]
 fn is_has_not_been_parsed() {
     let err = SpfError::HasNotBeenParsed;
-    assert_eq!(err.is_has_not_been_parsed(), true)
+    assert!(err.is_has_not_been_parsed())
 }
 #[test]
 fn is_invalid_spf() {
     let err = SpfError::InvalidSPF;
-    assert_eq!(err.is_invalid_spf(), true)
+    assert!(err.is_invalid_spf())
+}
+#[test]
+fn is_invalid_txt_rdata() {
+    let err = SpfError::InvalidTxtRdata("unterminated quoted string".to_string());
+    assert!(err.is_invalid_txt_rdata());
+    assert_eq!(
+        err.to_string(),
+        "Invalid TXT data: unterminated quoted string"
+    );
+}
+#[test]
+fn is_multiple_spf_records() {
+    let err = SpfError::MultipleSpfRecords(vec!["v=spf1 a".to_string(), "v=spf1 mx".to_string()]);
+    assert!(err.is_multiple_spf_records());
+    assert_eq!(
+        err.to_string(),
+        "Multiple Spf records found:\nv=spf1 a\nv=spf1 mx"
+    );
 }
 #[test]
 fn is_redirect_with_all_mechanism() {
     let err = SpfError::RedirectWithAllMechanism;
-    assert_eq!(err.is_redirect_with_all_mechanism(), true)
+    assert!(err.is_redirect_with_all_mechanism())
+}
+#[test]
+fn is_index_out_of_range() {
+    let err = SpfError::IndexOutOfRange(3);
+    assert!(err.is_index_out_of_range());
+    assert_eq!(err.to_string(), "No term found at index 3.");
 }
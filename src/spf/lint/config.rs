@@ -0,0 +1,103 @@
+use crate::prelude::*;
+use crate::spf::lint::{Rule, Severity};
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The level a rule has been set to by a [`LintConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Level {
+    /// The rule is disabled.
+    Allow,
+    /// The rule is enabled at its [default severity](Rule::default_severity). This is used to
+    /// turn on a rule which is not [enabled by default](Rule::enabled_by_default).
+    Enabled,
+    /// The rule is enabled and reports [`Severity::Info`].
+    Info,
+    /// The rule is enabled and reports [`Severity::Warning`].
+    Warning,
+    /// The rule is enabled and reports [`Severity::Error`].
+    Error,
+}
+
+impl From<Severity> for Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Info => Level::Info,
+            Severity::Warning => Level::Warning,
+            Severity::Error => Level::Error,
+        }
+    }
+}
+
+/// Enables, disables or re-levels rules by their id. Rules which are not given keep their
+/// defaults.
+///
+/// # Example:
+/// ```
+/// use decon_spf::lint::{Level, LintConfig, Linter, Severity};
+/// use decon_spf::Spf;
+/// let spf: Spf<String> = "v=spf1 ptr redirect=_spf.example.com -all".parse().unwrap();
+///
+/// let mut config = LintConfig::new();
+/// config
+///     .allow("deprecated-ptr")
+///     .set_severity("redirect-with-all", Severity::Error);
+/// assert_eq!(config.level("deprecated-ptr"), Some(Level::Allow));
+///
+/// let findings = Linter::with_config(config).check(&spf);
+/// assert_eq!(findings.len(), 1);
+/// assert_eq!(findings[0].rule(), "redirect-with-all");
+/// assert!(findings[0].severity().is_error());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LintConfig {
+    rules: BTreeMap<String, Level>,
+}
+
+impl LintConfig {
+    /// Create a configuration which keeps the default of every rule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set the level of the rule `id`.
+    pub fn set(&mut self, id: &str, level: Level) -> &mut Self {
+        self.rules.insert(id.to_string(), level);
+        self
+    }
+    /// Disable the rule `id`.
+    pub fn allow(&mut self, id: &str) -> &mut Self {
+        self.set(id, Level::Allow)
+    }
+    /// Enable the rule `id` at its default severity.
+    pub fn enable(&mut self, id: &str) -> &mut Self {
+        self.set(id, Level::Enabled)
+    }
+    /// Enable the rule `id` and report its findings with `severity`.
+    pub fn set_severity(&mut self, id: &str, severity: Severity) -> &mut Self {
+        self.set(id, severity.into())
+    }
+    /// Return the rule `id` to its default.
+    pub fn reset(&mut self, id: &str) -> &mut Self {
+        self.rules.remove(id);
+        self
+    }
+    /// The level the rule `id` has been set to. `None` if it keeps its default.
+    pub fn level(&self, id: &str) -> Option<Level> {
+        self.rules.get(id).copied()
+    }
+    /// The severity `rule` reports with under this configuration. `None` if it is disabled.
+    pub fn severity_of(&self, rule: &dyn Rule) -> Option<Severity> {
+        match self.level(rule.id()) {
+            None if rule.enabled_by_default() => Some(rule.default_severity()),
+            None | Some(Level::Allow) => None,
+            Some(Level::Enabled) => Some(rule.default_severity()),
+            Some(Level::Info) => Some(Severity::Info),
+            Some(Level::Warning) => Some(Severity::Warning),
+            Some(Level::Error) => Some(Severity::Error),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The severity of a [`Finding`]. Ordered from [`Info`](Severity::Info) to
+/// [`Error`](Severity::Error).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Severity {
+    /// The record is valid but could be improved.
+    Info,
+    /// The record is valid but is unlikely to behave as intended.
+    Warning,
+    /// The record will result in a *permerror* or is otherwise broken.
+    Error,
+}
+
+impl Severity {
+    /// Returns the severity as a lowercase string slice.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+    /// Returns `true` if the severity is [`Info`](Severity::Info).
+    pub fn is_info(&self) -> bool {
+        matches!(self, Self::Info)
+    }
+    /// Returns `true` if the severity is [`Warning`](Severity::Warning).
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Self::Warning)
+    }
+    /// Returns `true` if the severity is [`Error`](Severity::Error).
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error)
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A problem reported by a [`Rule`](super::Rule).
+///
+/// Displays in the form `warning[deprecated-ptr]: message`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Finding {
+    rule: &'static str,
+    severity: Severity,
+    index: Option<usize>,
+    message: String,
+    reference: &'static str,
+}
+
+impl Finding {
+    pub(crate) fn new(
+        rule: &'static str,
+        severity: Severity,
+        index: Option<usize>,
+        message: String,
+        reference: &'static str,
+    ) -> Self {
+        Self {
+            rule,
+            severity,
+            index,
+            message,
+            reference,
+        }
+    }
+    /// The id of the rule which reported this finding.
+    pub fn rule(&self) -> &'static str {
+        self.rule
+    }
+    /// The severity, after any change made by [`LintConfig`](super::LintConfig).
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+    /// The index of the term the finding applies to. The version is not a term, so `0` is the
+    /// first term after it. `None` when the finding applies to the whole record.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+    /// A description of the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    /// A link to the section of the RFC the rule is based on.
+    pub fn reference(&self) -> &'static str {
+        self.reference
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.rule, self.message)
+    }
+}
@@ -0,0 +1,272 @@
+//! A configurable lint engine for Spf records.
+//!
+//! Each [`Rule`] has a stable id, a default [`Severity`], a description and a link to the
+//! RFC section it is based on. A [`Linter`] runs its rules over a record and returns a
+//! [`Finding`] for every problem found. Rules can be disabled, enabled or given a different
+//! severity with a [`LintConfig`].
+//!
+//! The built-in rules are found in [`rules`]. Custom rules are added with [`Linter::register`].
+//!
+//! # Example:
+//! ```
+//! use decon_spf::lint::{Linter, Severity};
+//! use decon_spf::Spf;
+//! let spf: Spf<String> = "v=spf1 mx redirect=_spf.example.com ?all".parse().unwrap();
+//! let findings = Linter::new().check(&spf);
+//! assert_eq!(findings.len(), 2);
+//! assert_eq!(findings[0].rule(), "redirect-with-all");
+//! assert_eq!(findings[0].severity(), Severity::Warning);
+//! assert_eq!(findings[0].index(), Some(1));
+//! assert_eq!(
+//!     findings[0].to_string(),
+//!     "warning[redirect-with-all]: `redirect` is ignored because `all` is present"
+//! );
+//! for finding in &findings {
+//!     println!("{}", finding);
+//! }
+//! ```
+mod config;
+mod finding;
+mod networks;
+pub mod rules;
+mod shadow;
+mod strength;
+#[cfg(test)]
+mod tests;
+
+pub use crate::spf::lint::config::{Level, LintConfig};
+pub use crate::spf::lint::finding::{Finding, Severity};
+pub use crate::spf::lint::networks::{overlapping_networks, Overlap, Relation};
+pub use crate::spf::lint::shadow::{shadowed_terms, Shadow, Shadowed};
+pub use crate::spf::lint::strength::{strength, Deduction, Grade, Strength, Weakness};
+
+use crate::mechanism::Mechanism;
+use crate::prelude::*;
+use crate::{Spf, Version};
+use core::fmt::{Display, Formatter};
+
+/// The parts of a record that are checked by a [`Rule`].
+#[derive(Debug, Clone, Copy)]
+pub struct Record<'a> {
+    version: &'a Version,
+    terms: &'a [Mechanism<String>],
+    source: Option<&'a str>,
+}
+
+impl<'a> Record<'a> {
+    /// The version of the record.
+    pub fn version(&self) -> &'a Version {
+        self.version
+    }
+    /// The terms of the record in the order they are evaluated. The version is not included.
+    pub fn terms(&self) -> &'a [Mechanism<String>] {
+        self.terms
+    }
+    /// The text the record was parsed from. `None` if the record was built.
+    pub fn source(&self) -> Option<&'a str> {
+        self.source
+    }
+}
+
+/// Displays the source of a parsed record, otherwise the version and terms.
+impl Display for Record<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let Some(source) = self.source {
+            return write!(f, "{}", source);
+        }
+        write!(f, "{}", self.version)?;
+        for m in self.terms {
+            write!(f, " {}", m)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a Spf<String>> for Record<'a> {
+    fn from(spf: &'a Spf<String>) -> Self {
+        Self {
+            version: &spf.version,
+            terms: &spf.mechanisms,
+            source: (!spf.built()).then_some(spf.source.as_str()),
+        }
+    }
+}
+
+/// A record which has been built has no source.
+#[cfg_attr(docsrs, doc(cfg(feature = "builder")))]
+#[cfg(feature = "builder")]
+impl<'a, State> From<&'a crate::SpfBuilder<State>> for Record<'a> {
+    fn from(spf: &'a crate::SpfBuilder<State>) -> Self {
+        Self {
+            version: spf.version(),
+            terms: spf.terms(),
+            source: None,
+        }
+    }
+}
+
+/// Collects the findings of a single [`Rule`].
+pub struct Context<'a> {
+    rule: &'a dyn Rule,
+    severity: Severity,
+    findings: &'a mut Vec<Finding>,
+}
+
+impl Context<'_> {
+    /// Report a problem with the term at `index`.
+    pub fn report(&mut self, index: usize, message: impl Into<String>) {
+        self.push(Some(index), message.into());
+    }
+    /// Report a problem with the record as a whole.
+    pub fn report_record(&mut self, message: impl Into<String>) {
+        self.push(None, message.into());
+    }
+    fn push(&mut self, index: Option<usize>, message: String) {
+        self.findings.push(Finding::new(
+            self.rule.id(),
+            self.severity,
+            index,
+            message,
+            self.rule.reference(),
+        ));
+    }
+}
+
+/// A single check of a record.
+///
+/// # Example:
+/// ```
+/// use decon_spf::lint::{Context, Linter, Record, Rule, Severity};
+/// use decon_spf::Spf;
+///
+/// struct NoExists;
+///
+/// impl Rule for NoExists {
+///     fn id(&self) -> &'static str {
+///         "no-exists"
+///     }
+///     fn default_severity(&self) -> Severity {
+///         Severity::Info
+///     }
+///     fn description(&self) -> &'static str {
+///         "Our policy does not permit `exists`."
+///     }
+///     fn reference(&self) -> &'static str {
+///         "https://datatracker.ietf.org/doc/html/rfc7208#section-5.7"
+///     }
+///     fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+///         for (idx, m) in record.terms().iter().enumerate() {
+///             if m.kind().is_exists() {
+///                 cx.report(idx, "`exists` is not permitted");
+///             }
+///         }
+///     }
+/// }
+///
+/// let spf: Spf<String> = "v=spf1 exists:%{i}.example.com -all".parse().unwrap();
+/// let mut linter = Linter::new();
+/// linter.register(NoExists);
+/// let findings = linter.check(&spf);
+/// assert_eq!(findings[0].rule(), "no-exists");
+/// ```
+pub trait Rule: Send + Sync {
+    /// A stable id which is used to refer to the rule in a [`LintConfig`].
+    fn id(&self) -> &'static str;
+    /// The severity of the findings of this rule, unless changed by a [`LintConfig`].
+    fn default_severity(&self) -> Severity;
+    /// `false` if the rule must be enabled by a [`LintConfig`].
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+    /// A description of what the rule checks for and why.
+    fn description(&self) -> &'static str;
+    /// A link to the section of the RFC the rule is based on.
+    fn reference(&self) -> &'static str;
+    /// Check `record` and report each problem to `cx`.
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>);
+}
+
+/// Runs a set of [`Rule`]s over a record.
+pub struct Linter {
+    rules: Vec<Box<dyn Rule>>,
+    config: LintConfig,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linter {
+    /// Create a linter with the [built-in rules](rules) and their defaults.
+    pub fn new() -> Self {
+        Self::with_config(LintConfig::new())
+    }
+    /// Create a linter with the [built-in rules](rules) and `config`.
+    pub fn with_config(config: LintConfig) -> Self {
+        Self {
+            rules: rules::builtin(),
+            config,
+        }
+    }
+    /// Create a linter without any rules.
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            config: LintConfig::new(),
+        }
+    }
+    /// Add `rule` to the rules which are run.
+    pub fn register<R: Rule + 'static>(&mut self, rule: R) -> &mut Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+    /// Access the configuration.
+    pub fn config(&self) -> &LintConfig {
+        &self.config
+    }
+    /// Access the configuration to change it.
+    pub fn config_mut(&mut self) -> &mut LintConfig {
+        &mut self.config
+    }
+    /// Iterate over the rules, enabled or not.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|r| r.as_ref())
+    }
+    /// Find the rule with `id`.
+    pub fn rule(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules().find(|r| r.id() == id)
+    }
+    /// Run every enabled rule over `spf`.
+    ///
+    /// Findings are grouped by rule, in the order the rules were registered.
+    pub fn check(&self, spf: &Spf<String>) -> Vec<Finding> {
+        self.check_record(&Record::from(spf))
+    }
+    /// Run every enabled rule over `record`.
+    pub fn check_record(&self, record: &Record<'_>) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for rule in self.rules() {
+            if let Some(severity) = self.config.severity_of(rule) {
+                run(rule, severity, record, &mut findings);
+            }
+        }
+        findings
+    }
+}
+
+/// Run `rule` over `record`, adding its findings with `severity` to `findings`.
+pub(crate) fn run(
+    rule: &dyn Rule,
+    severity: Severity,
+    record: &Record<'_>,
+    findings: &mut Vec<Finding>,
+) {
+    let mut cx = Context {
+        rule,
+        severity,
+        findings,
+    };
+    rule.check(record, &mut cx);
+}
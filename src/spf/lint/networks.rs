@@ -0,0 +1,116 @@
+use crate::core::cidr::Cidr;
+use crate::prelude::*;
+use crate::spf::lint::Record;
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// How two networks with the same qualifier are related.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Relation {
+    /// The networks have at least one address in common.
+    Overlaps,
+    /// One network ends immediately before the other begins.
+    Adjacent,
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Relation::Overlaps => write!(f, "overlaps"),
+            Relation::Adjacent => write!(f, "is adjacent to"),
+        }
+    }
+}
+
+/// A pair of `ip4` or `ip6` terms with the same qualifier which overlap or are adjacent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Overlap {
+    index: usize,
+    other: usize,
+    relation: Relation,
+    combined: Option<String>,
+}
+
+impl Overlap {
+    /// The index of the later term.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// The index of the earlier term.
+    pub fn other(&self) -> usize {
+        self.other
+    }
+    /// How the two networks are related.
+    pub fn relation(&self) -> Relation {
+        self.relation
+    }
+    /// The single network, as it would appear in a mechanism, which covers exactly the
+    /// addresses of both terms. `None` if more than one network is needed.
+    pub fn combined(&self) -> Option<&str> {
+        self.combined.as_deref()
+    }
+}
+
+/// Find every pair of `ip4` or `ip6` terms of `record` with the same qualifier which overlap
+/// or are adjacent. Pairs are ordered by the later term and then the earlier term.
+///
+/// Terms after `all` are not considered. To merge the networks use
+/// `SpfBuilder::aggregate_networks()`.
+///
+/// # Example:
+/// ```
+/// use decon_spf::lint::{overlapping_networks, Record, Relation};
+/// use decon_spf::Spf;
+/// let spf: Spf<String> = "v=spf1 ip4:10.0.0.0/24 ip4:10.0.1.0/24 ip4:10.0.0.0/23 -all"
+///     .parse()
+///     .unwrap();
+/// let overlaps = overlapping_networks(&Record::from(&spf));
+/// assert_eq!(overlaps.len(), 3);
+/// assert_eq!((overlaps[0].other(), overlaps[0].index()), (0, 1));
+/// assert_eq!(overlaps[0].relation(), Relation::Adjacent);
+/// assert_eq!(overlaps[0].combined(), Some("10.0.0.0/23"));
+/// assert_eq!(overlaps[1].relation(), Relation::Overlaps);
+/// ```
+pub fn overlapping_networks(record: &Record<'_>) -> Vec<Overlap> {
+    let terms = record.terms();
+    let end = terms
+        .iter()
+        .position(|m| m.kind().is_all())
+        .unwrap_or(terms.len());
+    let networks: Vec<Option<Cidr>> = terms[..end].iter().map(Cidr::from_mechanism).collect();
+    let mut overlaps = Vec::new();
+    for (index, cidr) in networks.iter().enumerate() {
+        let cidr = match cidr {
+            Some(cidr) => cidr,
+            None => continue,
+        };
+        for (other, earlier) in networks[..index].iter().enumerate() {
+            let earlier = match earlier {
+                Some(earlier) if terms[other].qualifier() == terms[index].qualifier() => earlier,
+                _ => continue,
+            };
+            let relation = if earlier.overlaps(cidr) {
+                Relation::Overlaps
+            } else if earlier.is_adjacent(cidr) {
+                Relation::Adjacent
+            } else {
+                continue;
+            };
+            let combined = match crate::core::cidr::aggregate(&[*earlier, *cidr]).as_slice() {
+                [one] => Some(one.to_string()),
+                _ => None,
+            };
+            overlaps.push(Overlap {
+                index,
+                other,
+                relation,
+                combined,
+            });
+        }
+    }
+    overlaps
+}
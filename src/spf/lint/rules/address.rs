@@ -0,0 +1,159 @@
+//! Rules which check the addresses of `ip4` and `ip6` mechanisms.
+use crate::core::cidr::{net, Cidr};
+use crate::mechanism::{Kind, Mechanism};
+use crate::prelude::*;
+use crate::spf::lint::{Context, Record, Rule, Severity};
+use core::net::IpAddr;
+
+// Networks reserved for use in documentation and examples.
+const DOCUMENTATION_NETWORKS: [&str; 5] = [
+    "192.0.2.0/24",
+    "198.51.100.0/24",
+    "203.0.113.0/24",
+    "2001:db8::/32",
+    "3fff::/20",
+];
+
+/// `host-bits-set`: The address of an `ip4` or `ip6` network has bits set beyond the prefix
+/// length. These bits are ignored, so `ip4:192.0.2.55/24` is `ip4:192.0.2.0/24`.
+///
+/// [RFC7208 Section 5.6](https://datatracker.ietf.org/doc/html/rfc7208#section-5.6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostBitsSet;
+
+impl Rule for HostBitsSet {
+    fn id(&self) -> &'static str {
+        "host-bits-set"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Only the bits within the prefix length are compared. An address with host bits set \
+         suggests that a single host or a different prefix length was intended."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            match Cidr::from_mechanism(m) {
+                Some(cidr) if cidr.has_host_bits() => cx.report(
+                    idx,
+                    format!(
+                        "`{}` has host bits set, the network is `{}`",
+                        m,
+                        with_network(m, *m.kind(), &cidr.network())
+                    ),
+                ),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// `mapped-ip6`: An `ip6` network within `::ffff:0:0/96` (IPv4-mapped) or `::/96`
+/// (IPv4-compatible). Mail received over IPv4 is only compared with `ip4`, so these networks
+/// never match.
+///
+/// [RFC7208 Section 5.6](https://datatracker.ietf.org/doc/html/rfc7208#section-5.6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MappedIp6;
+
+impl Rule for MappedIp6 {
+    fn id(&self) -> &'static str {
+        "mapped-ip6"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "`ip4` is compared with IPv4 connections and `ip6` with IPv6 connections. An IPv4 \
+         address written as an IPv4-mapped or IPv4-compatible `ip6` address never matches."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            let cidr = match Cidr::from_mechanism(m) {
+                Some(cidr) if !cidr.is_ipv4() => cidr,
+                _ => continue,
+            };
+            let form = if net("::ffff:0:0/96").contains(&cidr) {
+                "IPv4-mapped"
+            } else if net("::/96").contains(&cidr) && !net("::/127").contains(&cidr) {
+                "IPv4-compatible"
+            } else {
+                continue;
+            };
+            let ip4 = match cidr.addr() {
+                IpAddr::V6(ip) => {
+                    let [.., a, b, c, d] = ip.octets();
+                    Cidr::parse(
+                        Kind::IpV4,
+                        &format!("{}.{}.{}.{}/{}", a, b, c, d, cidr.prefix() - 96),
+                    )
+                }
+                IpAddr::V4(_) => None,
+            };
+            if let Some(ip4) = ip4 {
+                cx.report(
+                    idx,
+                    format!(
+                        "`{}` is an {} address, use `{}`",
+                        m,
+                        form,
+                        with_network(m, Kind::IpV4, &ip4)
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// `documentation-network`: A network reserved for documentation, such as `192.0.2.0/24` or
+/// `2001:db8::/32`, is published. These are never used by real hosts.
+///
+/// [RFC5737](https://datatracker.ietf.org/doc/html/rfc5737),
+/// [RFC3849](https://datatracker.ietf.org/doc/html/rfc3849)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DocumentationNetwork;
+
+impl Rule for DocumentationNetwork {
+    fn id(&self) -> &'static str {
+        "documentation-network"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Documentation networks are reserved for examples. A record which lists one was most \
+         likely copied from an example and not completed."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc5737"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            let cidr = match Cidr::from_mechanism(m) {
+                Some(cidr) => cidr,
+                None => continue,
+            };
+            let range = DOCUMENTATION_NETWORKS
+                .iter()
+                .find(|range| net(range).contains(&cidr));
+            if let Some(range) = range {
+                cx.report(
+                    idx,
+                    format!("`{}` is within the documentation network {}", m, range),
+                );
+            }
+        }
+    }
+}
+
+// `m` with its network replaced by `cidr`.
+fn with_network(m: &Mechanism<String>, kind: Kind, cidr: &Cidr) -> Mechanism<String> {
+    Mechanism::generic_inclusive(kind, *m.qualifier(), Some(cidr.to_string()))
+}
@@ -0,0 +1,74 @@
+//! The built-in [`Rule`]s.
+//!
+//! The rules up to and including `whitespace` are the checks made by
+//! [`Spf::validate()`](crate::Spf::validate) and
+//! [`Spf::validation_report()`](crate::Spf::validation_report).
+//!
+//! | Id | Default | Checks |
+//! |----|---------|--------|
+//! | `invalid-version` | Error | A parsed record does not start with a complete version. |
+//! | `record-length` | Error | The record is longer than 512 characters. |
+//! | `deprecated-ptr` | Warning | A `ptr` mechanism is present. Only enabled by default with the `ptr` feature. |
+//! | `lookup-limit` | Error | The record requires more than 10 DNS lookups. |
+//! | `redirect-with-all` | Warning | A `redirect` is ignored as `all` is present. |
+//! | `repeated-modifier` | Error | `redirect` or `exp` appears more than once. |
+//! | `repeated-unknown-modifier` | Warning | An unknown modifier name appears more than once. |
+//! | `modifier-not-last` | Warning | A `redirect`, `exp` or unknown modifier is followed by a mechanism. |
+//! | `whitespace` | Warning | Terms are separated by more than a single space. |
+//! | `pass-all` | Error | `+all` authorizes every host. |
+//! | `neutral-all` | Warning | `?all` makes no assertion about other hosts. |
+//! | `missing-all` | Warning | There is neither `all` nor `redirect`. |
+//! | `open-network` | Error | `ip4:0.0.0.0/0` or `ip6:::/0` authorizes every address. |
+//! | `broad-network` | Warning | An `ip4` prefix shorter than /16 or `ip6` prefix shorter than /32. |
+//! | `private-network` | Warning | A private, loopback or reserved network is published. |
+//! | `broad-dual-cidr` | Warning | `a` or `mx` with a cidr length shorter than /16 or /32. |
+//! | `unreachable-term` | Warning | A term follows `all`, is covered by an earlier network or duplicates an earlier term. |
+//! | `mergeable-networks` | Info | Two networks with the same qualifier overlap or are adjacent and can be combined. |
+//! | `host-bits-set` | Warning | An `ip4` or `ip6` address has bits set beyond the prefix length. |
+//! | `mapped-ip6` | Warning | An IPv4-mapped or IPv4-compatible address is used in `ip6`. |
+//! | `documentation-network` | Warning | A network reserved for documentation is published. |
+mod address;
+mod policy;
+mod reachability;
+mod record;
+
+pub use crate::spf::lint::rules::address::{DocumentationNetwork, HostBitsSet, MappedIp6};
+pub use crate::spf::lint::rules::policy::{
+    BroadDualCidr, BroadNetwork, MissingAll, NeutralAll, OpenNetwork, PassAll, PrivateNetwork,
+};
+pub(crate) use crate::spf::lint::rules::policy::{MIN_IP4_PREFIX, MIN_IP6_PREFIX};
+pub use crate::spf::lint::rules::reachability::{MergeableNetworks, UnreachableTerm};
+pub use crate::spf::lint::rules::record::{
+    DeprecatedPtr, InvalidVersion, LookupLimit, ModifierNotLast, RecordLength, RedirectWithAll,
+    RepeatedModifier, RepeatedUnknownModifier, Whitespace,
+};
+
+use crate::prelude::*;
+use crate::spf::lint::Rule;
+
+// The rules registered by `Linter::new()`, in the order they are run.
+pub(crate) fn builtin() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(InvalidVersion),
+        Box::new(RecordLength),
+        Box::new(DeprecatedPtr),
+        Box::new(LookupLimit),
+        Box::new(RedirectWithAll),
+        Box::new(RepeatedModifier),
+        Box::new(RepeatedUnknownModifier),
+        Box::new(ModifierNotLast),
+        Box::new(Whitespace),
+        Box::new(PassAll),
+        Box::new(NeutralAll),
+        Box::new(MissingAll),
+        Box::new(OpenNetwork),
+        Box::new(BroadNetwork),
+        Box::new(PrivateNetwork),
+        Box::new(BroadDualCidr),
+        Box::new(UnreachableTerm),
+        Box::new(MergeableNetworks),
+        Box::new(HostBitsSet),
+        Box::new(MappedIp6),
+        Box::new(DocumentationNetwork),
+    ]
+}
@@ -0,0 +1,276 @@
+//! Rules which find weak or dangerous policies.
+use crate::core::cidr::{net, Cidr};
+use crate::mechanism::{Mechanism, Qualifier};
+use crate::prelude::*;
+use crate::spf::lint::{Context, Record, Rule, Severity};
+
+// The shortest prefixes which are not reported as broad.
+pub(crate) const MIN_IP4_PREFIX: u8 = 16;
+pub(crate) const MIN_IP6_PREFIX: u8 = 32;
+
+// Networks which are never reachable from the public internet.
+const PRIVATE_NETWORKS: [(&str, &str); 15] = [
+    ("0.0.0.0/8", "\"this\" network"),
+    ("10.0.0.0/8", "private-use"),
+    ("100.64.0.0/10", "shared address space"),
+    ("127.0.0.0/8", "loopback"),
+    ("169.254.0.0/16", "link local"),
+    ("172.16.0.0/12", "private-use"),
+    ("192.168.0.0/16", "private-use"),
+    ("198.18.0.0/15", "benchmarking"),
+    ("224.0.0.0/4", "multicast"),
+    ("240.0.0.0/4", "reserved"),
+    ("::/127", "unspecified and loopback"),
+    ("fc00::/7", "unique-local"),
+    ("fe80::/10", "link local"),
+    ("ff00::/8", "multicast"),
+    ("100::/64", "discard-only"),
+];
+
+fn is_pass_all(m: &Mechanism<String>) -> bool {
+    m.kind().is_all() && m.qualifier().is_pass()
+}
+
+/// `pass-all`: `+all` or `all` authorizes every host on the internet to send mail for the
+/// domain.
+///
+/// [RFC7208 Section 5.1](https://datatracker.ietf.org/doc/html/rfc7208#section-5.1)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PassAll;
+
+impl Rule for PassAll {
+    fn id(&self) -> &'static str {
+        "pass-all"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn description(&self) -> &'static str {
+        "`+all` matches every host, so any host on the internet is authorized to send mail for \
+         the domain. Use `-all` or `~all`."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.1"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            if is_pass_all(m) {
+                cx.report(idx, format!("`{}` authorizes every host", m));
+            }
+        }
+    }
+}
+
+/// `neutral-all`: `?all` gives a result which must be treated exactly like having no record.
+///
+/// [RFC7208 Section 8.2](https://datatracker.ietf.org/doc/html/rfc7208#section-8.2)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NeutralAll;
+
+impl Rule for NeutralAll {
+    fn id(&self) -> &'static str {
+        "neutral-all"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "A `neutral` result MUST be treated exactly like `none`. `?all` makes no assertion about \
+         hosts which are not listed."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-8.2"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            if m.kind().is_all() && m.qualifier() == &Qualifier::Neutral {
+                cx.report(idx, "`?all` makes no assertion about other hosts");
+            }
+        }
+    }
+}
+
+/// `missing-all`: A record with neither `all` nor `redirect` returns `neutral` for every host
+/// which is not listed.
+///
+/// [RFC7208 Section 4.7](https://datatracker.ietf.org/doc/html/rfc7208#section-4.7)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MissingAll;
+
+impl Rule for MissingAll {
+    fn id(&self) -> &'static str {
+        "missing-all"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "When no mechanism matches and there is no `redirect`, the result is `neutral`. End the \
+         record with `-all` or `~all`."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-4.7"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        let terms = record.terms();
+        if !terms
+            .iter()
+            .any(|m| m.kind().is_all() || m.kind().is_redirect())
+        {
+            cx.report_record("there is no `all` or `redirect`, other hosts are `neutral`");
+        }
+    }
+}
+
+/// `open-network`: `ip4:0.0.0.0/0` or `ip6:::/0` authorizes every address of the family.
+///
+/// [RFC7208 Section 5.6](https://datatracker.ietf.org/doc/html/rfc7208#section-5.6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpenNetwork;
+
+impl Rule for OpenNetwork {
+    fn id(&self) -> &'static str {
+        "open-network"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn description(&self) -> &'static str {
+        "A prefix length of 0 matches every address, so any host is authorized, just as with \
+         `+all`."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            match Cidr::from_mechanism(m) {
+                Some(cidr) if cidr.prefix() == 0 && m.qualifier().is_pass() => {
+                    cx.report(idx, format!("`{}` authorizes every address", m))
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// `broad-network`: An `ip4` prefix shorter than /16 or an `ip6` prefix shorter than /32
+/// authorizes far more hosts than a domain is likely to operate.
+///
+/// [RFC7208 Section 5.6](https://datatracker.ietf.org/doc/html/rfc7208#section-5.6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BroadNetwork;
+
+impl Rule for BroadNetwork {
+    fn id(&self) -> &'static str {
+        "broad-network"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Short prefixes authorize very large numbers of hosts, which are unlikely to all be \
+         under the control of the domain."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            let cidr = match Cidr::from_mechanism(m) {
+                Some(cidr) if cidr.prefix() > 0 && m.qualifier().is_pass() => cidr,
+                _ => continue,
+            };
+            let min = match cidr.is_ipv4() {
+                true => MIN_IP4_PREFIX,
+                false => MIN_IP6_PREFIX,
+            };
+            if cidr.prefix() < min {
+                cx.report(idx, format!("`{}` is broader than /{}", m, min));
+            }
+        }
+    }
+}
+
+/// `private-network`: Private, loopback and reserved networks can never be the source of mail
+/// received from the internet. Publishing them reveals internal addressing.
+///
+/// [RFC7208 Section 5.6](https://datatracker.ietf.org/doc/html/rfc7208#section-5.6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrivateNetwork;
+
+impl Rule for PrivateNetwork {
+    fn id(&self) -> &'static str {
+        "private-network"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Private, loopback and reserved networks are not reachable from the internet. They have \
+         no effect in a public record and reveal internal addressing."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            let cidr = match Cidr::from_mechanism(m) {
+                Some(cidr) => cidr,
+                None => continue,
+            };
+            let private = PRIVATE_NETWORKS
+                .iter()
+                .find(|(range, _)| net(range).contains(&cidr));
+            if let Some((range, name)) = private {
+                cx.report(
+                    idx,
+                    format!("`{}` is within the {} network {}", m, name, range),
+                );
+            }
+        }
+    }
+}
+
+/// `broad-dual-cidr`: An `a` or `mx` with an `ip4-cidr-length` shorter than /16 or an
+/// `ip6-cidr-length` shorter than /32 authorizes a large network around every address found.
+///
+/// [RFC7208 Section 5.3](https://datatracker.ietf.org/doc/html/rfc7208#section-5.3)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BroadDualCidr;
+
+impl Rule for BroadDualCidr {
+    fn id(&self) -> &'static str {
+        "broad-dual-cidr"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "The `dual-cidr-length` of `a` and `mx` is applied to every address found. Short \
+         lengths authorize large networks which the domain is unlikely to control."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.3"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            let cidr = match m.cidr() {
+                Some(cidr) if m.qualifier().is_pass() => cidr,
+                _ => continue,
+            };
+            if let Some(len) = cidr.ip4().filter(|len| *len < MIN_IP4_PREFIX) {
+                cx.report(
+                    idx,
+                    format!("`{}` authorizes an ip4 /{} around each address", m, len),
+                );
+            }
+            if let Some(len) = cidr.ip6().filter(|len| *len < MIN_IP6_PREFIX) {
+                cx.report(
+                    idx,
+                    format!("`{}` authorizes an ip6 /{} around each address", m, len),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Rules which find terms that have no effect or can be combined.
+use crate::core::cidr::Cidr;
+use crate::mechanism::Mechanism;
+use crate::prelude::*;
+use crate::spf::lint::{
+    overlapping_networks, shadowed_terms, Context, Record, Rule, Severity, Shadow,
+};
+
+/// `unreachable-term`: A term which can never influence the result. It follows `all`, its
+/// network is covered by an earlier `ip4` or `ip6`, or it duplicates an earlier mechanism.
+/// The earlier term is named in the message.
+///
+/// A `redirect` which is ignored because of `all` is reported by
+/// [`RedirectWithAll`](super::RedirectWithAll).
+///
+/// See [`shadowed_terms`] for the analysis this rule is based on.\
+/// [RFC7208 Section 4.6.2](https://datatracker.ietf.org/doc/html/rfc7208#section-4.6.2)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnreachableTerm;
+
+impl Rule for UnreachableTerm {
+    fn id(&self) -> &'static str {
+        "unreachable-term"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Mechanisms are evaluated in order and the first match decides the result. A mechanism \
+         which follows `all`, or which can only match when an earlier mechanism already has, is \
+         never used."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-4.6.2"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        let terms = record.terms();
+        for shadowed in shadowed_terms(record) {
+            if shadowed.reason() == Shadow::RedirectWithAll {
+                continue;
+            }
+            cx.report(
+                shadowed.index(),
+                format!(
+                    "`{}` {} `{}` at index {}",
+                    terms[shadowed.index()],
+                    shadowed.reason(),
+                    terms[shadowed.shadowed_by()],
+                    shadowed.shadowed_by()
+                ),
+            );
+        }
+    }
+}
+
+/// `mergeable-networks`: Two `ip4` or `ip6` terms with the same qualifier overlap or are
+/// adjacent, and a single network would cover the addresses of both.
+///
+/// A network which is covered by an earlier one is reported by [`UnreachableTerm`]. See
+/// [`overlapping_networks`] for the analysis this rule is based on.\
+/// [RFC7208 Section 3.4](https://datatracker.ietf.org/doc/html/rfc7208#section-3.4)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeableNetworks;
+
+impl Rule for MergeableNetworks {
+    fn id(&self) -> &'static str {
+        "mergeable-networks"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn description(&self) -> &'static str {
+        "Overlapping and adjacent networks use more of the space available to a record than \
+         needed. `SpfBuilder::aggregate_networks()` merges them."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-3.4"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        let terms = record.terms();
+        for overlap in overlapping_networks(record) {
+            let (later, earlier) = (&terms[overlap.index()], &terms[overlap.other()]);
+            let covered = match (Cidr::from_mechanism(earlier), Cidr::from_mechanism(later)) {
+                (Some(earlier), Some(later)) => earlier.contains(&later),
+                _ => false,
+            };
+            let combined = match overlap.combined() {
+                Some(combined) if !covered => Mechanism::generic_inclusive(
+                    *later.kind(),
+                    *later.qualifier(),
+                    Some(combined.to_string()),
+                ),
+                _ => continue,
+            };
+            cx.report(
+                overlap.index(),
+                format!(
+                    "`{}` {} `{}` at index {}, they can be combined as `{}`",
+                    later,
+                    overlap.relation(),
+                    earlier,
+                    overlap.other(),
+                    combined
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! The checks made by [`Spf::validate()`](crate::Spf::validate).
+use crate::core::{spf_check_whitespace, DNS_LOOKUP_LIMIT, MAX_SPF_STRING_LENGTH};
+use crate::mechanism::Mechanism;
+use crate::prelude::*;
+use crate::spf::lint::{Context, Record, Rule, Severity};
+use crate::spf::validate::{check_start_of_spf, lookup_count};
+
+/// `invalid-version`: A parsed record must start with a complete version, `v=spf1` or
+/// `spf2.0/` and its scopes. A version which has been set is always valid.
+///
+/// [RFC7208 Section 4.5](https://datatracker.ietf.org/doc/html/rfc7208#section-4.5)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InvalidVersion;
+
+impl Rule for InvalidVersion {
+    fn id(&self) -> &'static str {
+        "invalid-version"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn description(&self) -> &'static str {
+        "The record MUST begin with the version section `v=spf1`."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-4.5"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        if record
+            .source()
+            .is_some_and(|s| check_start_of_spf(s).is_err())
+        {
+            cx.report_record("the record does not start with `v=spf1` or `spf2.0/`");
+        }
+    }
+}
+
+/// `record-length`: The record must not be longer than 512 characters.
+///
+/// [RFC7208 Section 3.4](https://datatracker.ietf.org/doc/html/rfc7208#section-3.4)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecordLength;
+
+impl Rule for RecordLength {
+    fn id(&self) -> &'static str {
+        "record-length"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn description(&self) -> &'static str {
+        "The record must not be longer than 512 characters."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-3.4"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        let len = match record.source() {
+            Some(source) => source.len(),
+            None => record.to_string().len(),
+        };
+        if len > MAX_SPF_STRING_LENGTH {
+            cx.report_record("the record is longer than 512 characters");
+        }
+    }
+}
+
+/// `deprecated-ptr`: The `ptr` mechanism is slow, unreliable and places a large burden on the
+/// `.arpa` name servers.
+///
+/// This is only enabled by default when the `ptr` feature is enabled.
+///
+/// [RFC7208 Section 5.5](https://datatracker.ietf.org/doc/html/rfc7208#section-5.5)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeprecatedPtr;
+
+impl Rule for DeprecatedPtr {
+    fn id(&self) -> &'static str {
+        "deprecated-ptr"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn enabled_by_default(&self) -> bool {
+        cfg!(feature = "ptr")
+    }
+    fn description(&self) -> &'static str {
+        "The `ptr` mechanism SHOULD NOT be published. It is slow, unreliable and places a large \
+         burden on the .arpa name servers."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.5"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        for (idx, m) in record.terms().iter().enumerate() {
+            if m.kind().is_ptr() {
+                cx.report(idx, "`ptr` should not be used");
+            }
+        }
+    }
+}
+
+/// `redirect-with-all`: A `redirect` modifier is ignored when there is an `all` mechanism
+/// anywhere in the record.
+///
+/// [RFC7208 Section 5.1](https://datatracker.ietf.org/doc/html/rfc7208#section-5.1)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RedirectWithAll;
+
+impl Rule for RedirectWithAll {
+    fn id(&self) -> &'static str {
+        "redirect-with-all"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Any `redirect` modifier MUST be ignored when there is an `all` mechanism in the record, \
+         regardless of the relative ordering."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-5.1"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        let terms = record.terms();
+        if !terms.iter().any(|m| m.kind().is_all()) {
+            return;
+        }
+        for (idx, m) in terms.iter().enumerate() {
+            if m.kind().is_redirect() {
+                cx.report(idx, "`redirect` is ignored because `all` is present");
+            }
+        }
+    }
+}
+
+/// `modifier-not-last`: A modifier, whether `redirect`, `exp` or unknown, should appear after
+/// all mechanisms. Other modifiers may follow it. A `redirect` in a record with `all` is
+/// reported by [`RedirectWithAll`] instead.
+///
+/// [RFC7208 Section 6](https://datatracker.ietf.org/doc/html/rfc7208#section-6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModifierNotLast;
+
+impl Rule for ModifierNotLast {
+    fn id(&self) -> &'static str {
+        "modifier-not-last"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Modifiers, such as `redirect` and `exp`, SHOULD appear at the end of the record, after \
+         all mechanisms."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        let terms = record.terms();
+        let has_all = terms.iter().any(|m| m.kind().is_all());
+        for (idx, m) in terms.iter().enumerate() {
+            if !m.kind().is_modifier() || (has_all && m.kind().is_redirect()) {
+                continue;
+            }
+            if terms[idx..].iter().any(|m| !m.kind().is_modifier()) {
+                cx.report(idx, format!("`{}` is followed by a mechanism", m));
+            }
+        }
+    }
+}
+
+/// `lookup-limit`: Evaluating the record must not require more than 10 DNS lookups. The
+/// `a`, `mx`, `ptr`, `include` and `exists` mechanisms and the `redirect` modifier each count
+/// as one lookup.
+///
+/// [RFC7208 Section 4.6.4](https://datatracker.ietf.org/doc/html/rfc7208#section-4.6.4)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LookupLimit;
+
+impl Rule for LookupLimit {
+    fn id(&self) -> &'static str {
+        "lookup-limit"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn description(&self) -> &'static str {
+        "More than 10 DNS lookups MUST result in a permerror. Lookups made by included records \
+         are not counted."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-4.6.4"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        let count = lookup_count(record.terms());
+        if count > DNS_LOOKUP_LIMIT {
+            cx.report_record(format!(
+                "{} DNS lookups are required, the limit is {}",
+                count, DNS_LOOKUP_LIMIT
+            ));
+        }
+    }
+}
+
+/// `repeated-modifier`: The `redirect` and `exp` modifiers must not appear more than once.
+/// Each occurrence after the first is reported.
+///
+/// [RFC7208 Section 6](https://datatracker.ietf.org/doc/html/rfc7208#section-6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RepeatedModifier;
+
+impl Rule for RepeatedModifier {
+    fn id(&self) -> &'static str {
+        "repeated-modifier"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn description(&self) -> &'static str {
+        "The `redirect` and `exp` modifiers MUST NOT appear in a record more than once each."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        report_repeated(record, cx, |m| m.kind().is_redirect() || m.kind().is_exp());
+    }
+}
+
+/// `repeated-unknown-modifier`: An unknown modifier name appears more than once, compared
+/// without regard to case. Each occurrence after the first is reported.
+///
+/// [RFC7208 Section 6](https://datatracker.ietf.org/doc/html/rfc7208#section-6)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RepeatedUnknownModifier;
+
+impl Rule for RepeatedUnknownModifier {
+    fn id(&self) -> &'static str {
+        "repeated-unknown-modifier"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Modifiers are not expected to appear more than once. Only `redirect` and `exp` are \
+         limited by RFC7208."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-6"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        report_repeated(record, cx, |m| m.kind().is_unknown_modifier());
+    }
+}
+
+// Report each modifier selected by `select` which repeats an earlier one.
+fn report_repeated<F>(record: &Record<'_>, cx: &mut Context<'_>, select: F)
+where
+    F: Fn(&Mechanism<String>) -> bool,
+{
+    let terms = record.terms();
+    for (idx, m) in terms.iter().enumerate() {
+        if select(m) && terms[..idx].iter().any(|e| same_modifier(e, m)) {
+            let term = m.to_string();
+            let name = term.split('=').next().unwrap_or_default();
+            let message = format!("`{}` repeats the `{}` modifier", term, name);
+            cx.report(idx, message);
+        }
+    }
+}
+
+// Unknown modifiers are the same if their names are equal without regard to case.
+fn same_modifier(a: &Mechanism<String>, b: &Mechanism<String>) -> bool {
+    let name = |m: &Mechanism<String>| {
+        m.rr_data()
+            .as_deref()
+            .and_then(|s| s.split('=').next())
+            .map(str::to_ascii_lowercase)
+    };
+    a.kind() == b.kind() && (!a.kind().is_unknown_modifier() || name(a) == name(b))
+}
+
+/// `whitespace`: Terms are expected to be separated by a single space. Only a parsed record
+/// can contain white space.
+///
+/// [RFC7208 Section 12](https://datatracker.ietf.org/doc/html/rfc7208#section-12)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Whitespace;
+
+impl Rule for Whitespace {
+    fn id(&self) -> &'static str {
+        "whitespace"
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn description(&self) -> &'static str {
+        "Terms are expected to be separated by a single space."
+    }
+    fn reference(&self) -> &'static str {
+        "https://datatracker.ietf.org/doc/html/rfc7208#section-12"
+    }
+    fn check(&self, record: &Record<'_>, cx: &mut Context<'_>) {
+        if record.source().is_some_and(spf_check_whitespace) {
+            cx.report_record("terms are separated by more than a single space");
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::core::cidr::Cidr;
+use crate::mechanism::Mechanism;
+use crate::prelude::*;
+use crate::spf::lint::Record;
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Why a term can never influence the result of a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Shadow {
+    /// The mechanism follows `all` and is never evaluated.
+    /// [RFC7208 Section 5.1](https://datatracker.ietf.org/doc/html/rfc7208#section-5.1)
+    AfterAll,
+    /// The `redirect` is ignored because the record contains `all`.
+    /// [RFC7208 Section 6.1](https://datatracker.ietf.org/doc/html/rfc7208#section-6.1)
+    RedirectWithAll,
+    /// Every address of the `ip4` or `ip6` network is matched by an earlier network.
+    CoveredNetwork,
+    /// An earlier mechanism is the same. It is evaluated first and gives the same match.
+    Duplicate,
+}
+
+impl Display for Shadow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Shadow::AfterAll => write!(f, "follows"),
+            Shadow::RedirectWithAll => write!(f, "is ignored because of"),
+            Shadow::CoveredNetwork => write!(f, "is covered by"),
+            Shadow::Duplicate => write!(f, "duplicates"),
+        }
+    }
+}
+
+/// A term which can never influence the result, and the earlier term responsible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Shadowed {
+    index: usize,
+    by: usize,
+    reason: Shadow,
+}
+
+impl Shadowed {
+    /// The index of the term which has no effect.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// The index of the term which shadows it. For [`Shadow::RedirectWithAll`] this is the
+    /// `all`, which may come after the `redirect`.
+    pub fn shadowed_by(&self) -> usize {
+        self.by
+    }
+    /// Why the term has no effect.
+    pub fn reason(&self) -> Shadow {
+        self.reason
+    }
+}
+
+/// Find every term of `record` which can never influence its result.
+///
+/// Each term is reported once, in record order. A term which follows `all` is only reported
+/// as [`Shadow::AfterAll`].
+///
+/// # Example:
+/// ```
+/// use decon_spf::lint::{shadowed_terms, Record, Shadow};
+/// use decon_spf::Spf;
+/// let spf: Spf<String> =
+///     "v=spf1 ip4:10.0.0.0/16 include:a.com -ip4:10.0.1.0/24 include:A.com ~all mx"
+///         .parse()
+///         .unwrap();
+/// let shadowed = shadowed_terms(&Record::from(&spf));
+/// assert_eq!(shadowed.len(), 3);
+/// assert_eq!(shadowed[0].index(), 2);
+/// assert_eq!(shadowed[0].shadowed_by(), 0);
+/// assert_eq!(shadowed[0].reason(), Shadow::CoveredNetwork);
+/// assert_eq!(shadowed[1].reason(), Shadow::Duplicate);
+/// assert_eq!(shadowed[2].reason(), Shadow::AfterAll);
+/// ```
+pub fn shadowed_terms(record: &Record<'_>) -> Vec<Shadowed> {
+    let terms = record.terms();
+    let all = terms.iter().position(|m| m.kind().is_all());
+    let mut shadowed = Vec::new();
+    for (idx, m) in terms.iter().enumerate() {
+        let found = match all {
+            Some(all) if m.kind().is_redirect() => Some((all, Shadow::RedirectWithAll)),
+            Some(all) if idx > all && !m.kind().is_modifier() => Some((all, Shadow::AfterAll)),
+            _ => earlier(&terms[..idx], m),
+        };
+        if let Some((by, reason)) = found {
+            shadowed.push(Shadowed {
+                index: idx,
+                by,
+                reason,
+            });
+        }
+    }
+    shadowed
+}
+
+// The earlier term which gives the same or a wider match than `m`.
+fn earlier(before: &[Mechanism<String>], m: &Mechanism<String>) -> Option<(usize, Shadow)> {
+    if m.kind().is_modifier() || m.kind().is_all() {
+        return None;
+    }
+    if let Some(cidr) = Cidr::from_mechanism(m) {
+        return before
+            .iter()
+            .position(|e| Cidr::from_mechanism(e).is_some_and(|e| e.contains(&cidr)))
+            .map(|by| (by, Shadow::CoveredNetwork));
+    }
+    // The qualifier is ignored as only the first of the two can match.
+    before
+        .iter()
+        .position(|e| e.is_same_term(m))
+        .map(|by| (by, Shadow::Duplicate))
+}
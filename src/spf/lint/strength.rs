@@ -0,0 +1,304 @@
+use crate::core::cidr::Cidr;
+use crate::core::DNS_LOOKUP_LIMIT;
+use crate::mechanism::Qualifier;
+use crate::prelude::*;
+use crate::spf::lint::rules::{MIN_IP4_PREFIX, MIN_IP6_PREFIX};
+use crate::spf::lint::Record;
+use crate::spf::validate::lookup_count;
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// The points deducted for each weakness. A score starts at 100.
+const SOFTFAIL_ALL: u8 = 20;
+const NEUTRAL_ALL: u8 = 45;
+const REDIRECT: u8 = 5;
+const BROAD_NETWORK: u8 = 20;
+const INCLUDE: u8 = 3;
+const MAX_INCLUDES: u8 = 15;
+const PTR: u8 = 10;
+const LOOKUPS_NEAR_LIMIT: u8 = 5;
+const LOOKUPS_AT_LIMIT: u8 = 10;
+const LOOKUPS_OVER_LIMIT: u8 = 30;
+
+/// The grade of a [`Strength`]. Ordered from [`Open`](Grade::Open) to
+/// [`Strict`](Grade::Strict).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Grade {
+    /// Any host is authorized, or so little is asserted that the record offers no protection.
+    /// A score below 25.
+    Open,
+    /// Hosts which are not listed are not failed. A score from 25 to 59.
+    Weak,
+    /// Hosts which are not listed are soft failed, or the record relies heavily on other
+    /// domains. A score from 60 to 84.
+    Moderate,
+    /// Hosts which are not listed are failed. A score of 85 or more.
+    Strict,
+}
+
+impl Grade {
+    fn from_score(score: u8) -> Self {
+        match score {
+            0..=24 => Grade::Open,
+            25..=59 => Grade::Weak,
+            60..=84 => Grade::Moderate,
+            _ => Grade::Strict,
+        }
+    }
+    /// Returns the grade as a lowercase string slice.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Grade::Open => "open",
+            Grade::Weak => "weak",
+            Grade::Moderate => "moderate",
+            Grade::Strict => "strict",
+        }
+    }
+    /// Returns `true` if the grade is [`Open`](Grade::Open).
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open)
+    }
+    /// Returns `true` if the grade is [`Weak`](Grade::Weak).
+    pub fn is_weak(&self) -> bool {
+        matches!(self, Self::Weak)
+    }
+    /// Returns `true` if the grade is [`Moderate`](Grade::Moderate).
+    pub fn is_moderate(&self) -> bool {
+        matches!(self, Self::Moderate)
+    }
+    /// Returns `true` if the grade is [`Strict`](Grade::Strict).
+    pub fn is_strict(&self) -> bool {
+        matches!(self, Self::Strict)
+    }
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A weakness which lowers the [`Strength`] of a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Weakness {
+    /// `all` does not fail other hosts. `+all` authorizes every host and makes the record
+    /// [`Open`](Grade::Open).
+    All(Qualifier),
+    /// There is neither `all` nor `redirect`, so other hosts are `neutral`.
+    MissingAll,
+    /// The result for other hosts is given by the record of another domain.
+    Redirect,
+    /// An `ip4` or `ip6` network with a prefix of 0 authorizes every address and makes the
+    /// record [`Open`](Grade::Open).
+    OpenNetwork,
+    /// A network, or the `dual-cidr-length` of `a` or `mx`, is shorter than /16 for ip4 or
+    /// /32 for ip6.
+    BroadNetwork,
+    /// The given number of `include`s authorize hosts chosen by other domains.
+    Includes(usize),
+    /// `ptr` is slow and unreliable.
+    Ptr,
+    /// The given number of DNS lookups is close to, or exceeds, the limit of 10.
+    Lookups(usize),
+}
+
+impl Display for Weakness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Weakness::All(Qualifier::Pass) => write!(f, "`+all` authorizes every host"),
+            Weakness::All(q) => write!(f, "`{}all` does not fail other hosts", q.as_char()),
+            Weakness::MissingAll => write!(f, "there is no `all` or `redirect`"),
+            Weakness::Redirect => write!(f, "other hosts are handled by another domain"),
+            Weakness::OpenNetwork => write!(f, "a network authorizes every address"),
+            Weakness::BroadNetwork => write!(f, "a network authorizes a large number of hosts"),
+            Weakness::Includes(1) => write!(f, "an `include` relies on another domain"),
+            Weakness::Includes(n) => write!(f, "{} `include`s rely on other domains", n),
+            Weakness::Ptr => write!(f, "`ptr` is used"),
+            Weakness::Lookups(n) if *n > DNS_LOOKUP_LIMIT => {
+                write!(
+                    f,
+                    "{} DNS lookups exceed the limit of {}",
+                    n, DNS_LOOKUP_LIMIT
+                )
+            }
+            Weakness::Lookups(n) => write!(
+                f,
+                "{} DNS lookups leave {} before the limit",
+                n,
+                DNS_LOOKUP_LIMIT - n
+            ),
+        }
+    }
+}
+
+/// A [`Weakness`], the term it was found at and the points it deducted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Deduction {
+    weakness: Weakness,
+    index: Option<usize>,
+    points: u8,
+}
+
+impl Deduction {
+    /// The weakness found.
+    pub fn weakness(&self) -> Weakness {
+        self.weakness
+    }
+    /// The index of the term. `None` if the weakness is of the record as a whole.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+    /// The points deducted from the score.
+    pub fn points(&self) -> u8 {
+        self.points
+    }
+}
+
+/// How well a record protects a domain. See [`strength()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Strength {
+    score: u8,
+    grade: Grade,
+    deductions: Vec<Deduction>,
+}
+
+impl Strength {
+    /// The score, from 0 to 100.
+    pub fn score(&self) -> u8 {
+        self.score
+    }
+    /// The grade given by the score.
+    pub fn grade(&self) -> Grade {
+        self.grade
+    }
+    /// The weaknesses found, in record order. Weaknesses of the record as a whole are last.
+    pub fn deductions(&self) -> &[Deduction] {
+        &self.deductions
+    }
+}
+
+impl Display for Strength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} ({})", self.grade, self.score)
+    }
+}
+
+/// Assess the strength of `record`.
+///
+/// A score starts at 100 and points are deducted for each [`Weakness`]. `+all` or a network
+/// which authorizes every address gives a score of 0. Only the terms up to the first `all` are
+/// considered, as no other terms are evaluated.
+///
+/// | Weakness | Points |
+/// |----------|--------|
+/// | `~all` | 20 |
+/// | `?all`, or no `all` or `redirect` | 45 |
+/// | `redirect` | 5 |
+/// | Each broad network | 20 |
+/// | Each `include` | 3, up to 15 |
+/// | `ptr` | 10 |
+/// | 7 or 8 DNS lookups | 5 |
+/// | 9 or 10 DNS lookups | 10 |
+/// | More than 10 DNS lookups | 30 |
+///
+/// # Example:
+/// ```
+/// use decon_spf::lint::{strength, Grade, Record, Weakness};
+/// use decon_spf::Spf;
+/// let spf: Spf<String> = "v=spf1 mx include:_spf.example.net ~all".parse().unwrap();
+/// let strength = strength(&Record::from(&spf));
+/// assert_eq!(strength.score(), 77);
+/// assert_eq!(strength.grade(), Grade::Moderate);
+/// assert_eq!(strength.to_string(), "moderate (77)");
+/// let weaknesses: Vec<_> = strength.deductions().iter().map(|d| d.weakness()).collect();
+/// assert_eq!(weaknesses, [Weakness::All(decon_spf::mechanism::Qualifier::SoftFail), Weakness::Includes(1)]);
+/// ```
+pub fn strength(record: &Record<'_>) -> Strength {
+    let all_terms = record.terms();
+    let all = all_terms.iter().position(|m| m.kind().is_all());
+    let terms = match all {
+        Some(idx) => &all_terms[..=idx],
+        None => all_terms,
+    };
+    let mut deductions = Vec::new();
+    let mut deduct = |weakness, index, points| {
+        deductions.push(Deduction {
+            weakness,
+            index,
+            points,
+        })
+    };
+    let mut includes = 0;
+    for (idx, m) in terms.iter().enumerate() {
+        let kind = m.kind();
+        if kind.is_all() {
+            match m.qualifier() {
+                Qualifier::Pass => deduct(Weakness::All(Qualifier::Pass), Some(idx), 100),
+                Qualifier::Neutral => {
+                    deduct(Weakness::All(Qualifier::Neutral), Some(idx), NEUTRAL_ALL)
+                }
+                Qualifier::SoftFail => {
+                    deduct(Weakness::All(Qualifier::SoftFail), Some(idx), SOFTFAIL_ALL)
+                }
+                Qualifier::Fail => {}
+            }
+        } else if kind.is_redirect() && all.is_none() {
+            deduct(Weakness::Redirect, Some(idx), REDIRECT);
+        } else if kind.is_include() {
+            includes += 1;
+        } else if kind.is_ptr() {
+            deduct(Weakness::Ptr, Some(idx), PTR);
+        }
+        if !m.qualifier().is_pass() {
+            continue;
+        }
+        if let Some(cidr) = Cidr::from_mechanism(m) {
+            let min = match cidr.is_ipv4() {
+                true => MIN_IP4_PREFIX,
+                false => MIN_IP6_PREFIX,
+            };
+            if cidr.prefix() == 0 {
+                deduct(Weakness::OpenNetwork, Some(idx), 100);
+            } else if cidr.prefix() < min {
+                deduct(Weakness::BroadNetwork, Some(idx), BROAD_NETWORK);
+            }
+        } else if let Some(cidr) = m.cidr() {
+            let broad = cidr.ip4().is_some_and(|len| len < MIN_IP4_PREFIX)
+                || cidr.ip6().is_some_and(|len| len < MIN_IP6_PREFIX);
+            if broad {
+                deduct(Weakness::BroadNetwork, Some(idx), BROAD_NETWORK);
+            }
+        }
+    }
+    if all.is_none() && !terms.iter().any(|m| m.kind().is_redirect()) {
+        deduct(Weakness::MissingAll, None, NEUTRAL_ALL);
+    }
+    if includes > 0 {
+        let points = (includes * INCLUDE as usize).min(MAX_INCLUDES as usize) as u8;
+        deduct(Weakness::Includes(includes), None, points);
+    }
+    let lookups = lookup_count(all_terms);
+    let points = match lookups {
+        n if n > DNS_LOOKUP_LIMIT => LOOKUPS_OVER_LIMIT,
+        n if n + 2 > DNS_LOOKUP_LIMIT => LOOKUPS_AT_LIMIT,
+        n if n + 4 > DNS_LOOKUP_LIMIT => LOOKUPS_NEAR_LIMIT,
+        _ => 0,
+    };
+    if points > 0 {
+        deduct(Weakness::Lookups(lookups), None, points);
+    }
+    let score = deductions
+        .iter()
+        .fold(100u8, |score, d| score.saturating_sub(d.points));
+    Strength {
+        score,
+        grade: Grade::from_score(score),
+        deductions,
+    }
+}
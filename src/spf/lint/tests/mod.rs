@@ -0,0 +1,594 @@
+use crate::lint::{Level, LintConfig, Linter, Rule, Severity};
+use crate::Spf;
+
+fn rules(s: &str) -> Vec<&'static str> {
+    let spf: Spf<String> = s.parse().unwrap();
+    Linter::new().check(&spf).iter().map(|f| f.rule()).collect()
+}
+
+mod builtin {
+    use super::*;
+
+    #[test]
+    fn clean() {
+        assert!(rules("v=spf1 a mx include:_spf.example.com -all").is_empty());
+    }
+    #[test]
+    #[cfg(feature = "ptr")]
+    fn deprecated_ptr() {
+        assert_eq!(
+            rules("v=spf1 ptr ptr:example.com -all"),
+            ["deprecated-ptr"; 2]
+        );
+    }
+    #[test]
+    #[cfg(not(feature = "ptr"))]
+    fn deprecated_ptr_disabled() {
+        assert!(rules("v=spf1 ptr -all").is_empty());
+    }
+    #[test]
+    fn redirect_with_all() {
+        assert_eq!(
+            rules("v=spf1 -all redirect=_spf.example.com"),
+            ["redirect-with-all"]
+        );
+    }
+    #[test]
+    fn modifier_not_last() {
+        let spf: Spf<String> = "v=spf1 redirect=_spf.example.com a".parse().unwrap();
+        let findings = Linter::new().check(&spf);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule(), "modifier-not-last");
+        assert_eq!(findings[0].severity(), Severity::Warning);
+        assert_eq!(findings[0].index(), Some(0));
+        assert!(rules("v=spf1 a redirect=_spf.example.com").is_empty());
+        // Other modifiers may follow it.
+        assert!(rules("v=spf1 a redirect=_spf.example.com exp=explain.example.com").is_empty());
+    }
+    #[test]
+    fn exp_and_unknown_modifier_not_last() {
+        let spf: Spf<String> = "v=spf1 exp=x.example.com a moo=cow -all".parse().unwrap();
+        let findings = Linter::new().check(&spf);
+        let found: Vec<_> = findings.iter().map(|f| (f.rule(), f.index())).collect();
+        assert_eq!(
+            found,
+            [
+                ("modifier-not-last", Some(0)),
+                ("modifier-not-last", Some(2))
+            ]
+        );
+        assert_eq!(
+            findings[0].message(),
+            "`exp=x.example.com` is followed by a mechanism"
+        );
+        assert!(rules("v=spf1 a -all exp=x.example.com moo=cow").is_empty());
+    }
+    #[test]
+    fn repeated_modifiers() {
+        assert_eq!(
+            rules("v=spf1 a -all moo=cow exp=a.example.com MOO=calf exp=b.example.com"),
+            ["repeated-modifier", "repeated-unknown-modifier"]
+        );
+    }
+    #[test]
+    fn whitespace() {
+        assert_eq!(rules("v=spf1 a  mx -all"), ["whitespace"]);
+    }
+    #[test]
+    fn same_as_validation() {
+        let spf: Spf<String> =
+            "v=spf1 a redirect=a.example.com mx exp=a.example.com exp=b.example.com"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            Linter::new().check(&spf),
+            spf.validation_report().findings()
+        );
+    }
+    #[test]
+    fn lookup_limit() {
+        let ten = "v=spf1 a mx include:a.com include:b.com include:c.com include:d.com \
+                   exists:e.com a:f.com mx:g.com redirect=h.com";
+        assert!(rules(ten).is_empty());
+        let spf: Spf<String> = format!("{} a:i.com -all", ten.replace(" redirect=h.com", ""))
+            .parse()
+            .unwrap();
+        assert!(Linter::new().check(&spf).is_empty());
+        let spf: Spf<String> = format!("{} ptr:i.com", ten).parse().unwrap();
+        let findings = Linter::new().check(&spf);
+        let finding = findings
+            .iter()
+            .find(|f| f.rule() == "lookup-limit")
+            .unwrap();
+        assert_eq!(finding.index(), None);
+        assert!(finding.severity().is_error());
+        assert_eq!(
+            finding.message(),
+            "11 DNS lookups are required, the limit is 10"
+        );
+    }
+    #[test]
+    fn unique_ids() {
+        let linter = Linter::new();
+        let ids: Vec<_> = linter.rules().map(|r| r.id()).collect();
+        for (i, id) in ids.iter().enumerate() {
+            assert!(!ids[..i].contains(id), "{}", id);
+            assert!(linter.rule(id).unwrap().reference().starts_with("https://"));
+        }
+    }
+}
+
+mod config {
+    use super::*;
+    use crate::lint::rules::DeprecatedPtr;
+
+    const RECORD: &str = "v=spf1 ptr -all redirect=_spf.example.com";
+
+    #[test]
+    fn allow() {
+        let mut linter = Linter::new();
+        linter.config_mut().allow("redirect-with-all");
+        let spf: Spf<String> = RECORD.parse().unwrap();
+        assert!(linter
+            .check(&spf)
+            .iter()
+            .all(|f| f.rule() != "redirect-with-all"));
+    }
+    #[test]
+    fn set_severity() {
+        let mut config = LintConfig::new();
+        config.set_severity("redirect-with-all", Severity::Error);
+        let spf: Spf<String> = RECORD.parse().unwrap();
+        let findings = Linter::with_config(config).check(&spf);
+        let finding = findings
+            .iter()
+            .find(|f| f.rule() == "redirect-with-all")
+            .unwrap();
+        assert_eq!(finding.severity(), Severity::Error);
+        assert_eq!(
+            finding.to_string(),
+            "error[redirect-with-all]: `redirect` is ignored because `all` is present"
+        );
+    }
+    #[test]
+    fn enable_and_reset() {
+        let mut config = LintConfig::new();
+        config.allow("deprecated-ptr");
+        assert_eq!(config.severity_of(&DeprecatedPtr), None);
+        config.enable("deprecated-ptr");
+        assert_eq!(config.level("deprecated-ptr"), Some(Level::Enabled));
+        assert_eq!(config.severity_of(&DeprecatedPtr), Some(Severity::Warning));
+        config.reset("deprecated-ptr");
+        assert_eq!(config.level("deprecated-ptr"), None);
+        assert_eq!(
+            config.severity_of(&DeprecatedPtr).is_some(),
+            DeprecatedPtr.enabled_by_default()
+        );
+    }
+    #[test]
+    fn unknown_id_is_ignored() {
+        let mut config = LintConfig::new();
+        config.allow("no-such-rule");
+        let spf: Spf<String> = RECORD.parse().unwrap();
+        assert_eq!(
+            Linter::with_config(config).check(&spf),
+            Linter::new().check(&spf)
+        );
+    }
+    #[test]
+    fn empty() {
+        let spf: Spf<String> = RECORD.parse().unwrap();
+        assert!(Linter::empty().check(&spf).is_empty());
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut config = LintConfig::new();
+        config
+            .allow("deprecated-ptr")
+            .set_severity("lookup-limit", Severity::Warning);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            "{\"rules\":{\"deprecated-ptr\":\"Allow\",\"lookup-limit\":\"Warning\"}}"
+        );
+        assert_eq!(serde_json::from_str::<LintConfig>(&json).unwrap(), config);
+    }
+}
+
+mod policy {
+    use super::*;
+
+    #[test]
+    fn pass_all() {
+        assert_eq!(rules("v=spf1 a +all"), ["pass-all"]);
+        assert_eq!(rules("v=spf1 a all"), ["pass-all"]);
+        assert!(rules("v=spf1 a ~all").is_empty());
+    }
+    #[test]
+    fn neutral_all() {
+        assert_eq!(rules("v=spf1 a ?all"), ["neutral-all"]);
+    }
+    #[test]
+    fn missing_all() {
+        let spf: Spf<String> = "v=spf1 a mx".parse().unwrap();
+        let findings = Linter::new().check(&spf);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule(), "missing-all");
+        assert_eq!(findings[0].index(), None);
+        assert!(rules("v=spf1 a redirect=_spf.example.com").is_empty());
+    }
+    #[test]
+    fn open_network() {
+        assert_eq!(rules("v=spf1 ip4:0.0.0.0/0 -all"), ["open-network"]);
+        assert_eq!(rules("v=spf1 ip6:::/0 -all"), ["open-network"]);
+        // Failing every address is not a weakness.
+        assert_eq!(rules("v=spf1 a -ip4:0.0.0.0/0"), ["missing-all"]);
+    }
+    #[test]
+    fn broad_network() {
+        assert_eq!(rules("v=spf1 ip4:8.0.0.0/8 -all"), ["broad-network"]);
+        assert_eq!(rules("v=spf1 ip6:2c0f::/16 -all"), ["broad-network"]);
+        assert!(rules("v=spf1 ip4:8.8.0.0/16 ip6:2c0f:f000::/32 -all").is_empty());
+    }
+    #[test]
+    fn private_network() {
+        let spf: Spf<String> = "v=spf1 ip4:8.8.8.8 ip4:10.1.0.0/16 ip6:fd00::1 -all"
+            .parse()
+            .unwrap();
+        let findings = Linter::new().check(&spf);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].index(), Some(1));
+        assert_eq!(
+            findings[0].message(),
+            "`ip4:10.1.0.0/16` is within the private-use network 10.0.0.0/8"
+        );
+        assert_eq!(findings[1].index(), Some(2));
+        assert_eq!(rules("v=spf1 ip4:127.0.0.1 -all"), ["private-network"]);
+        assert_eq!(rules("v=spf1 ip6:::1 -all"), ["private-network"]);
+        assert!(rules("v=spf1 ip4:172.32.0.1 -all").is_empty());
+    }
+    #[test]
+    fn broad_dual_cidr() {
+        let spf: Spf<String> = "v=spf1 a/8 mx:example.com//16 a/24//64 -all"
+            .parse()
+            .unwrap();
+        let findings = Linter::new().check(&spf);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[0].message(),
+            "`a/8` authorizes an ip4 /8 around each address"
+        );
+        assert_eq!(findings[1].index(), Some(1));
+    }
+}
+
+mod shadow {
+    use super::*;
+    use crate::lint::Shadow;
+
+    fn shadowed(s: &str) -> Vec<(usize, usize, Shadow)> {
+        let spf: Spf<String> = s.parse().unwrap();
+        spf.shadowed_terms()
+            .iter()
+            .map(|s| (s.index(), s.shadowed_by(), s.reason()))
+            .collect()
+    }
+
+    #[test]
+    fn after_all() {
+        assert_eq!(
+            shadowed("v=spf1 a -all mx ip4:192.0.2.1 exp=explain.example.com"),
+            [(2, 1, Shadow::AfterAll), (3, 1, Shadow::AfterAll)]
+        );
+        assert_eq!(
+            shadowed("v=spf1 redirect=_spf.example.com -all"),
+            [(0, 1, Shadow::RedirectWithAll)]
+        );
+    }
+    #[test]
+    fn covered_network() {
+        assert_eq!(
+            shadowed(
+                "v=spf1 ip4:192.0.2.0/23 ip4:192.0.3.7 ip6:2001:db8::/32 ip6:2001:db8:1::/48 -all"
+            ),
+            [
+                (1, 0, Shadow::CoveredNetwork),
+                (3, 2, Shadow::CoveredNetwork)
+            ]
+        );
+        // A wider network which follows a narrower one is still needed.
+        assert!(shadowed("v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.0/23 -all").is_empty());
+    }
+    #[test]
+    fn duplicate() {
+        assert_eq!(
+            shadowed("v=spf1 include:Example.com a mx ~include:example.com ~mx -all"),
+            [(3, 0, Shadow::Duplicate), (4, 2, Shadow::Duplicate)]
+        );
+        // A different cidr length or macro letter case is not the same mechanism.
+        assert!(shadowed("v=spf1 a a/24 exists:%{d}.a.com exists:%{D}.a.com -all").is_empty());
+    }
+    #[test]
+    fn unreachable_term() {
+        let spf: Spf<String> = "v=spf1 include:a.com ~all include:a.com redirect=b.com"
+            .parse()
+            .unwrap();
+        let findings: Vec<_> = Linter::new()
+            .check(&spf)
+            .into_iter()
+            .filter(|f| f.rule() == "unreachable-term")
+            .collect();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].index(), Some(2));
+        assert_eq!(
+            findings[0].message(),
+            "`include:a.com` follows `~all` at index 1"
+        );
+        assert_eq!(
+            rules("v=spf1 ip4:64.18.0.0/24 ip4:64.18.0.1 -all"),
+            ["unreachable-term"]
+        );
+    }
+}
+
+mod networks {
+    use super::*;
+    use crate::lint::Relation;
+
+    fn overlaps(s: &str) -> Vec<(usize, usize, Relation, Option<String>)> {
+        let spf: Spf<String> = s.parse().unwrap();
+        spf.overlapping_networks()
+            .iter()
+            .map(|o| {
+                (
+                    o.other(),
+                    o.index(),
+                    o.relation(),
+                    o.combined().map(String::from),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_networks() {
+        assert_eq!(
+            overlaps("v=spf1 ip4:10.0.0.0/24 ip4:10.0.1.0/24 ip4:10.0.2.0/24 -all"),
+            [
+                (0, 1, Relation::Adjacent, Some("10.0.0.0/23".to_string())),
+                (1, 2, Relation::Adjacent, None),
+            ]
+        );
+        assert_eq!(
+            overlaps("v=spf1 ip6:2001:db8::/32 ip6:2001:db8:1::/48 -all"),
+            [(0, 1, Relation::Overlaps, Some("2001:db8::/32".to_string()))]
+        );
+        // Qualifiers and families are kept apart, and terms after `all` are ignored.
+        assert!(overlaps(
+            "v=spf1 ip4:10.0.0.0/24 -ip4:10.0.1.0/24 ip6:::ffff:a00:100/120 -all ip4:10.0.0.0/8"
+        )
+        .is_empty());
+    }
+    #[test]
+    fn mergeable_networks() {
+        let spf: Spf<String> = "v=spf1 ip4:64.18.0.0/24 ip4:64.18.0.0/23 ip4:64.18.2.0/23 -all"
+            .parse()
+            .unwrap();
+        let findings = Linter::new().check(&spf);
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|f| f.rule() == "mergeable-networks"));
+        assert_eq!(findings[0].severity(), Severity::Info);
+        assert_eq!(findings[0].index(), Some(1));
+        assert_eq!(
+            findings[0].message(),
+            "`ip4:64.18.0.0/23` overlaps `ip4:64.18.0.0/24` at index 0, they can be combined \
+             as `ip4:64.18.0.0/23`"
+        );
+        assert_eq!(
+            findings[1].message(),
+            "`ip4:64.18.2.0/23` is adjacent to `ip4:64.18.0.0/23` at index 1, they can be \
+             combined as `ip4:64.18.0.0/22`"
+        );
+        // A covered network is reported as unreachable.
+        assert_eq!(
+            rules("v=spf1 ip4:64.18.0.0/23 ip4:64.18.1.0/24 -all"),
+            ["unreachable-term"]
+        );
+    }
+}
+
+mod address {
+    use super::*;
+
+    fn messages(s: &str) -> Vec<String> {
+        let spf: Spf<String> = s.parse().unwrap();
+        Linter::new()
+            .check(&spf)
+            .iter()
+            .map(|f| f.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn host_bits_set() {
+        assert_eq!(
+            messages("v=spf1 ~ip4:64.18.0.55/24 ip6:2c0f:f000::1/32 -all"),
+            [
+                "`~ip4:64.18.0.55/24` has host bits set, the network is `~ip4:64.18.0.0/24`",
+                "`ip6:2c0f:f000::1/32` has host bits set, the network is `ip6:2c0f:f000::/32`",
+            ]
+        );
+        assert!(rules("v=spf1 ip4:64.18.0.55 ip4:64.19.0.0/24 -all").is_empty());
+    }
+    #[test]
+    fn mapped_ip6() {
+        assert_eq!(
+            messages("v=spf1 ip6:::ffff:64.18.0.1 ip6:::ffff:64.19.0.0/120 -all"),
+            [
+                "`ip6:::ffff:64.18.0.1` is an IPv4-mapped address, use `ip4:64.18.0.1`",
+                "`ip6:::ffff:64.19.0.0/120` is an IPv4-mapped address, use `ip4:64.19.0.0/24`",
+            ]
+        );
+        assert_eq!(
+            messages("v=spf1 ip6:::64.18.0.1 -all"),
+            ["`ip6:::4012:1` is an IPv4-compatible address, use `ip4:64.18.0.1`"]
+        );
+        // The loopback address is reported as a private network.
+        assert_eq!(rules("v=spf1 ip6:::1 -all"), ["private-network"]);
+        assert!(rules("v=spf1 ip6:2c0f:f000::ffff:4012:1 -all").is_empty());
+    }
+    #[test]
+    fn documentation_network() {
+        for network in [
+            "ip4:192.0.2.1",
+            "ip4:198.51.100.0/24",
+            "ip4:203.0.113.128/25",
+            "ip6:2001:db8::/48",
+            "ip6:3fff::1",
+        ] {
+            assert_eq!(
+                rules(&format!("v=spf1 {} -all", network)),
+                ["documentation-network"],
+                "{}",
+                network
+            );
+        }
+        assert_eq!(
+            messages("v=spf1 ip4:192.0.2.0/24 -all"),
+            ["`ip4:192.0.2.0/24` is within the documentation network 192.0.2.0/24"]
+        );
+        assert!(rules("v=spf1 ip4:192.0.3.0/24 ip6:2001:db9::/32 -all").is_empty());
+    }
+}
+
+mod strength {
+    use super::*;
+    use crate::lint::{Grade, Weakness};
+    use crate::mechanism::Qualifier;
+
+    fn assess(s: &str) -> (u8, Grade, Vec<(Weakness, Option<usize>)>) {
+        let spf: Spf<String> = s.parse().unwrap();
+        let strength = spf.strength();
+        let weaknesses = strength
+            .deductions()
+            .iter()
+            .map(|d| (d.weakness(), d.index()))
+            .collect();
+        (strength.score(), strength.grade(), weaknesses)
+    }
+
+    #[test]
+    fn all_qualifier() {
+        assert_eq!(assess("v=spf1 mx -all"), (100, Grade::Strict, vec![]));
+        assert_eq!(
+            assess("v=spf1 mx ~all"),
+            (
+                80,
+                Grade::Moderate,
+                vec![(Weakness::All(Qualifier::SoftFail), Some(1))]
+            )
+        );
+        assert_eq!(
+            assess("v=spf1 mx ?all"),
+            (
+                55,
+                Grade::Weak,
+                vec![(Weakness::All(Qualifier::Neutral), Some(1))]
+            )
+        );
+        assert_eq!(
+            assess("v=spf1 mx +all"),
+            (
+                0,
+                Grade::Open,
+                vec![(Weakness::All(Qualifier::Pass), Some(1))]
+            )
+        );
+        assert_eq!(
+            assess("v=spf1 mx"),
+            (55, Grade::Weak, vec![(Weakness::MissingAll, None)])
+        );
+    }
+    #[test]
+    fn redirect() {
+        assert_eq!(
+            assess("v=spf1 mx redirect=_spf.example.com.com"),
+            (95, Grade::Strict, vec![(Weakness::Redirect, Some(1))])
+        );
+        // The redirect is ignored.
+        assert_eq!(assess("v=spf1 redirect=_spf.example.com.com -all").2, []);
+    }
+    #[test]
+    fn networks() {
+        assert_eq!(
+            assess("v=spf1 ip4:64.0.0.0/8 -ip4:32.0.0.0/8 a/8 ip6:2001::/16 -all"),
+            (
+                40,
+                Grade::Weak,
+                vec![
+                    (Weakness::BroadNetwork, Some(0)),
+                    (Weakness::BroadNetwork, Some(2)),
+                    (Weakness::BroadNetwork, Some(3)),
+                ]
+            )
+        );
+        assert_eq!(
+            assess("v=spf1 ip6:::/0 -all"),
+            (0, Grade::Open, vec![(Weakness::OpenNetwork, Some(0))])
+        );
+        // Terms following `all` are never evaluated.
+        assert_eq!(assess("v=spf1 -all ip4:0.0.0.0/0").0, 100);
+    }
+    #[test]
+    fn includes() {
+        assert_eq!(
+            assess("v=spf1 include:a.example.com include:b.example.com -all"),
+            (94, Grade::Strict, vec![(Weakness::Includes(2), None)])
+        );
+        let (score, _, weaknesses) = assess(
+            "v=spf1 include:a.example.com include:b.example.com include:c.example.com include:d.example.com \
+             include:e.example.com include:f.example.com -all",
+        );
+        // The deduction for includes is capped.
+        assert_eq!(score, 85);
+        assert_eq!(weaknesses, [(Weakness::Includes(6), None)]);
+    }
+    #[test]
+    fn lookups() {
+        let (score, _, weaknesses) = assess("v=spf1 a mx a:a.example.com mx:b.example.com a:c.example.com mx:d.example.com a:e.example.com -all");
+        assert_eq!(score, 95);
+        assert_eq!(weaknesses, [(Weakness::Lookups(7), None)]);
+        let (score, grade, weaknesses) = assess(
+            "v=spf1 a mx a:a.example.com mx:b.example.com a:c.example.com mx:d.example.com a:e.example.com \
+             mx:f.example.com a:g.example.com mx:h.example.com a:i.example.com -all",
+        );
+        assert_eq!((score, grade), (70, Grade::Moderate));
+        assert_eq!(weaknesses, [(Weakness::Lookups(11), None)]);
+        assert_eq!(
+            Weakness::Lookups(11).to_string(),
+            "11 DNS lookups exceed the limit of 10"
+        );
+        assert_eq!(
+            Weakness::Lookups(9).to_string(),
+            "9 DNS lookups leave 1 before the limit"
+        );
+    }
+    #[test]
+    #[cfg(feature = "ptr")]
+    fn ptr() {
+        assert_eq!(
+            assess("v=spf1 ptr -all"),
+            (90, Grade::Strict, vec![(Weakness::Ptr, Some(0))])
+        );
+    }
+    #[test]
+    #[cfg(feature = "builder")]
+    fn built() {
+        use crate::lint::{strength, Record};
+        use crate::spf::builder::{Builder, SpfBuilder};
+        let spf: Spf<String> = "v=spf1 mx ~all".parse().unwrap();
+        let builder = SpfBuilder::<Builder>::from(spf.clone());
+        assert_eq!(strength(&Record::from(&builder)), spf.strength());
+    }
+}
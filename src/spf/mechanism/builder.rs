@@ -40,6 +40,6 @@ impl Mechanism<All> {
 
 impl Display for Mechanism<All> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", format!("{}all", self.qualifier))
+        write!(f, "{}all", self.qualifier)
     }
 }
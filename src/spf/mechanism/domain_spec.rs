@@ -0,0 +1,342 @@
+//! Typed representations of the `domain-spec` and `dual-cidr-length` found in the `rrdata`
+//! of a Mechanism or Modifier.
+//!
+//! A [`Mechanism`](super::Mechanism) stores its `rrdata` as text, which is what is displayed
+//! and serialized. These types are views of it: they are parsed from `rrdata` by
+//! [`domain_spec()`](super::Mechanism::domain_spec) and [`cidr()`](super::Mechanism::cidr)
+//! each time they are called, and written back as text by
+//! [`with_domain_spec()`](super::Mechanism::with_domain_spec) and
+//! [`with_cidr()`](super::Mechanism::with_cidr).
+//!
+//! [RFC7208 Section 7.1](https://datatracker.ietf.org/doc/html/rfc7208#section-7.1)
+use crate::prelude::*;
+use crate::spf::mechanism::MechanismError;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A single part of a [`DomainSpec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Literal text such as `_spf.example.com`
+    Literal(String),
+    /// A macro of the form `%{...}`
+    Macro(MacroExpand),
+    /// One of the escapes `%%`, `%_` or `%-`. The character following the `%` is stored.
+    Escape(char),
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Segment::Literal(s) => write!(f, "{}", s),
+            Segment::Macro(m) => write!(f, "{}", m),
+            Segment::Escape(c) => write!(f, "%{}", c),
+        }
+    }
+}
+
+/// A macro such as `%{ir}` or `%{l1r-}`.
+///
+/// ```text
+/// macro-expand = ( "%{" macro-letter transformers *delimiter "}" )
+/// transformers = *DIGIT [ "r" ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroExpand {
+    letter: char,
+    digits: Option<u32>,
+    reverse: bool,
+    delimiters: String,
+}
+
+impl MacroExpand {
+    pub(crate) fn new(letter: char, digits: Option<u32>, reverse: bool, delimiters: &str) -> Self {
+        Self {
+            letter,
+            digits,
+            reverse,
+            delimiters: delimiters.to_string(),
+        }
+    }
+    /// The macro letter in lowercase. `s`, `l`, `o`, `d`, `i`, `p`, `h`, `c`, `r`, `t`, or `v`
+    pub fn letter(&self) -> char {
+        self.letter.to_ascii_lowercase()
+    }
+    /// Returns `true` if the letter was given in uppercase. The expansion is then URL escaped.
+    pub fn is_url_escaped(&self) -> bool {
+        self.letter.is_ascii_uppercase()
+    }
+    /// The number of right-hand parts to keep, if given.
+    pub fn digits(&self) -> Option<u32> {
+        self.digits
+    }
+    /// Returns `true` if the parts are reversed before use.
+    pub fn is_reversed(&self) -> bool {
+        self.reverse
+    }
+    /// The delimiters used to split the expansion. An empty string means `.`
+    pub fn delimiters(&self) -> &str {
+        &self.delimiters
+    }
+}
+
+impl Display for MacroExpand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "%{{{}", self.letter)?;
+        if let Some(digits) = self.digits {
+            write!(f, "{}", digits)?;
+        }
+        if self.reverse {
+            write!(f, "r")?;
+        }
+        write!(f, "{}}}", self.delimiters)
+    }
+}
+
+/// A parsed `domain-spec`.
+///
+/// ```text
+/// domain-spec = macro-string domain-end
+/// domain-end  = ( "." toplabel [ "." ] ) / macro-expand
+/// ```
+///
+/// # Example:
+/// ```
+/// use decon_spf::mechanism::{DomainSpec, Segment};
+/// let spec: DomainSpec = "_spf.example.com".parse().unwrap();
+/// assert!(!spec.has_macros());
+/// assert_eq!(spec.labels().unwrap(), ["_spf", "example", "com"]);
+///
+/// let spec: DomainSpec = "%{ir}.%{v}._spf.%{d2}".parse().unwrap();
+/// assert!(spec.has_macros());
+/// assert_eq!(spec.segments().len(), 5);
+/// assert!(matches!(&spec.segments()[1], Segment::Literal(s) if s == "."));
+/// assert_eq!(spec.labels(), None);
+/// assert_eq!(spec.to_string(), "%{ir}.%{v}._spf.%{d2}");
+///
+/// assert!("example".parse::<DomainSpec>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainSpec {
+    segments: Vec<Segment>,
+}
+
+impl DomainSpec {
+    pub(crate) fn from_segments(segments: Vec<Segment>) -> Self {
+        Self { segments }
+    }
+    /// The literal, macro and escape segments which make up the `domain-spec`.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+    /// Returns `true` if any part of the `domain-spec` must be expanded at evaluation time.
+    pub fn has_macros(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|s| !matches!(s, Segment::Literal(_)))
+    }
+    /// The domain name when there are no macros.
+    pub fn as_literal(&self) -> Option<&str> {
+        match self.segments.as_slice() {
+            [Segment::Literal(s)] => Some(s),
+            _ => None,
+        }
+    }
+    /// The labels of the domain name when there are no macros. A trailing `.` is ignored.
+    pub fn labels(&self) -> Option<Vec<&str>> {
+        let literal = self.as_literal()?;
+        Some(
+            literal
+                .strip_suffix('.')
+                .unwrap_or(literal)
+                .split('.')
+                .collect(),
+        )
+    }
+}
+
+impl Display for DomainSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for segment in &self.segments {
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DomainSpec {
+    type Err = MechanismError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::core::parser::parse_domain_spec(s)
+            .ok_or_else(|| MechanismError::InvalidMechanismFormat(s.to_string()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for DomainSpec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DomainSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The optional prefix lengths given to `a` and `mx`.
+///
+/// ```text
+/// dual-cidr-length = [ ip4-cidr-length ] [ "/" ip6-cidr-length ]
+/// ```
+///
+/// # Example:
+/// ```
+/// use decon_spf::mechanism::DualCidr;
+/// let cidr: DualCidr = "/24//64".parse().unwrap();
+/// assert_eq!(cidr.ip4(), Some(24));
+/// assert_eq!(cidr.ip6(), Some(64));
+/// assert_eq!(DualCidr::new(None, Some(48)).unwrap().to_string(), "//48");
+/// assert!(DualCidr::new(Some(33), None).is_err());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DualCidr {
+    ip4: Option<u8>,
+    ip6: Option<u8>,
+}
+
+impl DualCidr {
+    /// Create a new `DualCidr`. The prefix lengths may not exceed 32 and 128.
+    pub fn new(ip4: Option<u8>, ip6: Option<u8>) -> Result<Self, MechanismError> {
+        let cidr = Self { ip4, ip6 };
+        if ip4.is_some_and(|l| l > 32) || ip6.is_some_and(|l| l > 128) {
+            return Err(MechanismError::InvalidMechanismFormat(cidr.to_string()));
+        }
+        Ok(cidr)
+    }
+    /// The prefix length applied to IPv4 addresses.
+    pub fn ip4(&self) -> Option<u8> {
+        self.ip4
+    }
+    /// The prefix length applied to IPv6 addresses.
+    pub fn ip6(&self) -> Option<u8> {
+        self.ip6
+    }
+    /// Returns `true` if neither prefix length is given.
+    pub fn is_empty(&self) -> bool {
+        self.ip4.is_none() && self.ip6.is_none()
+    }
+}
+
+impl Display for DualCidr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let Some(ip4) = self.ip4 {
+            write!(f, "/{}", ip4)?;
+        }
+        if let Some(ip6) = self.ip6 {
+            write!(f, "//{}", ip6)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DualCidr {
+    type Err = MechanismError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::core::parser::parse_dual_cidr_length(s)
+            .ok_or_else(|| MechanismError::InvalidMechanismFormat(s.to_string()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for DualCidr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DualCidr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal() {
+        let spec: DomainSpec = "example.com.".parse().unwrap();
+        assert_eq!(spec.as_literal(), Some("example.com."));
+        assert_eq!(spec.labels().unwrap(), ["example", "com"]);
+        assert_eq!(spec.to_string(), "example.com.");
+    }
+    #[test]
+    fn macros() {
+        let spec: DomainSpec = "%{L1r+-}.%%%_%-.example.com".parse().unwrap();
+        let segments = spec.segments();
+        match &segments[0] {
+            Segment::Macro(m) => {
+                assert_eq!(m.letter(), 'l');
+                assert!(m.is_url_escaped());
+                assert_eq!(m.digits(), Some(1));
+                assert!(m.is_reversed());
+                assert_eq!(m.delimiters(), "+-");
+            }
+            _ => panic!("expected a macro"),
+        }
+        assert_eq!(segments[2], Segment::Escape('%'));
+        assert_eq!(segments[3], Segment::Escape('_'));
+        assert_eq!(segments[4], Segment::Escape('-'));
+        assert_eq!(segments[5], Segment::Literal(".example.com".to_string()));
+        assert_eq!(spec.to_string(), "%{L1r+-}.%%%_%-.example.com");
+    }
+    #[test]
+    fn invalid() {
+        for s in ["", "example", "example.com/24", "%{x}.example.com"] {
+            assert!(s.parse::<DomainSpec>().is_err(), "{}", s);
+        }
+    }
+    #[test]
+    fn dual_cidr() {
+        let cidr: DualCidr = "//64".parse().unwrap();
+        assert_eq!(cidr.ip4(), None);
+        assert_eq!(cidr.ip6(), Some(64));
+        assert!("".parse::<DualCidr>().unwrap().is_empty());
+        assert!("/33".parse::<DualCidr>().is_err());
+        assert!("24".parse::<DualCidr>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde {
+        use super::*;
+
+        #[test]
+        fn domain_spec() {
+            let spec: DomainSpec = "%{i}._spf.example.com".parse().unwrap();
+            let json = serde_json::to_string(&spec).unwrap();
+            assert_eq!(json, "\"%{i}._spf.example.com\"");
+            let deserialized: DomainSpec = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized, spec);
+            assert!(serde_json::from_str::<DomainSpec>("\"example\"").is_err());
+        }
+        #[test]
+        fn dual_cidr() {
+            let cidr = DualCidr::new(Some(24), Some(64)).unwrap();
+            let json = serde_json::to_string(&cidr).unwrap();
+            assert_eq!(json, "\"/24//64\"");
+            assert_eq!(serde_json::from_str::<DualCidr>(&json).unwrap(), cidr);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::prelude::*;
+
 /// Error message when unable to construct a new Mechanism.
 #[derive(Debug, Clone, PartialEq)]
 pub enum MechanismError {
@@ -10,7 +12,7 @@ pub enum MechanismError {
     /// Indicates that the provided string could not be parsed into an IpNetwork::IP6 though it is a valid IpNetwork.
     NotIP6Network(String),
     /// Indicates that the provided string does not contain any valid IpNetwork.
-    InvalidIPNetwork(ipnetwork::IpNetworkError),
+    InvalidIPNetwork(NetworkError),
     /// Attempted to access a Mechanism as a `Mechanism<IpNetwork>` but is `Mechanism<String>`
     NotIpNetworkMechanism,
     /// Attempted to access a Mechanism as a `Mechanism<String>` but is `Mechanism<IpNetwork>`
@@ -20,8 +22,8 @@ pub enum MechanismError {
     InvalidDomainHost(String),
 }
 
-impl std::fmt::Display for MechanismError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for MechanismError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             MechanismError::InvalidMechanismFormat(mesg) => {
                 write!(f, "{} does not conform to any Mechanism format", mesg)
@@ -82,4 +84,59 @@ impl MechanismError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for MechanismError {}
+
+/// An `ip4` or `ip6` network which could not be parsed.
+///
+/// The same type is used with and without the `std` feature. With `std` the error reported by
+/// `ipnetwork` is kept and is available from
+/// [`ipnetwork_error()`](NetworkError::ipnetwork_error).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkError {
+    #[cfg(feature = "std")]
+    error: ipnetwork::IpNetworkError,
+    #[cfg(not(feature = "std"))]
+    network: String,
+}
+
+impl NetworkError {
+    // `network` is not a valid address or network.
+    pub(crate) fn invalid_addr(network: &str) -> Self {
+        #[cfg(feature = "std")]
+        return ipnetwork::IpNetworkError::InvalidAddr(network.to_string()).into();
+        #[cfg(not(feature = "std"))]
+        return Self {
+            network: network.to_string(),
+        };
+    }
+    /// The error reported by `ipnetwork`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    pub fn ipnetwork_error(&self) -> &ipnetwork::IpNetworkError {
+        &self.error
+    }
+}
+
+impl core::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(feature = "std")]
+        return write!(f, "{}", self.error);
+        #[cfg(not(feature = "std"))]
+        return write!(f, "invalid address: {}", self.network);
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ipnetwork::IpNetworkError> for NetworkError {
+    fn from(error: ipnetwork::IpNetworkError) -> Self {
+        Self { error }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NetworkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -53,6 +53,17 @@ pub enum Kind {
     Exists,
     /// Represents a Mechanism of type *All*
     All,
+    /// Represents a *Modifier* of type exp=
+    /// ```text
+    /// exp=<domain>
+    /// ```
+    Exp,
+    /// Represents any *Modifier* which is not defined by RFC7208.
+    /// The `rrdata` of such a modifier holds the complete `name=value` term.
+    /// ```text
+    /// moo=%{s}.example.com
+    /// ```
+    UnknownModifier,
 }
 
 impl Kind {
@@ -110,6 +121,45 @@ impl Kind {
     pub fn is_all(&self) -> bool {
         matches!(self, Self::All)
     }
+    /// Returns `true` if the modifier is [`Exp`](Kind::Exp).
+    pub fn is_exp(&self) -> bool {
+        matches!(self, Self::Exp)
+    }
+    /// Returns `true` if the modifier is [`UnknownModifier`](Kind::UnknownModifier).
+    pub fn is_unknown_modifier(&self) -> bool {
+        matches!(self, Self::UnknownModifier)
+    }
+    /// Returns `true` if this is a *Modifier* rather than a *Mechanism*.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use decon_spf::mechanism::Kind;
+    /// assert!(Kind::Redirect.is_modifier());
+    /// assert!(Kind::Exp.is_modifier());
+    /// assert!(!Kind::Include.is_modifier());
+    /// ```
+    pub fn is_modifier(&self) -> bool {
+        matches!(self, Self::Redirect | Self::Exp | Self::UnknownModifier)
+    }
+    /// Returns `true` if evaluating the term requires a DNS lookup which counts towards the
+    /// limit of 10.\
+    /// [RFC7208 Section 4.6.4](https://datatracker.ietf.org/doc/html/rfc7208#section-4.6.4)
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use decon_spf::mechanism::Kind;
+    /// assert!(Kind::Exists.is_lookup());
+    /// assert!(Kind::Redirect.is_lookup());
+    /// assert!(!Kind::IpV4.is_lookup());
+    /// ```
+    pub fn is_lookup(&self) -> bool {
+        matches!(
+            self,
+            Self::A | Self::MX | Self::Ptr | Self::Include | Self::Exists | Self::Redirect
+        )
+    }
     /// Returns a reference to the str for kind enums.
     ///
     /// # Examples:
@@ -135,12 +185,14 @@ impl Kind {
             Kind::Ptr => "ptr",
             Kind::Exists => "exists:",
             Kind::All => "all",
+            Kind::Exp => "exp=",
+            Kind::UnknownModifier => "",
         }
     }
 }
 
-impl std::fmt::Display for Kind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Kind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Kind::Redirect => write!(f, "redirect="),
             Kind::Include => write!(f, "include:"),
@@ -151,6 +203,8 @@ impl std::fmt::Display for Kind {
             Kind::Ptr => write!(f, "ptr"),
             Kind::Exists => write!(f, "exists:"),
             Kind::All => write!(f, "all"),
+            Kind::Exp => write!(f, "exp="),
+            Kind::UnknownModifier => Ok(()),
         }
     }
 }
@@ -200,6 +254,16 @@ fn all() {
     let a = Kind::All;
     assert_eq!(a.to_string(), "all");
 }
+#[test]
+fn exp() {
+    let a = Kind::Exp;
+    assert_eq!(a.to_string(), "exp=");
+}
+#[test]
+fn unknown_modifier() {
+    let a = Kind::UnknownModifier;
+    assert_eq!(a.to_string(), "");
+}
 
 #[cfg(test)]
 #[cfg(feature = "serde")]
@@ -8,19 +8,20 @@
 //! The module also contains a number of ways to create the `Mechanism` instances.
 //! - [`ParsedMechanism`]
 //!     - This provides a unified method for parsing any mechanism string. It will either contain
-//! a `Mechanism<String>`
-//! or a `Mechanism<IpNetwork>` if the string is successfully parsed.
+//!       a `Mechanism<String>` or a `Mechanism<IpNetwork>` if the string is successfully parsed.
 //! - Both `Mechanism<String>` and `Mechanism<IpNetwork>` have the `FromStr` trait implemented.
-//! Allowing for the strings
-//! to be `parsed()`
+//!   Allowing for the strings to be `parsed()`
+//! - `Mechanism<IpNetwork>` requires the `std` feature. Without it, `ip4` and `ip6` terms are
+//!   parsed into a `Mechanism<String>` held by [`ParsedMechanism`].
 //! - The `Mechanism` struct also has a number of specific methods which can be used to create
-//! related mechanisms; which are used with the `FromStr` trait.
+//!   related mechanisms; which are used with the `FromStr` trait.
 //!
 #[cfg(feature = "builder")]
 #[cfg_attr(docsrs, doc(cfg(feature = "builder")))]
 pub(crate) mod builder;
 #[cfg(feature = "builder")]
 mod conv_traits;
+mod domain_spec;
 mod errors;
 mod kind;
 mod parsedmechanism;
@@ -28,29 +29,87 @@ mod qualifier;
 #[cfg(test)]
 mod tests;
 
-pub use crate::spf::mechanism::errors::MechanismError;
+pub use crate::spf::mechanism::domain_spec::{DomainSpec, DualCidr, MacroExpand, Segment};
+pub use crate::spf::mechanism::errors::{MechanismError, NetworkError};
 pub use crate::spf::mechanism::kind::Kind;
 pub use crate::spf::mechanism::parsedmechanism::ParsedMechanism;
 pub use crate::spf::mechanism::qualifier::Qualifier;
 
 use crate::core;
+use crate::prelude::*;
 
+use ::core::fmt::{Display, Formatter};
+use ::core::{convert::TryFrom, str::FromStr};
+#[cfg(feature = "std")]
 use ipnetwork::{IpNetwork, IpNetworkError};
-use std::fmt::{Display, Formatter};
-use std::{convert::TryFrom, str::FromStr};
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
 /// Stores its [`Kind`], [`Qualifier`], and its `rrdata`
 #[derive(Default, Debug, Clone, PartialEq, Copy, Eq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Mechanism<T> {
     kind: Kind,
     qualifier: Qualifier,
     rrdata: Option<T>,
 }
 
+// The serialized form of a `Mechanism`, which is checked before a `Mechanism` is made from it.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "Mechanism")]
+struct Fields<T> {
+    kind: Kind,
+    qualifier: Qualifier,
+    rrdata: Option<T>,
+}
+
+/// The fields are parsed as the term they display as, so the `rrdata` must hold a valid
+/// [`DomainSpec`] and `dual-cidr-length` for its `Kind`. `a:exa mple..com/99` is rejected.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Mechanism<String> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = Fields::<String>::deserialize(deserializer)?;
+        let kind = fields.kind;
+        let term = Mechanism::generic_inclusive(kind, fields.qualifier, fields.rrdata).to_string();
+        let m: Mechanism<String> = match core::parser::parse_term(&term) {
+            #[cfg(feature = "std")]
+            Ok(ParsedMechanism::IP(m)) => m.into(),
+            Ok(ParsedMechanism::TXT(m)) => m,
+            Err(e) => return Err(de::Error::custom(e)),
+        };
+        // A qualifier given to a modifier makes it parse as another `Kind`.
+        match m.kind == kind {
+            true => Ok(m),
+            false => Err(de::Error::custom(MechanismError::InvalidMechanismFormat(
+                term,
+            ))),
+        }
+    }
+}
+
+/// The network must be of the family given by its `Kind`.
+#[cfg(all(feature = "serde", feature = "std"))]
+impl<'de> Deserialize<'de> for Mechanism<IpNetwork> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = Fields::<IpNetwork>::deserialize(deserializer)?;
+        match (fields.kind, fields.rrdata) {
+            (Kind::IpV4, Some(ip)) if ip.is_ipv4() => Ok(Mechanism::ip(fields.qualifier, ip)),
+            (Kind::IpV6, Some(ip)) if ip.is_ipv6() => Ok(Mechanism::ip(fields.qualifier, ip)),
+            (Kind::IpV4, Some(ip)) => Err(de::Error::custom(MechanismError::NotIP4Network(
+                ip.to_string(),
+            ))),
+            (Kind::IpV6, Some(ip)) => Err(de::Error::custom(MechanismError::NotIP6Network(
+                ip.to_string(),
+            ))),
+            (kind, _) => Err(de::Error::custom(MechanismError::InvalidMechanismFormat(
+                kind.to_string(),
+            ))),
+        }
+    }
+}
+
 /// Create a `Mechanism<String>` from the provided string.
 ///
 /// # Examples:
@@ -72,59 +131,11 @@ impl FromStr for Mechanism<String> {
     type Err = MechanismError;
 
     fn from_str(s: &str) -> Result<Mechanism<String>, Self::Err> {
-        // A String ending with either ':' or "/" is always invalid.
-        if s.ends_with(':') || s.ends_with('/') {
-            return Err(MechanismError::InvalidMechanismFormat(s.to_string()));
-        };
-        if s.contains(core::IP4) || s.contains(core::IP6) {
-            return Err(MechanismError::InvalidMechanismFormat(s.to_string()));
+        match core::parser::parse_term(s)? {
+            ParsedMechanism::TXT(m) if !m.kind().is_ip() => Ok(m),
+            // An ip4 or ip6 mechanism string.
+            _ => Err(MechanismError::InvalidMechanismFormat(s.to_string())),
         }
-        let mut m: Option<Mechanism<String>> = None;
-
-        if s.contains(core::REDIRECT) {
-            let mut items = s.rsplit('=');
-            if let Some(rrdata) = items.next() {
-                m = Some(Mechanism::generic_inclusive(
-                    Kind::Redirect,
-                    Qualifier::Pass,
-                    Some(rrdata.to_string()),
-                ));
-            }
-        } else if s.contains(core::INCLUDE) {
-            let qualifier_and_modified_str = core::return_and_remove_qualifier(s, 'i');
-            if let Some(rrdata) = s.rsplit(':').next() {
-                m = Some(Mechanism::generic_inclusive(
-                    Kind::Include,
-                    qualifier_and_modified_str.0,
-                    Some(rrdata.to_string()),
-                ));
-            }
-        } else if s.ends_with(core::ALL) && (s.len() == 3 || s.len() == 4) {
-            m = Some(Mechanism::generic_inclusive(
-                Kind::All,
-                core::return_and_remove_qualifier(s, 'a').0,
-                None,
-            ));
-        } else if let Ok(mechanism) = core::spf_regex::capture_matches(s, Kind::A) {
-            m = Some(mechanism);
-        } else if let Ok(mechanism) = core::spf_regex::capture_matches(s, Kind::MX) {
-            m = Some(mechanism);
-        } else if let Ok(mechanism) = core::spf_regex::capture_matches(s, Kind::Ptr) {
-            m = Some(mechanism);
-        } else if let Ok(mechanism) = core::spf_regex::capture_matches(s, Kind::Exists) {
-            m = Some(mechanism);
-        }
-        if let Some(value) = m {
-            #[cfg(feature = "strict-dns")]
-            {
-                if !core::dns::is_dns_suffix_valid(core::dns::get_domain_before_slash(&value.raw()))
-                {
-                    return Err(MechanismError::InvalidDomainHost(value.raw()));
-                }
-            }
-            return Ok(value);
-        }
-        Err(MechanismError::InvalidMechanismFormat(s.to_string()))
     }
 }
 
@@ -156,42 +167,20 @@ impl TryFrom<&str> for Mechanism<String> {
 /// assert_eq!(err, MechanismError::NotIP6Network("203.32.160.0/24".to_string()));
 /// assert_eq!(err.to_string(), "203.32.160.0/24 is not an ip6 network");
 ///```
+#[cfg(feature = "std")]
 impl FromStr for Mechanism<IpNetwork> {
     type Err = MechanismError;
 
     fn from_str(s: &str) -> Result<Mechanism<IpNetwork>, Self::Err> {
-        if s.contains(core::IP4) || s.contains(core::IP6) {
-            let raw_ip: Option<&str>;
-            let qualifier_and_modified_str = core::return_and_remove_qualifier(s, 'i');
-            let kind = match qualifier_and_modified_str {
-                (_, str) if str.contains(core::IP4) => Kind::IpV4,
-                (_, str) if str.contains(core::IP6) => Kind::IpV6,
-                // This is probably unreachable.
-                _ => return Err(MechanismError::InvalidMechanismFormat(s.to_string())),
-            };
-            raw_ip = qualifier_and_modified_str.1.splitn(2, ":").last();
-            return match raw_ip.unwrap().parse::<IpNetwork>() {
-                Err(e) => Err(MechanismError::InvalidIPNetwork(e)),
-                Ok(ip) => {
-                    if ip.is_ipv4() && !kind.is_ip_v4() {
-                        return Err(MechanismError::NotIP6Network(ip.to_string()));
-                    }
-                    if ip.is_ipv6() && !kind.is_ip_v6() {
-                        return Err(MechanismError::NotIP4Network(ip.to_string()));
-                    }
-                    Ok(Mechanism::generic_inclusive(
-                        kind,
-                        qualifier_and_modified_str.0,
-                        Some(ip),
-                    ))
-                }
-            };
+        match core::parser::parse_term(s)? {
+            ParsedMechanism::IP(m) => Ok(m),
+            // Catch all. This is not an ip4 or ip6 mechanism string.
+            ParsedMechanism::TXT(_) => Err(MechanismError::InvalidMechanismFormat(s.to_string())),
         }
-        // Catch all. This is not an ip4 or ip6 mechanism string.
-        Err(MechanismError::InvalidMechanismFormat(s.to_string()))
     }
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<&str> for Mechanism<IpNetwork> {
     type Error = MechanismError;
 
@@ -259,7 +248,7 @@ impl<T> Mechanism<T> {
 impl Mechanism<String> {
     /// Create a new Mechanism struct of `Redirect`
     pub fn redirect(qualifier: Qualifier, rrdata: &str) -> Result<Self, MechanismError> {
-        Ok(Mechanism::new(Kind::Redirect, qualifier).with_rrdata(rrdata)?)
+        Mechanism::new(Kind::Redirect, qualifier).with_rrdata(rrdata)
     }
     /// Create a new Mechanism struct of `A`
     ///
@@ -330,7 +319,7 @@ impl Mechanism<String> {
     /// assert_eq!(include2.to_string(), "~include:example.com")
     /// ```
     pub fn include(qualifier: Qualifier, rrdata: &str) -> Result<Self, MechanismError> {
-        Ok(Mechanism::new(Kind::Include, qualifier).with_rrdata(rrdata)?)
+        Mechanism::new(Kind::Include, qualifier).with_rrdata(rrdata)
     }
     /// Create a new Mechanism struct of `Ptr`
     /// # Example:
@@ -349,7 +338,18 @@ impl Mechanism<String> {
     }
     /// Create a new Mechanism struct of `Exists`
     pub fn exists(qualifier: Qualifier, rrdata: &str) -> Result<Self, MechanismError> {
-        Ok(Mechanism::new(Kind::Exists, qualifier).with_rrdata(rrdata)?)
+        Mechanism::new(Kind::Exists, qualifier).with_rrdata(rrdata)
+    }
+    /// Create a new Mechanism struct of `Exp`
+    /// # Example:
+    /// ```rust
+    /// use decon_spf::mechanism::Mechanism;
+    /// let exp = Mechanism::exp("explain._spf.%{d}").unwrap();
+    /// assert_eq!(exp.kind().is_exp(), true);
+    /// assert_eq!(exp.to_string(), "exp=explain._spf.%{d}");
+    /// ```
+    pub fn exp(rrdata: &str) -> Result<Self, MechanismError> {
+        Mechanism::new(Kind::Exp, Qualifier::Pass).with_rrdata(rrdata)
     }
     /// Set the rrdata for Mechanism
     /// # Note: This is only applicable for Mechanisms of `A`, `MX` and `Ptr`.  
@@ -358,22 +358,113 @@ impl Mechanism<String> {
     /// See: [`a`](Mechanism<String>::a) for an example.
     pub fn with_rrdata(mut self, rrdata: impl Into<String>) -> Result<Self, MechanismError> {
         let rrdata_string = rrdata.into();
+        if !core::parser::is_valid_rrdata(self.kind, &rrdata_string) {
+            return Err(MechanismError::InvalidMechanismFormat(rrdata_string));
+        }
+        self.rrdata = Some(rrdata_string);
         #[cfg(feature = "strict-dns")]
         {
-            match self.kind() {
-                Kind::A | Kind::MX | Kind::Include | Kind::Ptr | Kind::Exists => {
-                    if !core::dns::is_dns_suffix_valid(core::dns::get_domain_before_slash(
-                        rrdata_string.as_str(),
-                    )) {
-                        return Err(MechanismError::InvalidDomainHost(rrdata_string));
-                    };
-                }
-                _ => {}
-            };
+            if !core::dns::is_domain_spec_valid(self.domain_spec()) {
+                return Err(MechanismError::InvalidDomainHost(
+                    self.rrdata.take().unwrap_or_default(),
+                ));
+            }
         }
-        self.rrdata = Some(rrdata_string);
         Ok(self)
     }
+    /// Set the domain of the Mechanism or Modifier. Any `dual-cidr-length` of `A` and `MX` is kept.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{DomainSpec, Mechanism, Qualifier};
+    /// let spec: DomainSpec = "example.com".parse().unwrap();
+    /// let a = Mechanism::a(Qualifier::Pass).with_rrdata("/24").unwrap();
+    /// let a = a.with_domain_spec(spec).unwrap();
+    /// assert_eq!(a.to_string(), "a:example.com/24");
+    /// let include = Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap();
+    /// assert_eq!(include.with_domain_spec("%{d}.example.com".parse().unwrap()).unwrap().raw(), "%{d}.example.com");
+    /// ```
+    pub fn with_domain_spec(self, spec: DomainSpec) -> Result<Self, MechanismError> {
+        let cidr = self.cidr().map(|c| c.to_string()).unwrap_or_default();
+        self.with_rrdata(format!("{}{}", spec, cidr))
+    }
+    /// Set the `dual-cidr-length` of `A` and `MX`. An empty `DualCidr` removes it.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{DualCidr, Mechanism, Qualifier};
+    /// let mx = Mechanism::mx(Qualifier::Pass).with_rrdata("example.com").unwrap();
+    /// let mx = mx.with_cidr(DualCidr::new(Some(24), None).unwrap()).unwrap();
+    /// assert_eq!(mx.to_string(), "mx:example.com/24");
+    /// let mx = Mechanism::mx(Qualifier::Pass).with_cidr("//64".parse().unwrap()).unwrap();
+    /// assert_eq!(mx.to_string(), "mx//64");
+    /// ```
+    pub fn with_cidr(mut self, cidr: DualCidr) -> Result<Self, MechanismError> {
+        if !self.kind.is_a() && !self.kind.is_mx() {
+            return Err(MechanismError::InvalidMechanismFormat(format!(
+                "{}{}",
+                self, cidr
+            )));
+        }
+        match (self.domain_spec(), cidr.is_empty()) {
+            (Some(spec), _) => self.with_rrdata(format!("{}{}", spec, cidr)),
+            (None, true) => {
+                self.rrdata = None;
+                Ok(self)
+            }
+            (None, false) => self.with_rrdata(cidr.to_string()),
+        }
+    }
+    /// Returns the `domain-spec` held in `rrdata`, without any `dual-cidr-length`. It is parsed
+    /// from `rrdata` on each call.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Mechanism;
+    /// let a: Mechanism<String> = "a:%{d}.example.com/24".parse().unwrap();
+    /// let spec = a.domain_spec().unwrap();
+    /// assert!(spec.has_macros());
+    /// assert_eq!(spec.to_string(), "%{d}.example.com");
+    /// assert_eq!(a.cidr().unwrap().ip4(), Some(24));
+    /// ```
+    pub fn domain_spec(&self) -> Option<DomainSpec> {
+        let rrdata = self.rrdata.as_deref()?;
+        match self.kind {
+            Kind::A | Kind::MX => {
+                core::parser::parse_domain_spec(core::parser::split_dual_cidr_length(rrdata).0)
+            }
+            Kind::Include | Kind::Exists | Kind::Ptr | Kind::Redirect | Kind::Exp => {
+                core::parser::parse_domain_spec(rrdata)
+            }
+            _ => None,
+        }
+    }
+    /// Returns the `dual-cidr-length` of `A` and `MX` if one is present. It is parsed from
+    /// `rrdata` on each call.
+    pub fn cidr(&self) -> Option<DualCidr> {
+        let rrdata = self.rrdata.as_deref()?;
+        match self.kind {
+            Kind::A | Kind::MX if rrdata.starts_with('/') => {
+                core::parser::parse_dual_cidr_length(rrdata)
+            }
+            Kind::A | Kind::MX => {
+                core::parser::parse_dual_cidr_length(core::parser::split_dual_cidr_length(rrdata).1)
+                    .filter(|cidr| !cidr.is_empty())
+            }
+            _ => None,
+        }
+    }
+    // Terms are the same if they differ only by qualifier. Domain names are compared without
+    // regard to case, but the case of a macro letter is significant.
+    pub(crate) fn is_same_term(&self, other: &Mechanism<String>) -> bool {
+        self.kind == other.kind
+            && match (self.rrdata.as_deref(), other.rrdata.as_deref()) {
+                (Some(a), Some(b)) if a.contains('%') => a == b,
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
     /// Return the mechanism string stored in the `Mechanism`
     ///
     /// # Example:
@@ -386,13 +477,9 @@ impl Mechanism<String> {
     /// assert_eq!(mechanism_a_string.raw(), "example.com");
     /// ```
     pub fn raw(&self) -> String {
-        if self.rrdata.is_none() {
-            self.kind().to_string()
-        } else {
-            self.rrdata
-                .as_ref()
-                .expect("This should not be None.")
-                .to_string()
+        match self.rrdata {
+            None => self.kind().to_string(),
+            Some(ref rrdata) => rrdata.to_string(),
         }
     }
 
@@ -409,18 +496,14 @@ impl Mechanism<String> {
             tmp_mechanism_str = "";
         }
         match self.kind {
-            Kind::A | Kind::MX => {
-                // This must be starting with 'domain.com' So prepend ':'
-                if !tmp_mechanism_str.is_empty() && !tmp_mechanism_str.starts_with('/') {
-                    mechanism_str.push(':')
-                }
-            }
-            Kind::Ptr => {
-                // This Ptr has a domain. Prepend ':'
-                if !tmp_mechanism_str.is_empty() {
-                    mechanism_str.push(':')
-                }
+            // This must be starting with 'domain.com' So prepend ':'
+            Kind::A | Kind::MX
+                if !tmp_mechanism_str.is_empty() && !tmp_mechanism_str.starts_with('/') =>
+            {
+                mechanism_str.push(':')
             }
+            // This Ptr has a domain. Prepend ':'
+            Kind::Ptr if !tmp_mechanism_str.is_empty() => mechanism_str.push(':'),
             // Do nothing in all other cases.
             _ => {}
         }
@@ -431,17 +514,19 @@ impl Mechanism<String> {
 
 /// Provide to_string for `Mechanism<String>`
 impl Display for Mechanism<String> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::core::fmt::Result {
         write!(f, "{}", self.build_string())
     }
 }
 
+#[cfg(feature = "std")]
 impl From<IpNetworkError> for MechanismError {
     fn from(err: IpNetworkError) -> Self {
-        MechanismError::InvalidIPNetwork(err)
+        MechanismError::InvalidIPNetwork(err.into())
     }
 }
 
+#[cfg(feature = "std")]
 impl Mechanism<IpNetwork> {
     /// Create a new V4 or V6 Mechanism from a string representation.
     /// This is really just a convenience function around the `FromStr` trait that
@@ -455,7 +540,7 @@ impl Mechanism<IpNetwork> {
     /// }
     ///```
     pub fn ip_from_string(string: &str) -> Result<Mechanism<IpNetwork>, MechanismError> {
-        Ok(Mechanism::<IpNetwork>::from_str(string)?)
+        Mechanism::<IpNetwork>::from_str(string)
     }
 
     /// Create a new V4 or V6 `Mechanism<IpNetwork>`
@@ -534,16 +619,9 @@ impl Mechanism<IpNetwork> {
     // This happens when the ip address provided does not contain a prefix.
     // This means we need to strip this out before returning the string representation
     fn sanitize_ip_addr(ip: &IpNetwork) -> String {
-        let ip_binding = ip.to_string();
-        match ip.is_ipv4() {
-            true => match ip.prefix() {
-                32 => ip.network().to_string(),
-                _ => ip_binding,
-            },
-            false => match ip.prefix() {
-                128 => ip.network().to_string(),
-                _ => ip_binding,
-            },
+        match (ip, ip.prefix()) {
+            (IpNetwork::V4(_), 32) | (IpNetwork::V6(_), 128) => ip.ip().to_string(),
+            _ => ip.to_string(),
         }
     }
 
@@ -554,8 +632,9 @@ impl Mechanism<IpNetwork> {
 }
 
 /// Provide to_string for `Mechanism<IpNetwork`>
+#[cfg(feature = "std")]
 impl Display for Mechanism<IpNetwork> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> ::core::fmt::Result {
         write!(f, "{}", self.build_string())
     }
 }
@@ -591,8 +670,51 @@ mod serde_tests {
         let deserialized: Mechanism<String> = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, mx);
     }
+
+    #[test]
+    fn ip4() {
+        let ip4 = Mechanism::generic_inclusive(
+            Kind::IpV4,
+            Qualifier::Fail,
+            Some("203.0.113.0/24".to_string()),
+        );
+        let json = serde_json::to_string(&ip4).unwrap();
+        let deserialized: Mechanism<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, ip4);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn network() {
+        let ip6: Mechanism<IpNetwork> = "ip6:2001:db8::/32".parse().unwrap();
+        let json = serde_json::to_string(&ip6).unwrap();
+        let deserialized: Mechanism<IpNetwork> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, ip6);
+        let json = r#"{"kind":"IpV4","qualifier":"Pass","rrdata":"2001:db8::/32"}"#;
+        assert!(serde_json::from_str::<Mechanism<IpNetwork>>(json).is_err());
+    }
+
+    #[test]
+    fn invalid_rrdata() {
+        for json in [
+            r#"{"kind":"A","qualifier":"Pass","rrdata":"exa mple..com/99"}"#,
+            r#"{"kind":"MX","qualifier":"Pass","rrdata":"example.com/33"}"#,
+            r#"{"kind":"Include","qualifier":"Pass","rrdata":null}"#,
+            r#"{"kind":"Exists","qualifier":"Pass","rrdata":"%{z}.example.com"}"#,
+            r#"{"kind":"All","qualifier":"Fail","rrdata":"example.com"}"#,
+            r#"{"kind":"Redirect","qualifier":"Fail","rrdata":"example.com"}"#,
+            r#"{"kind":"IpV4","qualifier":"Pass","rrdata":"2001:db8::/32"}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Mechanism<String>>(json).is_err(),
+                "{}",
+                json
+            );
+        }
+    }
 }
 
+#[cfg(feature = "std")]
 impl From<Mechanism<IpNetwork>> for Mechanism<String> {
     fn from(value: Mechanism<IpNetwork>) -> Self {
         Mechanism::generic_inclusive(
@@ -605,6 +727,7 @@ impl From<Mechanism<IpNetwork>> for Mechanism<String> {
     }
 }
 
+#[cfg(feature = "std")]
 impl TryFrom<Mechanism<String>> for Mechanism<IpNetwork> {
     type Error = MechanismError;
     fn try_from(value: Mechanism<String>) -> Result<Self, Self::Error> {
@@ -619,11 +742,12 @@ impl TryFrom<Mechanism<String>> for Mechanism<IpNetwork> {
 }
 
 #[cfg(test)]
+#[cfg(feature = "std")]
 mod string_ip_conversion {
     use crate::mechanism::Kind::{IpV4, A};
     use crate::mechanism::Qualifier;
     use crate::mechanism::*;
-    use std::convert::TryInto;
+    use ::core::convert::TryInto;
     #[test]
     fn ip_to_string_mechanism() {
         let s = "ip4:192.168.0.1".parse::<Mechanism<IpNetwork>>().unwrap();
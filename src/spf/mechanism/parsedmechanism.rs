@@ -1,23 +1,32 @@
 use crate::core;
+use crate::prelude::*;
 use crate::spf::mechanism::{Kind, Mechanism, MechanismError, Qualifier};
+#[cfg(feature = "std")]
 use ipnetwork::IpNetwork;
 
-use std::{convert::TryFrom, str::FromStr};
+use ::core::{convert::TryFrom, str::FromStr};
 
 /// Stores the result of a successful parsing of a Mechanism String.  
 /// This will either contain a `Mechanism<String>` or `Mechanism<IpNetwork>`
+///
+/// Without the `std` feature there is only the `TXT` variant, which also holds `ip4` and `ip6`
+/// mechanisms. The enum is `non_exhaustive` so that a `match` compiles whether or not `std` is
+/// enabled by any crate in the build.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ParsedMechanism {
     /// This variant represents a Mechanism containing a String  
     TXT(Mechanism<String>),
     /// This variant represents a Mechanism containing an IpNetwork  
+    #[cfg(feature = "std")]
     IP(Mechanism<IpNetwork>),
 }
 
-impl std::fmt::Display for ParsedMechanism {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ::core::fmt::Display for ParsedMechanism {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
         match *self {
             ParsedMechanism::TXT(ref m) => write!(f, "{}", m),
+            #[cfg(feature = "std")]
             ParsedMechanism::IP(ref m) => write!(f, "{}", m),
         }
     }
@@ -27,15 +36,19 @@ impl std::fmt::Display for ParsedMechanism {
 /// Provides the ability to parse any supported `Spf Mechanisms`. See [`Kind`](Kind)
 /// # Examples:
 ///```rust
-/// use ipnetwork::IpNetworkError::InvalidAddr;
 /// use decon_spf::mechanism::{ParsedMechanism, MechanismError};
 /// let mechanism_a: ParsedMechanism = "a:test.com/24".parse().unwrap();
 /// let mechanism_mx = "mx:example.com".parse::<ParsedMechanism>().unwrap();
 /// let mechanism_ip4 = "ip4:203.32.160.10/24".parse::<ParsedMechanism>().unwrap();
 ///
 /// let mechanism_not_ip4 = "ip4:example.com".parse::<ParsedMechanism>().unwrap_err();
-/// assert_eq!(mechanism_not_ip4,
-///            MechanismError::InvalidIPNetwork(InvalidAddr("example.com".to_string())));
+/// #[cfg(feature = "std")]
+/// {
+///     use ipnetwork::IpNetworkError::InvalidAddr;
+///     assert_eq!(mechanism_not_ip4,
+///                MechanismError::InvalidIPNetwork(InvalidAddr("example.com".to_string()).into()));
+/// }
+/// assert!(mechanism_not_ip4.is_invalid_ip());
 /// assert_eq!(mechanism_not_ip4.to_string(), "invalid address: example.com".to_string());
 ///
 /// let mechanism_malformed: MechanismError =
@@ -47,11 +60,7 @@ impl FromStr for ParsedMechanism {
     type Err = MechanismError;
 
     fn from_str(s: &str) -> Result<ParsedMechanism, Self::Err> {
-        if s.contains(core::IP4) || s.contains(core::IP6) {
-            Ok(ParsedMechanism::IP(Mechanism::<IpNetwork>::from_str(s)?))
-        } else {
-            Ok(ParsedMechanism::TXT(Mechanism::<String>::from_str(s)?))
-        }
+        core::parser::parse_term(s)
     }
 }
 
@@ -88,15 +97,20 @@ impl ParsedMechanism {
     /// let mechanism = parsed_mechanism.txt();
     /// assert_eq!(mechanism.kind().is_mx(), true);
     /// let parsed_mechanism = ParsedMechanism::new("ip4:203.32.160.10").unwrap();
-    /// let mechanism = parsed_mechanism.network();
-    /// assert_eq!(mechanism.kind().is_ip(), true);
-    /// assert_eq!(mechanism.kind().is_ip_v4(), true);
-    /// assert_eq!(mechanism.kind().is_ip_v6(), false);
+    /// #[cfg(feature = "std")]
+    /// {
+    ///     let mechanism = parsed_mechanism.network();
+    ///     assert_eq!(mechanism.kind().is_ip(), true);
+    ///     assert_eq!(mechanism.kind().is_ip_v4(), true);
+    ///     assert_eq!(mechanism.kind().is_ip_v6(), false);
+    /// }
+    /// assert_eq!(parsed_mechanism.is_network(), true);
     ///```
     pub fn txt(&self) -> Mechanism<String> {
         match *self {
             ParsedMechanism::TXT(ref m) => m.clone(),
             // This needs to be changed to return an Err when called on network
+            #[cfg(feature = "std")]
             ParsedMechanism::IP(_) => unreachable!(),
         }
     }
@@ -108,6 +122,7 @@ impl ParsedMechanism {
     /// let mechanism = parsed_mechanism.network();
     /// assert_eq!(mechanism.kind().is_ip_v4(), true);
     ///```
+    #[cfg(feature = "std")]
     pub fn network(&self) -> Mechanism<IpNetwork> {
         match *self {
             ParsedMechanism::IP(ref m) => *m,
@@ -119,6 +134,7 @@ impl ParsedMechanism {
     fn kind(&self) -> &Kind {
         match *self {
             ParsedMechanism::TXT(ref m) => m.kind(),
+            #[cfg(feature = "std")]
             ParsedMechanism::IP(ref m) => m.kind(),
         }
     }
@@ -126,6 +142,7 @@ impl ParsedMechanism {
     fn qualifier(&self) -> &Qualifier {
         match *self {
             ParsedMechanism::TXT(ref m) => m.qualifier(),
+            #[cfg(feature = "std")]
             ParsedMechanism::IP(ref m) => m.qualifier(),
         }
     }
@@ -133,16 +150,15 @@ impl ParsedMechanism {
     fn raw(&self) -> String {
         match *self {
             ParsedMechanism::TXT(ref m) => m.raw(),
+            #[cfg(feature = "std")]
             ParsedMechanism::IP(ref m) => m.raw(),
         }
     }
     /// Returns `true` if the mechanism is an `IpNetwork` or `false` if it contains any other; A, MX, etc.
     pub fn is_network(&self) -> bool {
-        match *self {
-            ParsedMechanism::TXT(_) => false,
-            ParsedMechanism::IP(_) => true,
-        }
+        self.kind().is_ip()
     }
+    #[cfg(feature = "std")]
     #[allow(dead_code)]
     fn as_network(&self) -> Result<&IpNetwork, MechanismError> {
         match *self {
@@ -61,8 +61,8 @@ impl Qualifier {
     }
 }
 
-impl std::fmt::Display for Qualifier {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Qualifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Qualifier::Pass => write!(f, ""),
             Qualifier::Fail => write!(f, "-"),
@@ -72,21 +72,25 @@ impl std::fmt::Display for Qualifier {
     }
 }
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn is_pass() {
     let q = Qualifier::Pass;
     assert_eq!(q.is_pass(), true);
 }
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn is_fail() {
     let q = Qualifier::Fail;
     assert_eq!(q.is_fail(), true);
 }
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn is_softfail() {
     let q = Qualifier::SoftFail;
     assert_eq!(q.is_softfail(), true);
 }
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn is_neutral() {
     let q = Qualifier::Neutral;
     assert_eq!(q.is_neutral(), true);
@@ -62,7 +62,7 @@ mod ip4 {
             let m: MechanismError = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
             assert_eq!(
                 m,
-                MechanismError::InvalidIPNetwork(InvalidAddr("example.com".to_string()))
+                MechanismError::InvalidIPNetwork(InvalidAddr("example.com".to_string()).into())
             );
         }
 
@@ -72,7 +72,7 @@ mod ip4 {
             let ip_error = "203.32.160.0/33".parse::<IpNetwork>().unwrap_err();
 
             let m: MechanismError = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
-            assert_eq!(m, MechanismError::InvalidIPNetwork(ip_error));
+            assert_eq!(m, MechanismError::InvalidIPNetwork(ip_error.into()));
         }
 
         #[test]
@@ -146,7 +146,7 @@ mod ip6 {
             let ip_error = "2001:4860:4000::/129".parse::<IpNetwork>().unwrap_err();
 
             let m: MechanismError = input.parse::<Mechanism<IpNetwork>>().unwrap_err();
-            assert_eq!(m, MechanismError::InvalidIPNetwork(ip_error));
+            assert_eq!(m, MechanismError::InvalidIPNetwork(ip_error.into()));
         }
 
         #[test]
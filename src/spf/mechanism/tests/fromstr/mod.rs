@@ -3,6 +3,7 @@ mod all;
 mod exists;
 mod include;
 mod invalid;
+#[cfg(feature = "std")]
 mod ipnetwork;
 mod mx;
 mod ptr;
@@ -0,0 +1,301 @@
+//! Merge several records into one.
+//!
+//! [`merge()`] combines the terms of each record in turn into a [`SpfBuilder`]. A term found
+//! in an earlier record is not added again, and a term which differs from an earlier term only
+//! by its qualifier is a conflict: the earlier term is kept. The terms of earlier records are
+//! evaluated first, so records should be given in order of priority.
+//!
+//! A `redirect` is replaced by an `include` of the same domain so the hosts it authorizes
+//! remain authorized, and the strictest `all` of any record ends the merged record.
+//! Overlapping and adjacent networks are then merged with
+//! [aggregate_networks()](SpfBuilder::aggregate_networks).
+//!
+//! Each choice made is described by a [`Decision`], and the merged record is validated so any
+//! limit it exceeds, such as the number of DNS lookups, is reported.
+//!
+//! # Example:
+//! ```
+//! use decon_spf::merge::{merge, Decision};
+//! use decon_spf::Spf;
+//! let records: Vec<Spf<String>> = [
+//!     "v=spf1 ip4:192.0.2.0/25 include:_spf.example.net ~all",
+//!     "v=spf1 ip4:192.0.2.128/25 include:_spf.example.net -mx -all",
+//!     "v=spf1 mx redirect=_spf.example.org",
+//! ]
+//! .iter()
+//! .map(|r| r.parse().unwrap())
+//! .collect();
+//! let merged = merge(&records);
+//! assert_eq!(
+//!     merged.builder().to_string(),
+//!     "v=spf1 ip4:192.0.2.0/24 include:_spf.example.net -mx include:_spf.example.org -all"
+//! );
+//! assert!(merged.has_conflicts());
+//! assert_eq!(
+//!     merged.conflicts().map(|d| d.to_string()).collect::<Vec<_>>(),
+//!     ["record 2: `mx` conflicts with `-mx`, which is kept"]
+//! );
+//! assert!(!merged.report().has_errors());
+//! ```
+use crate::mechanism::{Kind, Mechanism, Qualifier};
+use crate::{Builder, Spf, SpfBuilder, ValidationReport, Version};
+use core::fmt::{Display, Formatter};
+
+/// A choice made by [`merge()`]. `record` is the index of the record within those given and
+/// `index` the index of the term within that record.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decision {
+    /// The record has a different version from the first record and none of its terms are
+    /// merged.
+    Skipped {
+        /// The record.
+        record: usize,
+        /// The version of the record.
+        version: Version,
+    },
+    /// The term is already present and is not added again.
+    Duplicate {
+        /// The record.
+        record: usize,
+        /// The index of the term.
+        index: usize,
+        /// The term.
+        term: Mechanism<String>,
+    },
+    /// The term differs from an earlier term only by its qualifier, or is a second `ptr` or
+    /// `exp`. The earlier term is kept.
+    Conflict {
+        /// The record.
+        record: usize,
+        /// The index of the term.
+        index: usize,
+        /// The term which is not merged.
+        term: Mechanism<String>,
+        /// The term which is kept.
+        kept: Mechanism<String>,
+    },
+    /// The term is never evaluated and is not merged. It follows `all`, or is a `redirect` in
+    /// a record with `all`.
+    Unreachable {
+        /// The record.
+        record: usize,
+        /// The index of the term.
+        index: usize,
+        /// The term.
+        term: Mechanism<String>,
+    },
+    /// The `redirect` is replaced by an `include` of the same domain.
+    RedirectToInclude {
+        /// The record.
+        record: usize,
+        /// The index of the `redirect`.
+        index: usize,
+        /// The `redirect`.
+        term: Mechanism<String>,
+    },
+    /// The `all` of the record is the strictest and ends the merged record.
+    All {
+        /// The record.
+        record: usize,
+        /// The index of `all`.
+        index: usize,
+        /// The qualifier of `all`.
+        qualifier: Qualifier,
+    },
+    /// Overlapping and adjacent networks were merged, removing the given number of terms.
+    NetworksMerged(usize),
+}
+
+impl Decision {
+    /// Returns `true` if the decision is a [`Conflict`](Decision::Conflict).
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Self::Conflict { .. })
+    }
+}
+
+impl Display for Decision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Decision::Skipped { record, version } => {
+                write!(f, "record {}: `{}` is skipped", record, version)
+            }
+            Decision::Duplicate { record, term, .. } => {
+                write!(f, "record {}: `{}` is already present", record, term)
+            }
+            Decision::Conflict {
+                record, term, kept, ..
+            } => write!(
+                f,
+                "record {}: `{}` conflicts with `{}`, which is kept",
+                record, term, kept
+            ),
+            Decision::Unreachable { record, term, .. } => {
+                write!(f, "record {}: `{}` is never evaluated", record, term)
+            }
+            Decision::RedirectToInclude { record, term, .. } => write!(
+                f,
+                "record {}: `{}` is replaced by `include:{}`",
+                record,
+                term,
+                term.raw()
+            ),
+            Decision::All {
+                record, qualifier, ..
+            } => write!(f, "record {}: `{}all` is kept", record, qualifier.as_str()),
+            Decision::NetworksMerged(removed) => {
+                write!(f, "merged networks, removing {} terms", removed)
+            }
+        }
+    }
+}
+
+/// The result of [`merge()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merged {
+    builder: SpfBuilder<Builder>,
+    decisions: Vec<Decision>,
+    report: ValidationReport,
+}
+
+impl Merged {
+    /// The merged record.
+    pub fn builder(&self) -> &SpfBuilder<Builder> {
+        &self.builder
+    }
+    /// Take the merged record.
+    pub fn into_builder(self) -> SpfBuilder<Builder> {
+        self.builder
+    }
+    /// Every choice made, in the order the records and their terms were merged. The choice of
+    /// `all` and the merging of networks are last.
+    pub fn decisions(&self) -> &[Decision] {
+        &self.decisions
+    }
+    /// The [`Conflict`](Decision::Conflict)s found.
+    pub fn conflicts(&self) -> impl Iterator<Item = &Decision> {
+        self.decisions.iter().filter(|d| d.is_conflict())
+    }
+    /// Returns `true` if any terms conflict.
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicts().next().is_some()
+    }
+    /// The validation of the merged record. See [SpfBuilder::validation_report()].
+    pub fn report(&self) -> &ValidationReport {
+        &self.report
+    }
+}
+
+// Terms are the same if they differ only by qualifier. Only one `ptr` and `exp` is kept.
+fn same_term(a: &Mechanism<String>, b: &Mechanism<String>) -> bool {
+    a.is_same_term(b) || (a.kind() == b.kind() && matches!(a.kind(), Kind::Ptr | Kind::Exp))
+}
+
+// The strictness of the result given by `all`.
+fn strictness(qualifier: Qualifier) -> u8 {
+    match qualifier {
+        Qualifier::Pass => 0,
+        Qualifier::Neutral => 1,
+        Qualifier::SoftFail => 2,
+        Qualifier::Fail => 3,
+    }
+}
+
+/// Merge `records` into a single record. See the [module](self) documentation.
+pub fn merge<'a, I>(records: I) -> Merged
+where
+    I: IntoIterator<Item = &'a Spf<String>>,
+{
+    let mut version: Option<&Version> = None;
+    let mut terms: Vec<Mechanism<String>> = Vec::new();
+    let mut all: Option<(usize, usize, Qualifier)> = None;
+    let mut decisions = Vec::new();
+    for (record, spf) in records.into_iter().enumerate() {
+        match version {
+            Some(v) if v != spf.version() => {
+                decisions.push(Decision::Skipped {
+                    record,
+                    version: spf.version().clone(),
+                });
+                continue;
+            }
+            Some(_) => {}
+            None => version = Some(spf.version()),
+        }
+        let has_all = spf.all().is_some();
+        let mut after_all = false;
+        for (index, term) in spf.iter().enumerate() {
+            let kind = term.kind();
+            if (after_all && !kind.is_modifier()) || (has_all && kind.is_redirect()) {
+                decisions.push(Decision::Unreachable {
+                    record,
+                    index,
+                    term: term.clone(),
+                });
+                continue;
+            }
+            if kind.is_all() {
+                after_all = true;
+                let qualifier = *term.qualifier();
+                match all {
+                    Some((_, _, q)) if strictness(q) >= strictness(qualifier) => {}
+                    _ => all = Some((record, index, qualifier)),
+                }
+                continue;
+            }
+            let term = match kind {
+                Kind::Redirect => match Mechanism::include(Qualifier::Pass, &term.raw()) {
+                    Ok(include) => {
+                        decisions.push(Decision::RedirectToInclude {
+                            record,
+                            index,
+                            term: term.clone(),
+                        });
+                        include
+                    }
+                    // Both take a domain-spec, so this does not happen.
+                    Err(_) => continue,
+                },
+                _ => term.clone(),
+            };
+            match terms.iter().find(|kept| same_term(kept, &term)) {
+                Some(kept) if kept.qualifier() == term.qualifier() && kept.is_same_term(&term) => {
+                    decisions.push(Decision::Duplicate {
+                        record,
+                        index,
+                        term,
+                    })
+                }
+                Some(kept) => decisions.push(Decision::Conflict {
+                    record,
+                    index,
+                    term,
+                    kept: kept.clone(),
+                }),
+                None => terms.push(term),
+            }
+        }
+    }
+    let mut builder = SpfBuilder::new_builder();
+    builder.set_version(version.cloned().unwrap_or_default());
+    for term in terms {
+        builder.append_mechanism(term);
+    }
+    if let Some((record, index, qualifier)) = all {
+        decisions.push(Decision::All {
+            record,
+            index,
+            qualifier,
+        });
+        builder.append_mechanism(Mechanism::<String>::new(Kind::All, qualifier));
+    }
+    let removed = builder.aggregate_networks();
+    if removed > 0 {
+        decisions.push(Decision::NetworksMerged(removed));
+    }
+    let report = builder.validation_report();
+    Merged {
+        builder,
+        decisions,
+        report,
+    }
+}
@@ -4,16 +4,32 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "builder")))]
 #[cfg(feature = "builder")]
 pub mod builder;
+pub mod diff;
 pub mod errors;
+pub mod lint;
 pub mod mechanism;
+#[cfg_attr(docsrs, doc(cfg(feature = "builder")))]
+#[cfg(feature = "builder")]
+pub mod merge;
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[cfg(feature = "serde")]
+pub mod output;
+pub mod size;
 mod string;
 #[cfg(test)]
 mod tests;
 mod validate;
+pub(crate) mod version;
+#[cfg_attr(docsrs, doc(cfg(feature = "zone")))]
+#[cfg(feature = "zone")]
+pub mod zone;
 
+use crate::prelude::*;
 pub use crate::spf::errors::SpfError;
+pub use crate::spf::validate::ValidationReport;
+pub use crate::spf::version::{Scope, Version};
+use core::fmt::{Debug, Display};
 pub use mechanism::Mechanism;
-use std::fmt::{Debug, Display};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -21,9 +37,13 @@ use serde::{Deserialize, Serialize};
 /// Base struct for an Spf of any type.
 #[derive(Debug, Default, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "T: Deserialize<'de>, Mechanism<T>: Deserialize<'de>"))
+)]
 pub struct Spf<T> {
     source: T,
-    version: T,
+    version: Version,
     redirect_idx: usize,
     has_redirect: bool,
     all_idx: usize,
@@ -32,7 +52,7 @@ pub struct Spf<T> {
 }
 
 pub struct SpfIterator<'a, T> {
-    mechanism_iter: std::slice::Iter<'a, Mechanism<T>>,
+    mechanism_iter: core::slice::Iter<'a, Mechanism<T>>,
 }
 
 impl<'a, T> Iterator for SpfIterator<'a, T> {
@@ -50,7 +70,15 @@ where
     T: Display,
 {
     /// Access the version attribute associated with the Spf record.
-    pub fn version(&self) -> &T {
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Scope, Spf};
+    /// let spf: Spf<String> = "spf2.0/pra a -all".parse().unwrap();
+    /// assert!(spf.version().has_scope(Scope::Pra));
+    /// assert_eq!(spf.version(), "spf2.0/pra");
+    /// ```
+    pub fn version(&self) -> &Version {
         &self.version
     }
     /// Access the number of DNS lookups required for this Spf record.
@@ -72,7 +100,7 @@ where
 
 impl<T> IntoIterator for Spf<T> {
     type Item = Mechanism<T>;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.mechanisms.into_iter()
@@ -0,0 +1,581 @@
+//! Serialize findings for other tools.
+//!
+//! [`Output`] collects the findings of one or more records, along with where each record was
+//! found when that is known. It can be serialized as
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html), which code
+//! scanning services display as annotations, or as JSON with a stable schema. Any serde
+//! serializer, such as `serde_json`, can be used.
+//!
+//! # Example:
+//! ```
+//! use decon_spf::lint::Linter;
+//! use decon_spf::output::{Location, Output};
+//! use decon_spf::Spf;
+//! let spf: Spf<String> = "v=spf1 redirect=_spf.example.com ?all".parse().unwrap();
+//! let linter = Linter::new();
+//! let mut output = Output::new();
+//! output.add_linter(&linter);
+//! output.add_findings(
+//!     &spf.to_string(),
+//!     spf.validation_report().iter().chain(&linter.check(&spf)),
+//!     Some(Location::new("zones/example.com.zone").with_line(4)),
+//! );
+//!
+//! let sarif = serde_json::to_value(output.sarif()).unwrap();
+//! assert_eq!(sarif["version"], "2.1.0");
+//! let result = &sarif["runs"][0]["results"][0];
+//! assert_eq!(result["ruleId"], "redirect-with-all");
+//! assert_eq!(result["level"], "warning");
+//! let location = &result["locations"][0]["physicalLocation"];
+//! assert_eq!(location["artifactLocation"]["uri"], "zones/example.com.zone");
+//! assert_eq!(location["region"]["startLine"], 4);
+//!
+//! let json = serde_json::to_value(output.json()).unwrap();
+//! assert_eq!(json["records"][0]["findings"][0]["severity"], "warning");
+//! // `redirect-with-all` is found by both and listed once, with `neutral-all`.
+//! assert_eq!(json["summary"]["warnings"], 2);
+//! ```
+use crate::lint::{Finding, Linter, Rule, Severity};
+use crate::prelude::*;
+use crate::spf::validate;
+use crate::ValidationReport;
+use serde::{Serialize, Serializer};
+
+#[cfg(feature = "zone")]
+use crate::zone::ZoneRecord;
+#[cfg(feature = "zone")]
+use crate::SpfError;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// Describes a record which could not be parsed.
+const INVALID_SPF: RuleInfo = RuleInfo {
+    id: "invalid-spf",
+    severity: Severity::Error,
+    description: "The record does not follow the syntax of RFC7208.",
+    reference: "https://datatracker.ietf.org/doc/html/rfc7208#section-12",
+};
+
+// A rule as it is described in the output.
+#[derive(Debug, Clone, Copy)]
+struct RuleInfo {
+    id: &'static str,
+    severity: Severity,
+    description: &'static str,
+    reference: &'static str,
+}
+
+impl RuleInfo {
+    fn new(rule: &dyn Rule, severity: Severity) -> Self {
+        Self {
+            id: rule.id(),
+            severity,
+            description: rule.description(),
+            reference: rule.reference(),
+        }
+    }
+}
+
+/// Where a record was found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Location {
+    uri: String,
+    line: Option<usize>,
+    owner: Option<String>,
+}
+
+impl Location {
+    /// A record found in the file at `uri`. A relative path is relative to the root of the
+    /// repository being scanned.
+    pub fn new(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            line: None,
+            owner: None,
+        }
+    }
+    /// A record found by a [`ZoneScanner`](crate::zone::ZoneScanner) in the zone file at `uri`.
+    #[cfg(feature = "zone")]
+    pub fn in_zone(uri: impl Into<String>, record: &ZoneRecord) -> Self {
+        Self::new(uri)
+            .with_line(record.line())
+            .with_owner(record.owner())
+    }
+    /// The line, starting at 1, on which the record starts.
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+    /// The domain which owns the record.
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+    /// The file in which the record was found.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+    /// The line on which the record starts.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+    /// The domain which owns the record.
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    source: String,
+    location: Option<Location>,
+    findings: Vec<Finding>,
+}
+
+/// The findings of one or more records, ready to be serialized.
+///
+/// Every rule run by [`Spf::validate()`](crate::Spf::validate) is described without further
+/// setup. The rules of a [`Linter`] are described once it is [added](Output::add_linter).
+#[derive(Debug, Clone)]
+pub struct Output {
+    entries: Vec<Entry>,
+    rules: Vec<RuleInfo>,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Output {
+    /// Create an empty output.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            rules: validate::CHECKS
+                .iter()
+                .map(|check| RuleInfo::new(check.rule, check.rule.default_severity()))
+                .chain([INVALID_SPF])
+                .collect(),
+        }
+    }
+    /// Describe the rules of `linter` with the severity it is configured to report.
+    pub fn add_linter(&mut self, linter: &Linter) -> &mut Self {
+        for rule in linter.rules() {
+            let severity = linter
+                .config()
+                .severity_of(rule)
+                .unwrap_or_else(|| rule.default_severity());
+            let info = RuleInfo::new(rule, severity);
+            match self.rules.iter_mut().find(|r| r.id == info.id) {
+                Some(r) => *r = info,
+                None => self.rules.push(info),
+            }
+        }
+        self
+    }
+    /// Add the findings of the record `source`. A record without findings is still listed.
+    ///
+    /// A finding given more than once is listed once, so the findings of a
+    /// [`ValidationReport`] and a [`Linter`], which run some of the same rules, can be combined.
+    pub fn add_findings<'a>(
+        &mut self,
+        source: &str,
+        findings: impl IntoIterator<Item = &'a Finding>,
+        location: Option<Location>,
+    ) -> &mut Self {
+        let mut unique: Vec<Finding> = Vec::new();
+        for finding in findings {
+            if !unique.contains(finding) {
+                unique.push(finding.clone());
+            }
+        }
+        self.entries.push(Entry {
+            source: source.to_string(),
+            location,
+            findings: unique,
+        });
+        self
+    }
+    /// Add the findings of a [`ValidationReport`].
+    pub fn add_report(
+        &mut self,
+        report: &ValidationReport,
+        location: Option<Location>,
+    ) -> &mut Self {
+        self.add_findings(report.source(), report, location)
+    }
+    /// Validate a record found in the zone file at `uri`. A record which could not be parsed is
+    /// given as `invalid-spf` or `invalid-version`, with an empty source.
+    #[cfg(feature = "zone")]
+    pub fn add_zone_record(&mut self, uri: &str, record: &ZoneRecord) -> &mut Self {
+        let location = Some(Location::in_zone(uri, record));
+        match record.spf() {
+            Ok(spf) => self.add_report(&spf.validation_report(), location),
+            Err(e) => self.add_findings("", &[parse_error(e)], location),
+        }
+    }
+    /// Iterate over every finding of every record.
+    pub fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.entries.iter().flat_map(|e| e.findings.iter())
+    }
+    /// The highest severity of any finding. `None` if there are no findings.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings().map(|f| f.severity()).max()
+    }
+    /// Serialize as a SARIF 2.1.0 log with a single run.
+    ///
+    /// Each finding is a `result` with a `ruleId` and `level`. `error`, `warning` and `note`
+    /// are used for [`Error`](Severity::Error), [`Warning`](Severity::Warning) and
+    /// [`Info`](Severity::Info). The record and the index of the term are given as the
+    /// `record` and `index` properties of the result. The rules which were reported are
+    /// described in `tool.driver.rules`.
+    pub fn sarif(&self) -> Sarif<'_> {
+        Sarif(self)
+    }
+    /// Serialize as JSON with a stable schema. Fields are never omitted, a missing value is
+    /// `null`.
+    ///
+    /// ```json
+    /// {
+    ///   "version": 1,
+    ///   "records": [
+    ///     {
+    ///       "source": "v=spf1 ptr -all",
+    ///       "location": { "uri": "example.com.zone", "line": 3, "owner": "example.com." },
+    ///       "findings": [
+    ///         {
+    ///           "rule": "deprecated-ptr",
+    ///           "severity": "warning",
+    ///           "index": 0,
+    ///           "message": "`ptr` should not be used",
+    ///           "reference": "https://datatracker.ietf.org/doc/html/rfc7208#section-5.5"
+    ///         }
+    ///       ]
+    ///     }
+    ///   ],
+    ///   "summary": { "errors": 0, "warnings": 1, "infos": 0 }
+    /// }
+    /// ```
+    /// `severity` is one of `error`, `warning` or `info`. `version` is only changed when a
+    /// field is removed or its meaning changes.
+    pub fn json(&self) -> Json<'_> {
+        Json(self)
+    }
+    fn rule(&self, finding: &Finding) -> RuleInfo {
+        match self.rules.iter().find(|r| r.id == finding.rule()) {
+            Some(rule) => *rule,
+            None => RuleInfo {
+                id: finding.rule(),
+                severity: finding.severity(),
+                description: "",
+                reference: finding.reference(),
+            },
+        }
+    }
+}
+
+// Describe `error`, which stopped a record from being parsed.
+#[cfg(feature = "zone")]
+fn parse_error(error: &SpfError) -> Finding {
+    let (rule, message) = match error {
+        SpfError::InvalidVersion => {
+            let rule = crate::lint::rules::InvalidVersion;
+            let info = RuleInfo::new(&rule, rule.default_severity());
+            let message = "the record does not start with `v=spf1` or `spf2.0/`";
+            (info, message.to_string())
+        }
+        e => (INVALID_SPF, e.to_string()),
+    };
+    Finding::new(
+        rule.id,
+        rule.severity,
+        error.index(),
+        message,
+        rule.reference,
+    )
+}
+
+/// An [`Output`] serialized as SARIF 2.1.0. See [`Output::sarif()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Sarif<'a>(&'a Output);
+
+impl Serialize for Sarif<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut rules: Vec<RuleInfo> = Vec::new();
+        let mut results = Vec::new();
+        for entry in &self.0.entries {
+            for finding in &entry.findings {
+                let rule_index = match rules.iter().position(|r| r.id == finding.rule()) {
+                    Some(idx) => idx,
+                    None => {
+                        rules.push(self.0.rule(finding));
+                        rules.len() - 1
+                    }
+                };
+                results.push(sarif::Result {
+                    rule_id: finding.rule(),
+                    rule_index,
+                    level: sarif::level(finding.severity()),
+                    message: sarif::Message {
+                        text: finding.message(),
+                    },
+                    locations: entry.location.iter().map(sarif::Location::from).collect(),
+                    properties: sarif::Properties {
+                        record: &entry.source,
+                        index: finding.index(),
+                    },
+                });
+            }
+        }
+        let rules = rules
+            .iter()
+            .map(|r| sarif::Rule {
+                id: r.id,
+                short_description: match r.description.is_empty() {
+                    true => None,
+                    false => Some(sarif::Message {
+                        text: r.description,
+                    }),
+                },
+                help_uri: r.reference,
+                default_configuration: sarif::Configuration {
+                    level: sarif::level(r.severity),
+                },
+            })
+            .collect();
+        sarif::Log {
+            schema: SARIF_SCHEMA,
+            version: "2.1.0",
+            runs: [sarif::Run {
+                tool: sarif::Tool {
+                    driver: sarif::Driver {
+                        name: env!("CARGO_PKG_NAME"),
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+        .serialize(serializer)
+    }
+}
+
+/// An [`Output`] serialized with a stable JSON schema. See [`Output::json()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Json<'a>(&'a Output);
+
+impl Serialize for Json<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let count = |severity| {
+            self.0
+                .findings()
+                .filter(|f| f.severity() == severity)
+                .count()
+        };
+        json::Log {
+            version: 1,
+            records: self
+                .0
+                .entries
+                .iter()
+                .map(|entry| json::Record {
+                    source: &entry.source,
+                    location: entry.location.as_ref(),
+                    findings: entry
+                        .findings
+                        .iter()
+                        .map(|f| json::Finding {
+                            rule: f.rule(),
+                            severity: f.severity().as_str(),
+                            index: f.index(),
+                            message: f.message(),
+                            reference: f.reference(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            summary: json::Summary {
+                errors: count(Severity::Error),
+                warnings: count(Severity::Warning),
+                infos: count(Severity::Info),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+// The subset of the SARIF 2.1.0 object model which is produced.
+mod sarif {
+    use crate::lint::Severity;
+    use crate::prelude::*;
+    use serde::Serialize;
+
+    pub(super) fn level(severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        }
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Log<'a> {
+        #[serde(rename = "$schema")]
+        pub(super) schema: &'static str,
+        pub(super) version: &'static str,
+        pub(super) runs: [Run<'a>; 1],
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Run<'a> {
+        pub(super) tool: Tool<'a>,
+        pub(super) results: Vec<Result<'a>>,
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Tool<'a> {
+        pub(super) driver: Driver<'a>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct Driver<'a> {
+        pub(super) name: &'static str,
+        pub(super) version: &'static str,
+        pub(super) information_uri: &'static str,
+        pub(super) rules: Vec<Rule<'a>>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct Rule<'a> {
+        pub(super) id: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub(super) short_description: Option<Message<'a>>,
+        pub(super) help_uri: &'a str,
+        pub(super) default_configuration: Configuration,
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Configuration {
+        pub(super) level: &'static str,
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Message<'a> {
+        pub(super) text: &'a str,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct Result<'a> {
+        pub(super) rule_id: &'a str,
+        pub(super) rule_index: usize,
+        pub(super) level: &'static str,
+        pub(super) message: Message<'a>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub(super) locations: Vec<Location<'a>>,
+        pub(super) properties: Properties<'a>,
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Properties<'a> {
+        pub(super) record: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub(super) index: Option<usize>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub(super) struct Location<'a> {
+        physical_location: PhysicalLocation<'a>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        logical_locations: Vec<LogicalLocation<'a>>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PhysicalLocation<'a> {
+        artifact_location: ArtifactLocation<'a>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        region: Option<Region>,
+    }
+
+    #[derive(Serialize)]
+    struct ArtifactLocation<'a> {
+        uri: &'a str,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Region {
+        start_line: usize,
+    }
+
+    #[derive(Serialize)]
+    struct LogicalLocation<'a> {
+        name: &'a str,
+        kind: &'static str,
+    }
+
+    impl<'a> From<&'a super::Location> for Location<'a> {
+        fn from(location: &'a super::Location) -> Self {
+            Self {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: location.uri(),
+                    },
+                    region: location.line().map(|start_line| Region { start_line }),
+                },
+                logical_locations: location
+                    .owner()
+                    .map(|name| LogicalLocation {
+                        name,
+                        kind: "resource",
+                    })
+                    .into_iter()
+                    .collect(),
+            }
+        }
+    }
+}
+
+// The stable JSON schema described by `Output::json()`.
+mod json {
+    use crate::prelude::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    pub(super) struct Log<'a> {
+        pub(super) version: u32,
+        pub(super) records: Vec<Record<'a>>,
+        pub(super) summary: Summary,
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Record<'a> {
+        pub(super) source: &'a str,
+        pub(super) location: Option<&'a super::Location>,
+        pub(super) findings: Vec<Finding<'a>>,
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Finding<'a> {
+        pub(super) rule: &'a str,
+        pub(super) severity: &'static str,
+        pub(super) index: Option<usize>,
+        pub(super) message: &'a str,
+        pub(super) reference: &'a str,
+    }
+
+    #[derive(Serialize)]
+    pub(super) struct Summary {
+        pub(super) errors: usize,
+        pub(super) warnings: usize,
+        pub(super) infos: usize,
+    }
+}
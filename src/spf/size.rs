@@ -0,0 +1,189 @@
+//! Estimate the size of a record on the wire.
+//!
+//! A TXT record is published as one or more *character-strings* of at most 255 octets, each
+//! preceded by a length octet. [RFC7208 Section 3.4](https://datatracker.ietf.org/doc/html/rfc7208#section-3.4)
+//! advises that the answer to a query for the record fits within 512 octets. A larger response
+//! is truncated and the query is retried over TCP, which some networks block. Any other TXT
+//! records of the domain are part of the same answer and must be taken into account.
+//!
+//! [`WireSize`] gives the size of the RDATA and an estimate of the response to a query for the
+//! TXT records of the owner, along with the headroom left before the response no longer fits
+//! in a UDP datagram.
+//!
+//! # Example:
+//! ```
+//! use decon_spf::Spf;
+//! let spf: Spf<String> = "v=spf1 include:_spf.example.net -all".parse().unwrap();
+//! let size = spf.wire_size("example.com");
+//! assert_eq!(size.record_len(), 36);
+//! assert_eq!(size.character_strings(), &[36]);
+//! assert_eq!(size.rdata_len(), 37);
+//! assert_eq!(size.response_len(), 78);
+//! assert_eq!(size.headroom(), 434);
+//! assert!(size.fits());
+//!
+//! // Other TXT records of the domain share the answer.
+//! let size = size.with_txt(&"google-site-verification=".repeat(20));
+//! assert_eq!(size.response_len(), 592);
+//! assert!(size.requires_tcp());
+//! // Unless EDNS allows a larger payload.
+//! assert!(size.with_udp_payload(1232).fits());
+//! ```
+use crate::core::txt;
+use crate::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// The largest UDP payload without EDNS. [RFC1035 Section 2.3.4](https://datatracker.ietf.org/doc/html/rfc1035#section-2.3.4)
+pub const UDP_PAYLOAD_LIMIT: usize = 512;
+// The DNS message header.
+const HEADER_LEN: usize = 12;
+// The type and class of the question.
+const QUESTION_LEN: usize = 4;
+// A compressed owner name, the type, class, TTL and RDLENGTH of an answer.
+const ANSWER_LEN: usize = 2 + 10;
+// The OPT pseudo-record of EDNS, with an empty name and no options.
+const OPT_LEN: usize = 11;
+
+/// The size of a record on the wire. See the [module](self) documentation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct WireSize {
+    record_len: usize,
+    character_strings: Vec<usize>,
+    owner_len: usize,
+    other_rdata: Vec<usize>,
+    udp_payload: Option<usize>,
+}
+
+impl WireSize {
+    /// The size of `record` when published at `owner`.
+    ///
+    /// `owner` is taken to be an absolute domain name, with or without the trailing dot. A name
+    /// relative to a zone origin, such as `@` or `mail`, must be qualified by the caller, as
+    /// `ZoneRecord::owner()` is when the zone has an origin.
+    pub fn new(record: &str, owner: &str) -> Self {
+        Self {
+            record_len: record.len(),
+            character_strings: txt::split(record).iter().map(|s| s.len()).collect(),
+            owner_len: name_len(owner),
+            other_rdata: Vec::new(),
+            udp_payload: None,
+        }
+    }
+    /// Include another TXT record of the owner in the response.
+    pub fn with_txt(mut self, record: &str) -> Self {
+        self.other_rdata.push(rdata_len(&txt::split(record)));
+        self
+    }
+    /// Assume the resolver uses EDNS and advertises `payload` octets. Resolvers are
+    /// commonly configured with 1232.
+    /// [RFC6891 Section 6.2.5](https://datatracker.ietf.org/doc/html/rfc6891#section-6.2.5)
+    pub fn with_udp_payload(mut self, payload: usize) -> Self {
+        self.udp_payload = Some(payload);
+        self
+    }
+    /// The length of the record text.
+    pub fn record_len(&self) -> usize {
+        self.record_len
+    }
+    /// The length of each *character-string* the record is split into.
+    pub fn character_strings(&self) -> &[usize] {
+        &self.character_strings
+    }
+    /// Returns `true` if the record does not fit in a single *character-string*.
+    pub fn is_split(&self) -> bool {
+        self.character_strings.len() > 1
+    }
+    /// The size of the TXT RDATA, including a length octet for each *character-string*.
+    pub fn rdata_len(&self) -> usize {
+        self.character_strings.iter().map(|len| len + 1).sum()
+    }
+    /// An estimate of the response to a query for the TXT records of the owner. The owner is
+    /// assumed to be compressed in every answer and no authority records are counted.
+    pub fn response_len(&self) -> usize {
+        let opt = match self.udp_payload {
+            Some(_) => OPT_LEN,
+            None => 0,
+        };
+        let answers: usize = core::iter::once(self.rdata_len())
+            .chain(self.other_rdata.iter().copied())
+            .map(|rdata| ANSWER_LEN + rdata)
+            .sum();
+        HEADER_LEN + self.owner_len + QUESTION_LEN + answers + opt
+    }
+    /// The largest response which fits in a UDP datagram.
+    pub fn payload_limit(&self) -> usize {
+        self.udp_payload.unwrap_or(UDP_PAYLOAD_LIMIT)
+    }
+    /// The octets left before the response no longer fits in a UDP datagram. Negative when
+    /// the response is too large.
+    pub fn headroom(&self) -> isize {
+        self.payload_limit() as isize - self.response_len() as isize
+    }
+    /// Returns `true` if the response fits in a UDP datagram.
+    pub fn fits(&self) -> bool {
+        self.headroom() >= 0
+    }
+    /// Returns `true` if the response is truncated, so the query must be retried over TCP.
+    pub fn requires_tcp(&self) -> bool {
+        !self.fits()
+    }
+}
+
+fn rdata_len(strings: &[&str]) -> usize {
+    strings.iter().map(|s| s.len() + 1).sum()
+}
+
+// The length of the absolute `name` in wire format.
+fn name_len(name: &str) -> usize {
+    let name = name.trim_end_matches('.');
+    match name {
+        "" => 1,
+        _ => name.split('.').map(|label| label.len() + 1).sum::<usize>() + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(name_len("."), 1);
+        assert_eq!(name_len("com"), 5);
+        assert_eq!(name_len("example.com"), 13);
+        assert_eq!(name_len("example.com."), 13);
+    }
+    #[test]
+    fn minimal() {
+        let size = WireSize::new("v=spf1 -all", "example.com");
+        assert_eq!(size.rdata_len(), 12);
+        assert_eq!(size.response_len(), 12 + 13 + 4 + 12 + 12);
+        assert!(!size.is_split());
+        let size = size.with_udp_payload(1232);
+        assert_eq!(size.response_len(), 64);
+        assert_eq!(size.headroom(), 1232 - 64);
+    }
+    #[test]
+    fn split_strings() {
+        let record = format!("v=spf1 {}-all", "include:_spf.example.com ".repeat(12));
+        let size = WireSize::new(&record, "example.com");
+        assert_eq!(size.record_len(), 311);
+        assert!(size.is_split());
+        assert_eq!(size.character_strings(), &[232, 79]);
+        assert_eq!(size.rdata_len(), 313);
+    }
+    #[test]
+    fn truncated() {
+        let record = format!("v=spf1 {}-all", "ip4:192.0.2.0/24 ".repeat(27));
+        let size = WireSize::new(&record, "example.com");
+        assert_eq!(size.record_len(), 470);
+        assert_eq!(size.response_len(), 12 + 13 + 4 + 12 + 472);
+        assert_eq!(size.headroom(), -1);
+        assert!(size.requires_tcp());
+        // Published at the root the owner takes a single octet.
+        assert!(WireSize::new(&record, ".").fits());
+    }
+}
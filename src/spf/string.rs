@@ -1,19 +1,23 @@
-use crate::mechanism::{Kind, Mechanism};
+use crate::core::parser;
+use crate::diff::Diff;
+use crate::lint::{Finding, Linter, Overlap, Record, Shadowed, Strength};
+use crate::mechanism::{Kind, Mechanism, ParsedMechanism, Qualifier};
+use crate::prelude::*;
+use crate::size::WireSize;
 use crate::spf::errors::SpfErrors;
-use crate::spf::validate::{self, check_whitespaces, Validate};
-use crate::{Spf, SpfError};
-use ipnetwork::IpNetwork;
-use std::convert::TryFrom;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use crate::spf::validate::{self, Validate, ValidationReport};
+use crate::{Spf, SpfError, Version};
+use core::convert::TryFrom;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 
 impl Display for Spf<String> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         if !&self.source.is_empty() {
             write!(f, "{}", self.source)
         } else {
             let mut spf_string = String::new();
-            spf_string.push_str(self.version().as_str());
+            spf_string.push_str(&self.version().to_string());
             for m in self.iter() {
                 spf_string.push_str(format!(" {}", m).as_str());
             }
@@ -32,47 +36,7 @@ impl Display for Spf<String> {
 impl FromStr for Spf<String> {
     type Err = SpfError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        validate::check_start_of_spf(s)?;
-        validate::check_spf_length(s)?;
-
-        // Index of Redirect Mechanism
-        let mut redirect_idx: usize = 0;
-        // Index of All Mechanism
-        let mut all_idx = 0;
-        let mut idx = 0;
-        let mut spf = Spf::default();
-        let mechanisms = s.split_whitespace();
-        for m in mechanisms {
-            if m.contains(crate::core::SPF1) {
-                spf.version = m.to_string();
-            } else if m.contains(crate::core::IP4) || m.contains(crate::core::IP6) {
-                let m_ip = m.parse::<Mechanism<IpNetwork>>()?;
-                spf.mechanisms.push(m_ip.into());
-            } else {
-                let m_str = m.parse::<Mechanism<String>>()?;
-                spf.lookup_count += Self::update_lookup_count(&m_str);
-                match *m_str.kind() {
-                    Kind::Redirect => {
-                        if !spf.has_redirect {
-                            spf.has_redirect = true;
-                            redirect_idx = idx;
-                        } else {
-                            return Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect));
-                        }
-                    }
-                    Kind::All => {
-                        all_idx = idx;
-                    }
-                    _ => {}
-                }
-                spf.mechanisms.push(m_str);
-                idx += 1;
-            }
-        }
-        spf.source = s.to_string();
-        spf.redirect_idx = redirect_idx;
-        spf.all_idx = all_idx;
-        Ok(spf)
+        Spf::parse_record(s, Err)
     }
 }
 
@@ -92,9 +56,219 @@ impl Spf<String> {
         s.parse::<Spf<String>>()
     }
 
+    /// Parse `s` without stopping at the first invalid term.
+    ///
+    /// Every term that can be parsed is kept, and every term that can not is reported in the
+    /// returned [SpfErrors]. This allows all the problems in a record to be reported at once.
+    /// The returned `Spf<String>` is partial when any errors have been found.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let (spf, errors) = Spf::parse_lenient("v=spf1 a:example.com mx:/ ip4:203.32.160.0/33 -all");
+    /// assert_eq!(spf.iter().count(), 2);
+    /// assert_eq!(spf.all().unwrap().to_string(), "-all");
+    /// assert_eq!(errors.errors().len(), 2);
+    /// assert_eq!(errors.source(), "v=spf1 a:example.com mx:/ ip4:203.32.160.0/33 -all");
+    ///
+    /// let (_, errors) = Spf::parse_lenient("v=spf1 a -all");
+    /// assert!(errors.is_empty());
+    /// ```
+    pub fn parse_lenient(s: &str) -> (Spf<String>, SpfErrors) {
+        let mut errors = SpfErrors::new();
+        let spf = Spf::parse_record(s, |e| {
+            errors.register_error(e);
+            Ok(())
+        })
+        .expect("Errors are collected and never returned.");
+        if !errors.is_empty() {
+            errors.register_source(s.to_string());
+        }
+        (spf, errors)
+    }
+
+    /// Creates a `Spf<String>` from the *character-strings* of a TXT record.
+    ///
+    /// TXT records longer than 255 octets are returned by DNS as several *character-strings*.
+    /// These are joined without adding spaces, as required by
+    /// [RFC7208 Section 3.3](https://datatracker.ietf.org/doc/html/rfc7208#section-3.3),
+    /// before the record is parsed.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let rdata: [&[u8]; 2] = [b"v=spf1 ip4:192.0.2.0/24 ", b"include:_spf.example.com -all"];
+    /// let spf = Spf::from_character_strings(&rdata).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.0/24 include:_spf.example.com -all");
+    /// ```
+    pub fn from_character_strings<S: AsRef<[u8]>>(strings: &[S]) -> Result<Spf<String>, SpfError> {
+        crate::core::txt::concatenate(strings)?.parse()
+    }
+
+    /// Creates a `Spf<String>` from a TXT record written in zone file presentation format.
+    ///
+    /// The record may be given as one or more quoted strings, as found in a zone file. Escaped
+    /// characters (`\"`, `\\` and `\DDD`) are accepted.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf = Spf::from_presentation_format(r#""v=spf1 ip4:192.0.2.0/24 " "-all""#).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.0/24 -all");
+    ///
+    /// let err = Spf::from_presentation_format(r#""v=spf1 -all"#).unwrap_err();
+    /// assert!(err.is_invalid_txt_rdata());
+    /// ```
+    pub fn from_presentation_format(s: &str) -> Result<Spf<String>, SpfError> {
+        Spf::from_character_strings(&crate::core::txt::parse_presentation(s)?)
+    }
+
+    /// Select and parse the Spf record from all the TXT records found for a domain.
+    ///
+    /// As described in [RFC7208 Section 4.5](https://datatracker.ietf.org/doc/html/rfc7208#section-4.5)
+    /// records which do not begin with the version section `v=spf1` are ignored. This includes
+    /// site verification and `spf2.0` records.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if there is no Spf record.
+    /// - `Ok(Some(spf))` if there is exactly one.
+    /// - [`MultipleSpfRecords`](SpfError::MultipleSpfRecords) listing each record if there are
+    ///   more than one.
+    /// - Any error found while parsing the selected record.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Spf, SpfError};
+    /// let rrset = [
+    ///     "google-site-verification=abc123",
+    ///     "v=spf1 include:_spf.example.com -all",
+    ///     "spf2.0/pra a -all",
+    /// ];
+    /// let spf = Spf::from_txt_rrset(rrset).unwrap().unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 include:_spf.example.com -all");
+    ///
+    /// assert_eq!(Spf::from_txt_rrset(["v=spf10 -all"]), Ok(None));
+    ///
+    /// let err = Spf::from_txt_rrset(["v=spf1 a -all", "v=spf1 mx -all"]).unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     SpfError::MultipleSpfRecords(vec!["v=spf1 a -all".to_string(), "v=spf1 mx -all".to_string()])
+    /// );
+    /// ```
+    pub fn from_txt_rrset<I, S>(records: I) -> Result<Option<Spf<String>>, SpfError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut spf_records: Vec<String> = records
+            .into_iter()
+            .filter(|r| {
+                parser::parse_version(parser::split_record(r.as_ref()).0) == Some(Version::V1)
+            })
+            .map(|r| r.as_ref().to_string())
+            .collect();
+        match spf_records.len() {
+            0 => Ok(None),
+            1 => spf_records.remove(0).parse().map(Some),
+            _ => Err(SpfError::MultipleSpfRecords(spf_records)),
+        }
+    }
+
+    /// Returns the record split into *character-strings* of no more than 255 octets.
+    ///
+    /// Strings are split after a space where possible so that each term is kept whole.
+    pub fn to_character_strings(&self) -> Vec<String> {
+        crate::core::txt::split(&self.to_string())
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns the size of the record on the wire when published at `owner`.
+    /// See [`size`](crate::size) for how the response is estimated.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf<String> = "v=spf1 a -all".parse().unwrap();
+    /// let size = spf.wire_size("example.com.");
+    /// assert_eq!(size.rdata_len(), 14);
+    /// assert!(size.fits());
+    /// ```
+    pub fn wire_size(&self, owner: &str) -> WireSize {
+        WireSize::new(&self.to_string(), owner)
+    }
+
+    /// Returns the record in presentation format, ready to be published in a zone file.
+    ///
+    /// Records longer than 255 octets are split into several quoted strings.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf<String> = "v=spf1 a -all".parse().unwrap();
+    /// assert_eq!(spf.to_presentation_format(), "\"v=spf1 a -all\"");
+    /// ```
+    pub fn to_presentation_format(&self) -> String {
+        crate::core::txt::to_presentation(&self.to_string())
+    }
+
+    // Parses the record handing every error to `on_error`. Parsing is halted if `on_error`
+    // returns the error, otherwise the offending term is skipped.
+    fn parse_record<F>(s: &str, mut on_error: F) -> Result<Spf<String>, SpfError>
+    where
+        F: FnMut(SpfError) -> Result<(), SpfError>,
+    {
+        let mut spf = Spf::default();
+        let (version, terms) = parser::split_record(s);
+        match version.parse() {
+            Ok(version) => spf.version = version,
+            // The invalid version string is not a term.
+            Err(e) => on_error(e)?,
+        }
+        if let Err(e) = validate::check_spf_length(s) {
+            on_error(e)?;
+        }
+
+        for (idx, term) in terms.enumerate() {
+            let m_str: Mechanism<String> = match parser::parse_term(term) {
+                #[cfg(feature = "std")]
+                Ok(ParsedMechanism::IP(m_ip)) => m_ip.into(),
+                Ok(ParsedMechanism::TXT(m_str)) => m_str,
+                Err(e) => {
+                    on_error(e.into())?;
+                    continue;
+                }
+            };
+            match *m_str.kind() {
+                Kind::Redirect if spf.has_redirect => {
+                    on_error(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, idx))?;
+                    continue;
+                }
+                Kind::Redirect => {
+                    spf.has_redirect = true;
+                    spf.redirect_idx = spf.mechanisms.len();
+                }
+                // Evaluation ends at the first `all`.
+                Kind::All if spf.all().is_none() => spf.all_idx = spf.mechanisms.len(),
+                _ => {}
+            }
+            spf.lookup_count = spf
+                .lookup_count
+                .saturating_add(m_str.kind().is_lookup() as u8);
+            spf.mechanisms.push(m_str);
+        }
+        spf.source = s.to_string();
+        Ok(spf)
+    }
+
     /// Check that version is v1
     pub fn is_v1(&self) -> bool {
-        self.version.contains(crate::core::SPF1)
+        self.version.is_v1()
+    }
+    /// Check that version is `spf2.0`
+    pub fn is_v2(&self) -> bool {
+        self.version.is_v2()
     }
     /// Check if the Spf record was created from [`crate::SpfBuilder<Builder>`] or from `&str`
     /// ```
@@ -108,34 +282,190 @@ impl Spf<String> {
     }
     /// Give access to the redirect modifier if present
     pub fn redirect(&self) -> Option<&Mechanism<String>> {
-        if self.redirect_idx == 0 {
-            match self
-                .mechanisms
-                .first()
-                .expect("There should be a Mechanism<>")
-                .kind()
-            {
-                Kind::Redirect => self.mechanisms.first(),
-                _ => None,
-            }
-        } else {
-            Some(&self.mechanisms[self.redirect_idx])
-        }
+        self.mechanisms
+            .get(self.redirect_idx)
+            .filter(|m| m.kind().is_redirect())
     }
     /// Give access to the `all` mechanism if it is present.
     pub fn all(&self) -> Option<&Mechanism<String>> {
-        if self.all_idx == 0 {
-            match self
-                .mechanisms
-                .first()
-                .expect("There should be a Mechanism<>")
-                .kind()
-            {
-                Kind::All => self.mechanisms.first(),
-                _ => None,
-            }
-        } else {
-            Some(&self.mechanisms[self.all_idx])
+        self.mechanisms
+            .get(self.all_idx)
+            .filter(|m| m.kind().is_all())
+    }
+
+    /// Insert a Mechanism or Modifier so that it is found at `index`. An `index` equal to the
+    /// number of terms appends it.
+    ///
+    /// Unlike [SpfBuilder](crate::SpfBuilder), the order of the terms is kept as given. After
+    /// any edit the source of a parsed record is regenerated from its terms. Spacing and letter
+    /// case which differ from the canonical form of a term are not kept.
+    ///
+    /// # Errors:
+    /// - [IndexOutOfRange](SpfError::IndexOutOfRange) if `index` is greater than the number
+    ///   of terms.
+    /// - [ModifierMayOccurOnlyOnce](SpfError::ModifierMayOccurOnlyOnce) if a `redirect` is
+    ///   inserted and one is already present.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::{Mechanism, Qualifier};
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf<String> = "v=spf1 mx  -all".parse().unwrap();
+    /// spf.insert(1, Mechanism::include(Qualifier::Pass, "_spf.example.com").unwrap())
+    ///     .unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 mx include:_spf.example.com -all");
+    /// assert_eq!(spf.lookup_count(), 2);
+    /// assert_eq!(spf.all().unwrap().to_string(), "-all");
+    /// ```
+    pub fn insert<T>(
+        &mut self,
+        index: usize,
+        mechanism: Mechanism<T>,
+    ) -> Result<&mut Self, SpfError>
+    where
+        Mechanism<T>: Into<Mechanism<String>>,
+    {
+        if index > self.mechanisms.len() {
+            return Err(SpfError::IndexOutOfRange(index));
+        }
+        let mechanism = mechanism.into();
+        self.check_redirect(&mechanism, None, index)?;
+        self.mechanisms.insert(index, mechanism);
+        self.update();
+        Ok(self)
+    }
+    /// Remove and return the term at `index`.
+    ///
+    /// # Errors:
+    /// - [IndexOutOfRange](SpfError::IndexOutOfRange) if there is no term at `index`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf<String> = "v=spf1 a redirect=_spf.example.com".parse().unwrap();
+    /// let redirect = spf.remove(1).unwrap();
+    /// assert_eq!(redirect.to_string(), "redirect=_spf.example.com");
+    /// assert_eq!(spf.redirect(), None);
+    /// assert_eq!(spf.to_string(), "v=spf1 a");
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Result<Mechanism<String>, SpfError> {
+        if index >= self.mechanisms.len() {
+            return Err(SpfError::IndexOutOfRange(index));
+        }
+        let removed = self.mechanisms.remove(index);
+        self.update();
+        Ok(removed)
+    }
+    /// Replace the term at `index` with `mechanism` and return the term it replaced.
+    ///
+    /// # Errors:
+    /// - [IndexOutOfRange](SpfError::IndexOutOfRange) if there is no term at `index`.
+    /// - [ModifierMayOccurOnlyOnce](SpfError::ModifierMayOccurOnlyOnce) if a `redirect` is
+    ///   given and another term is a `redirect`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Mechanism;
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf<String> = "v=spf1 mx -all".parse().unwrap();
+    /// let a: Mechanism<String> = "a:mail.example.com".parse().unwrap();
+    /// let old = spf.replace(0, a).unwrap();
+    /// assert_eq!(old.to_string(), "mx");
+    /// assert_eq!(spf.to_string(), "v=spf1 a:mail.example.com -all");
+    /// ```
+    pub fn replace<T>(
+        &mut self,
+        index: usize,
+        mechanism: Mechanism<T>,
+    ) -> Result<Mechanism<String>, SpfError>
+    where
+        Mechanism<T>: Into<Mechanism<String>>,
+    {
+        if index >= self.mechanisms.len() {
+            return Err(SpfError::IndexOutOfRange(index));
+        }
+        let mechanism = mechanism.into();
+        self.check_redirect(&mechanism, Some(index), index)?;
+        let replaced = core::mem::replace(&mut self.mechanisms[index], mechanism);
+        self.update();
+        Ok(replaced)
+    }
+    /// Move the term at `from` so that it is found at `to`.
+    ///
+    /// # Errors:
+    /// - [IndexOutOfRange](SpfError::IndexOutOfRange) if either index is not that of a term.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf<String> = "v=spf1 redirect=_spf.example.com a".parse().unwrap();
+    /// spf.move_term(0, 1).unwrap();
+    /// assert_eq!(spf.to_string(), "v=spf1 a redirect=_spf.example.com");
+    /// assert!(spf.validate().is_ok());
+    /// ```
+    pub fn move_term(&mut self, from: usize, to: usize) -> Result<&mut Self, SpfError> {
+        let len = self.mechanisms.len();
+        if let Some(index) = [from, to].iter().copied().find(|&idx| idx >= len) {
+            return Err(SpfError::IndexOutOfRange(index));
+        }
+        let term = self.mechanisms.remove(from);
+        self.mechanisms.insert(to, term);
+        self.update();
+        Ok(self)
+    }
+    /// Set the qualifier of the `all` mechanism. Returns `false`, leaving the record unchanged,
+    /// if there is no `all`.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::mechanism::Qualifier;
+    /// use decon_spf::Spf;
+    /// let mut spf: Spf<String> = "v=spf1 mx ~all".parse().unwrap();
+    /// assert!(spf.set_all_qualifier(Qualifier::Fail));
+    /// assert_eq!(spf.to_string(), "v=spf1 mx -all");
+    ///
+    /// let mut spf: Spf<String> = "v=spf1 redirect=_spf.example.com".parse().unwrap();
+    /// assert!(!spf.set_all_qualifier(Qualifier::Fail));
+    /// ```
+    pub fn set_all_qualifier(&mut self, qualifier: Qualifier) -> bool {
+        if self.all().is_none() {
+            return false;
+        }
+        self.mechanisms[self.all_idx] = Mechanism::new(Kind::All, qualifier);
+        self.update();
+        true
+    }
+    // A record may only hold one `redirect`, as when it is parsed. `replacing` is the index of
+    // a term which is about to be replaced.
+    fn check_redirect(
+        &self,
+        mechanism: &Mechanism<String>,
+        replacing: Option<usize>,
+        index: usize,
+    ) -> Result<(), SpfError> {
+        let other_redirect = self
+            .mechanisms
+            .iter()
+            .enumerate()
+            .any(|(idx, m)| m.kind().is_redirect() && Some(idx) != replacing);
+        match mechanism.kind().is_redirect() && other_redirect {
+            true => Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, index)),
+            false => Ok(()),
+        }
+    }
+    // Bring the indices, lookup count and source up to date after the terms have been edited.
+    fn update(&mut self) {
+        let terms = &self.mechanisms;
+        let redirect = terms.iter().position(|m| m.kind().is_redirect());
+        let all = terms.iter().position(|m| m.kind().is_all());
+        let lookup_count = terms.iter().filter(|m| m.kind().is_lookup()).count();
+        self.has_redirect = redirect.is_some();
+        self.redirect_idx = redirect.unwrap_or_default();
+        self.all_idx = all.unwrap_or_default();
+        self.lookup_count = u8::try_from(lookup_count).unwrap_or(u8::MAX);
+        if !self.built() {
+            self.source = String::new();
+            self.source = self.to_string();
         }
     }
 
@@ -165,47 +495,110 @@ impl Spf<String> {
     ///     - [Lookup Count Exceeded](SpfError::LookupLimitExceeded)
     ///     - [Redirect & All](SpfError::RedirectWithAllMechanism)
     ///     - [Redirect Position](SpfError::RedirectNotFinalMechanism)
+    ///     - [Modifier Position](SpfError::ModifierNotFinal)
+    ///     - [Repeated Modifier](SpfError::ModifierMayOccurOnlyOnce)
+    ///     - [White space](SpfError::WhiteSpaceSyntaxError)
+    ///
+    /// The same checks are made by `SpfBuilder::validate()`, so a record gets the same verdict
+    /// however it was created. [validation_report()](Self::validation_report) gives the
+    /// severity, rule id and term index of each problem.
     pub fn validate(&self) -> Result<(), SpfErrors> {
-        let mut errors = SpfErrors::new();
-
-        // Handle hard errors that stop further validation
-        for check in [self.validate_version(), self.validate_length()] {
-            if let Err(e) = check {
-                errors.register_source(self.source.clone());
-                errors.register_error(e);
-                return Err(errors);
-            }
-        }
-
-        // Handle soft errors that allow continued validation
-        let soft_checks = [
-            self.validate_ptr(),
-            self.validate_lookup_count(),
-            self.validate_redirect_all(),
-            // todo: Consider changing this to be part of Trait Validate??
-            check_whitespaces(&self.source),
-        ];
-
-        for check in soft_checks {
-            if let Err(e) = check {
-                errors.register_error(e);
-            }
-        }
-        // Return errors if any occurred
-        if errors.errors().is_empty() {
-            Ok(())
-        } else {
-            errors.register_source(self.source.clone());
-            Err(errors)
-        }
+        self.validate_all()
+    }
+    /// Validate the record and describe each problem found with a [`Finding`].
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf<String> = "v=spf1 redirect=_spf.example.com a -all".parse().unwrap();
+    /// let report = spf.validation_report();
+    /// assert!(!report.has_errors());
+    /// let warnings: Vec<_> = report.warnings().map(|f| (f.rule(), f.index())).collect();
+    /// assert_eq!(warnings, [("redirect-with-all", Some(0))]);
+    /// assert_eq!(
+    ///     report.findings()[0].reference(),
+    ///     "https://datatracker.ietf.org/doc/html/rfc7208#section-5.1"
+    /// );
+    /// ```
+    pub fn validation_report(&self) -> ValidationReport {
+        self.validate_report()
     }
 
-    // If the Mechanism will cause a DNS Lookup 1 should be added to the `lookup_count`. Otherwise 0
-    fn update_lookup_count(m_str: &Mechanism<String>) -> u8 {
-        match *m_str.kind() {
-            Kind::Redirect | Kind::A | Kind::MX | Kind::Include | Kind::Ptr | Kind::Exists => 1,
-            _ => 0,
-        }
+    /// Run the [built-in lint rules](crate::lint::rules) with their defaults.
+    ///
+    /// Use a [`Linter`](crate::lint::Linter) to configure the rules or add your own.
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf<String> = "v=spf1 redirect=_spf.example.com -all".parse().unwrap();
+    /// let findings = spf.lint();
+    /// assert_eq!(findings.len(), 1);
+    /// assert_eq!(findings[0].rule(), "redirect-with-all");
+    /// assert_eq!(findings[0].index(), Some(0));
+    /// ```
+    pub fn lint(&self) -> Vec<Finding> {
+        Linter::new().check(self)
+    }
+    /// Find the terms which can never influence the result and the earlier term which shadows
+    /// each of them. See [`shadowed_terms`](crate::lint::shadowed_terms).
+    /// # Example:
+    /// ```
+    /// use decon_spf::lint::Shadow;
+    /// use decon_spf::Spf;
+    /// let spf: Spf<String> = "v=spf1 ip4:192.0.2.0/24 ip4:192.0.2.10 -all".parse().unwrap();
+    /// let shadowed = spf.shadowed_terms();
+    /// assert_eq!(shadowed[0].index(), 1);
+    /// assert_eq!(shadowed[0].shadowed_by(), 0);
+    /// assert_eq!(shadowed[0].reason(), Shadow::CoveredNetwork);
+    /// ```
+    pub fn shadowed_terms(&self) -> Vec<Shadowed> {
+        crate::lint::shadowed_terms(&Record::from(self))
+    }
+    /// Find the pairs of `ip4` or `ip6` terms with the same qualifier which overlap or are
+    /// adjacent. See [`overlapping_networks`](crate::lint::overlapping_networks).
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let spf: Spf<String> = "v=spf1 ip4:10.0.0.0/24 ip4:10.0.0.0/23 -all".parse().unwrap();
+    /// let overlaps = spf.overlapping_networks();
+    /// assert_eq!(overlaps[0].index(), 1);
+    /// assert_eq!(overlaps[0].combined(), Some("10.0.0.0/23"));
+    /// ```
+    pub fn overlapping_networks(&self) -> Vec<Overlap> {
+        crate::lint::overlapping_networks(&Record::from(self))
+    }
+    /// Assess how well the record protects the domain. See [`strength`](crate::lint::strength).
+    /// # Example:
+    /// ```
+    /// use decon_spf::lint::Grade;
+    /// use decon_spf::Spf;
+    /// let spf: Spf<String> = "v=spf1 mx -all".parse().unwrap();
+    /// assert_eq!(spf.strength().grade(), Grade::Strict);
+    /// let spf: Spf<String> = "v=spf1 mx ?all".parse().unwrap();
+    /// assert_eq!(spf.strength().grade(), Grade::Weak);
+    /// let spf: Spf<String> = "v=spf1 ip4:0.0.0.0/0 -all".parse().unwrap();
+    /// assert_eq!(spf.strength().score(), 0);
+    /// assert_eq!(spf.strength().grade(), Grade::Open);
+    /// ```
+    pub fn strength(&self) -> Strength {
+        crate::lint::strength(&Record::from(self))
+    }
+    /// Compare this record with `other`, taken to be its replacement. See [`diff`](crate::diff).
+    /// # Example:
+    /// ```
+    /// use decon_spf::Spf;
+    /// let old: Spf<String> = "v=spf1 -ip4:192.0.2.1 ip4:192.0.2.0/24 -all".parse().unwrap();
+    /// let new: Spf<String> = "v=spf1 ip4:192.0.2.0/24 -ip4:192.0.2.1 -all".parse().unwrap();
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.changes().len(), 1);
+    /// assert_eq!(diff.added_coverage(), ["192.0.2.1"]);
+    /// // Terms which cannot match the same host may be reordered freely.
+    /// let new: Spf<String> = "v=spf1 ip4:192.0.2.0/24 -ip4:198.51.100.1 -all".parse().unwrap();
+    /// let old: Spf<String> = "v=spf1 -ip4:198.51.100.1 ip4:192.0.2.0/24 -all".parse().unwrap();
+    /// assert!(old.diff(&new).is_empty());
+    /// ```
+    pub fn diff(&self, other: &Spf<String>) -> Diff {
+        crate::spf::diff::diff(self, other)
     }
 }
 
@@ -220,7 +613,7 @@ mod tests {
         let spf = "v=spf1 -all".parse::<Spf<String>>().unwrap();
         assert!(!spf.source.is_empty());
         assert_eq!(spf.redirect(), None);
-        assert_eq!(spf.has_redirect, false);
+        assert!(!spf.has_redirect);
         assert_eq!(spf.all_idx, 0);
         assert_eq!(spf.lookup_count(), 0);
         assert_eq!(spf.all().unwrap().to_string(), "-all");
@@ -233,7 +626,7 @@ mod tests {
         let spf = "v=spf1 ptr -all".parse::<Spf<String>>().unwrap();
         assert!(!spf.source.is_empty());
         assert_eq!(spf.redirect(), None);
-        assert_eq!(spf.has_redirect, false);
+        assert!(!spf.has_redirect);
         assert_eq!(spf.all_idx, 1);
         let validation_result = spf.validate();
         assert!(validation_result.is_ok());
@@ -244,7 +637,7 @@ mod tests {
         let spf = "v=spf1 ptr -all".parse::<Spf<String>>().unwrap();
         assert!(!spf.source.is_empty());
         assert_eq!(spf.redirect(), None);
-        assert_eq!(spf.has_redirect, false);
+        assert!(!spf.has_redirect);
         assert_eq!(spf.all_idx, 1);
         let validation_result_vec = spf.validate();
         assert!(validation_result_vec.is_err());
@@ -261,7 +654,7 @@ mod tests {
             let spf = "v=spf1 redirect=_spf.example.com redirect=_spf.example.com"
                 .parse::<Spf<String>>()
                 .unwrap_err();
-            assert_eq!(spf, SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect));
+            assert_eq!(spf, SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 1));
         }
     }
     mod soft_errors {
@@ -0,0 +1,60 @@
+use crate::{Builder, Spf, SpfBuilder};
+
+fn aggregated(s: &str) -> (usize, String) {
+    let spf: Spf<String> = s.parse().unwrap();
+    let mut builder = SpfBuilder::<Builder>::from(spf);
+    let removed = builder.aggregate_networks();
+    (removed, builder.to_string())
+}
+
+#[test]
+fn overlapping() {
+    assert_eq!(
+        aggregated("v=spf1 ip4:10.0.0.0/24 ip4:10.0.0.0/23 -all"),
+        (1, "v=spf1 ip4:10.0.0.0/23 -all".to_string())
+    );
+}
+#[test]
+fn adjacent() {
+    assert_eq!(
+        aggregated("v=spf1 ip4:192.0.2.0/25 a ip4:192.0.2.128/25 ip4:192.0.2.5 ~all"),
+        (2, "v=spf1 ip4:192.0.2.0/24 a ~all".to_string())
+    );
+    assert_eq!(
+        aggregated("v=spf1 ip6:2001:db8::/48 ip6:2001:db8:1::/48 -all"),
+        (1, "v=spf1 ip6:2001:db8::/47 -all".to_string())
+    );
+}
+#[test]
+fn host_bits_are_cleared() {
+    assert_eq!(
+        aggregated("v=spf1 ip4:10.0.0.1/24 ip4:10.0.1.0/24 -all"),
+        (1, "v=spf1 ip4:10.0.0.0/23 -all".to_string())
+    );
+}
+#[test]
+fn unchanged() {
+    for record in [
+        "v=spf1 ip4:10.0.0.0/24 ip4:10.0.2.0/24 -all",
+        "v=spf1 ip4:10.0.0.0/24 ~ip4:10.0.1.0/24 -all",
+        "v=spf1 ip4:10.0.0.0/24 ?a ip4:10.0.1.0/24 -all",
+        "v=spf1 ip4:10.0.0.0/24 -all ip4:10.0.1.0/24",
+        // Kept with its host bits as there is nothing to merge.
+        "v=spf1 ip4:10.0.0.1/24 -all",
+    ] {
+        assert_eq!(aggregated(record), (0, record.to_string()));
+    }
+}
+#[test]
+fn result_builds() {
+    let spf: Spf<String> = "v=spf1 ip4:10.0.0.0/24 ip4:10.0.1.0/24 redirect=_spf.example.com"
+        .parse()
+        .unwrap();
+    let mut builder = SpfBuilder::<Builder>::from(spf);
+    builder.aggregate_networks();
+    let spf = builder.build().unwrap();
+    assert_eq!(
+        spf.to_string(),
+        "v=spf1 ip4:10.0.0.0/23 redirect=_spf.example.com"
+    );
+}
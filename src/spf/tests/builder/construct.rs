@@ -8,6 +8,19 @@ mod spf1 {
         spf.set_v1();
         assert_eq!(spf.version(), "v=spf1");
     }
+    #[test]
+    fn default_v1() {
+        let spf: SpfBuilder<Builder> = SpfBuilder::new();
+        assert!(spf.version().is_v1());
+        assert_eq!(spf.build().unwrap().to_string(), "v=spf1");
+    }
+    #[test]
+    fn set_version() {
+        let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+        spf.set_version("spf2.0/mfrom".parse().unwrap());
+        assert!(spf.version().has_scope(crate::Scope::MFrom));
+        assert!(!spf.version().has_scope(crate::Scope::Pra));
+    }
 }
 
 #[cfg(feature = "spf2")]
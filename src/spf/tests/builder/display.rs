@@ -22,7 +22,7 @@ fn include_x2() {
     spf.set_v1()
         .append_mechanism(Mechanism::include(Qualifier::Pass, "test.com").unwrap())
         .append_mechanism(Mechanism::include(Qualifier::Pass, "example.com").unwrap());
-    let spf = spf.add_all(Mechanism::all().into());
+    let spf = spf.add_all(Mechanism::all());
     assert_eq!(spf.includes().unwrap().len(), 2);
     assert_eq!(spf.to_string(), input);
 }
@@ -48,6 +48,6 @@ fn ip4_x3() {
             .parse::<Mechanism<IpNetwork>>()
             .unwrap(),
     );
-    let spf = spf.add_all(Mechanism::all().into());
+    let spf = spf.add_all(Mechanism::all());
     assert_eq!(spf.to_string(), input);
 }
@@ -0,0 +1,111 @@
+use crate::mechanism::{Kind, Mechanism, Qualifier};
+use crate::{Builder, Spf, SpfBuilder, SpfError};
+
+fn builder(s: &str) -> SpfBuilder<Builder> {
+    s.parse::<Spf<String>>().unwrap().into()
+}
+
+#[test]
+fn remove() {
+    let mut spf =
+        builder("v=spf1 include:_spf.esp1.com -include:_spf.esp1.com ip4:192.0.2.0/24 -all");
+    assert!(spf.remove_mechanism(Mechanism::include(Qualifier::Fail, "_spf.esp1.com").unwrap()));
+    assert_eq!(
+        spf.to_string(),
+        "v=spf1 include:_spf.esp1.com ip4:192.0.2.0/24 -all"
+    );
+    assert!(spf.remove_mechanism(Mechanism::ip_from_string("ip4:192.0.2.0/24").unwrap()));
+    assert!(!spf.remove_mechanism(Mechanism::ip_from_string("ip4:192.0.2.0/24").unwrap()));
+    assert!(spf.remove_mechanism(Mechanism::all()));
+    assert_eq!(spf.to_string(), "v=spf1 include:_spf.esp1.com");
+}
+#[test]
+fn replace() {
+    let mut spf = builder("v=spf1 a mx include:_spf.example.com -all");
+    assert!(spf
+        .replace_mechanism(
+            Mechanism::a(Qualifier::Pass),
+            Mechanism::a(Qualifier::SoftFail)
+        )
+        .unwrap());
+    assert_eq!(
+        spf.to_string(),
+        "v=spf1 ~a mx include:_spf.example.com -all"
+    );
+    assert!(!spf
+        .replace_mechanism(
+            Mechanism::a(Qualifier::Pass),
+            Mechanism::ptr(Qualifier::Pass)
+        )
+        .unwrap());
+    // `all` may replace itself.
+    assert!(spf
+        .replace_mechanism(
+            Mechanism::all(),
+            Mechanism::all_with_qualifier(Qualifier::SoftFail)
+        )
+        .unwrap());
+    assert_eq!(
+        spf.to_string(),
+        "v=spf1 ~a mx include:_spf.example.com ~all"
+    );
+    assert_eq!(
+        spf.replace_mechanism(Mechanism::mx(Qualifier::Pass), Mechanism::all()),
+        Err(SpfError::ModifierMayOccurOnlyOnce(Kind::All, 1))
+    );
+}
+#[test]
+fn replace_with_duplicate() {
+    let mut spf = builder("v=spf1 a mx ip4:192.0.2.1 -all");
+    let ip = Mechanism::ip_from_string("ip4:192.0.2.1").unwrap();
+    assert!(spf
+        .replace_mechanism(Mechanism::a(Qualifier::Pass), ip)
+        .unwrap());
+    assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.1 mx -all");
+    let mut spf = builder("v=spf1 a mx -all");
+    assert!(spf
+        .replace_mechanism(
+            Mechanism::mx(Qualifier::Pass),
+            Mechanism::a(Qualifier::Pass)
+        )
+        .unwrap());
+    assert_eq!(spf.to_string(), "v=spf1 a -all");
+}
+#[test]
+fn retain() {
+    let mut spf =
+        builder("v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 mx -all exp=explain.example.com");
+    assert!(spf.retain_mechanisms(|m| !m.kind().is_modifier()));
+    assert!(spf.retain_mechanisms(|m| *m.kind() != Kind::IpV6));
+    assert_eq!(spf.to_string(), "v=spf1 ip4:192.0.2.0/24 mx -all");
+    assert!(!spf.retain_mechanisms(|_| true));
+}
+#[test]
+fn requalify() {
+    let mut spf = builder("v=spf1 ?a:mail.example.com mx ~all redirect=_spf.example.com");
+    assert!(spf.set_qualifier(
+        Mechanism::a(Qualifier::Pass)
+            .with_rrdata("mail.example.com")
+            .unwrap(),
+        Qualifier::Pass
+    ));
+    assert!(!spf.set_qualifier(Mechanism::a(Qualifier::Pass), Qualifier::Fail));
+    assert!(spf.set_qualifier(Mechanism::all(), Qualifier::Fail));
+    assert!(!spf.set_qualifier(
+        Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap(),
+        Qualifier::Fail
+    ));
+    assert_eq!(
+        spf.to_string(),
+        "v=spf1 a:mail.example.com mx -all redirect=_spf.example.com"
+    );
+}
+#[test]
+fn requalify_duplicate() {
+    let mut spf = builder("v=spf1 mx -mx -all");
+    assert!(spf.set_qualifier(Mechanism::mx(Qualifier::Pass), Qualifier::Pass));
+    assert_eq!(spf.to_string(), "v=spf1 mx -all");
+    let mut spf = builder("v=spf1 -mx ~mx -all");
+    assert!(spf.set_qualifier(Mechanism::mx(Qualifier::Pass), Qualifier::Fail));
+    assert_eq!(spf.to_string(), "v=spf1 -mx -all");
+}
@@ -0,0 +1,162 @@
+use crate::mechanism::Qualifier;
+use crate::merge::{merge, Decision, Merged};
+use crate::Spf;
+
+fn merged(records: &[&str]) -> Merged {
+    let records: Vec<Spf<String>> = records.iter().map(|r| r.parse().unwrap()).collect();
+    merge(&records)
+}
+
+#[test]
+fn union() {
+    let merged = merged(&[
+        "v=spf1 a include:_spf.example.com -all",
+        "v=spf1 include:_spf.example.com include:_spf.example.net ip6:2001:db8::/32 -all",
+    ]);
+    assert_eq!(
+        merged.builder().to_string(),
+        "v=spf1 a include:_spf.example.com include:_spf.example.net ip6:2001:db8::/32 -all"
+    );
+    assert!(!merged.has_conflicts());
+    assert!(matches!(
+        merged.decisions()[0],
+        Decision::Duplicate {
+            record: 1,
+            index: 0,
+            ..
+        }
+    ));
+    assert_eq!(
+        merged.decisions()[1],
+        Decision::All {
+            record: 0,
+            index: 2,
+            qualifier: Qualifier::Fail
+        }
+    );
+    assert_eq!(merged.decisions().len(), 2);
+}
+#[test]
+fn strictest_all() {
+    let merged1 = merged(&["v=spf1 a ?all", "v=spf1 mx ~all", "v=spf1 ptr +all"]);
+    assert!(merged1.builder().to_string().ends_with(" ~all"));
+    assert_eq!(
+        merged1.decisions().last().unwrap().to_string(),
+        "record 1: `~all` is kept"
+    );
+    let merged2 = merged(&["v=spf1 a", "v=spf1 mx"]);
+    assert_eq!(merged2.builder().to_string(), "v=spf1 a mx");
+}
+#[test]
+fn conflicts() {
+    let merged = merged(&[
+        "v=spf1 -include:bad.example.com ptr exp=explain.example.com -all",
+        "v=spf1 include:bad.example.com ptr:example.com -all exp=other.example.com",
+    ]);
+    assert_eq!(
+        merged.builder().to_string(),
+        "v=spf1 -include:bad.example.com ptr -all exp=explain.example.com"
+    );
+    let conflicts: Vec<_> = merged.conflicts().map(|d| d.to_string()).collect();
+    assert_eq!(
+        conflicts,
+        [
+            "record 1: `include:bad.example.com` conflicts with `-include:bad.example.com`, which is kept",
+            "record 1: `ptr:example.com` conflicts with `ptr`, which is kept",
+            "record 1: `exp=other.example.com` conflicts with `exp=explain.example.com`, which is kept",
+        ]
+    );
+}
+#[test]
+fn letter_case() {
+    let merged = merged(&[
+        "v=spf1 include:_SPF.example.com -all",
+        "v=spf1 include:_spf.example.com ~include:%{i}._spf.example.com -all",
+        "v=spf1 -include:_spf.EXAMPLE.com include:%{I}._spf.example.com -all",
+    ]);
+    assert_eq!(
+        merged.builder().to_string(),
+        "v=spf1 include:_SPF.example.com ~include:%{i}._spf.example.com include:%{I}._spf.example.com -all"
+    );
+    assert!(matches!(
+        merged.decisions()[0],
+        Decision::Duplicate {
+            record: 1,
+            index: 0,
+            ..
+        }
+    ));
+    // The case of a macro letter is significant.
+    assert_eq!(
+        merged.conflicts().map(|d| d.to_string()).collect::<Vec<_>>(),
+        ["record 2: `-include:_spf.EXAMPLE.com` conflicts with `include:_SPF.example.com`, which is kept"]
+    );
+}
+#[test]
+fn redirect() {
+    let merged = merged(&[
+        "v=spf1 redirect=_spf.example.com",
+        "v=spf1 mx redirect=_spf.example.net -all",
+    ]);
+    assert_eq!(
+        merged.builder().to_string(),
+        "v=spf1 include:_spf.example.com mx -all"
+    );
+    assert_eq!(
+        merged.decisions()[0].to_string(),
+        "record 0: `redirect=_spf.example.com` is replaced by `include:_spf.example.com`"
+    );
+    assert_eq!(
+        merged.decisions()[1].to_string(),
+        "record 1: `redirect=_spf.example.net` is never evaluated"
+    );
+}
+#[test]
+fn unreachable() {
+    let merged = merged(&["v=spf1 -all a"]);
+    assert_eq!(merged.builder().to_string(), "v=spf1 -all");
+    assert!(matches!(
+        &merged.decisions()[0],
+        Decision::Unreachable { record: 0, index: 1, term } if term.to_string() == "a"
+    ));
+}
+#[test]
+fn skipped() {
+    let merged = merged(&["v=spf1 a -all", "spf2.0/pra mx -all"]);
+    assert_eq!(merged.builder().to_string(), "v=spf1 a -all");
+    assert_eq!(
+        merged.decisions()[0].to_string(),
+        "record 1: `spf2.0/pra` is skipped"
+    );
+}
+#[test]
+fn networks() {
+    let merged = merged(&[
+        "v=spf1 ip4:192.0.2.0/25 -all",
+        "v=spf1 ip4:192.0.2.128/25 ip4:192.0.2.7 -all",
+    ]);
+    assert_eq!(merged.builder().to_string(), "v=spf1 ip4:192.0.2.0/24 -all");
+    assert_eq!(
+        merged.decisions().last(),
+        Some(&Decision::NetworksMerged(2))
+    );
+}
+#[test]
+fn limits() {
+    let records: Vec<String> = (0..4)
+        .map(|n| {
+            format!(
+                "v=spf1 include:a{0}.example.com include:b{0}.example.com include:c{0}.example.com -all",
+                n
+            )
+        })
+        .collect();
+    let records: Vec<&str> = records.iter().map(String::as_str).collect();
+    let merged = merged(&records);
+    assert!(merged.report().has_errors());
+    assert_eq!(
+        merged.report().errors().next().unwrap().rule(),
+        "lookup-limit"
+    );
+    assert!(merged.builder().validate().is_err());
+}
@@ -1,11 +1,17 @@
+#![allow(clippy::bool_assert_comparison)]
+
 mod a;
+mod aggregate;
 mod construct;
 mod display;
+mod edit;
 mod exists;
 mod include;
 mod ip4;
 mod ip6;
+mod merge;
 mod mx;
+mod order;
 mod parse;
 mod ptr;
 #[cfg(feature = "serde")]
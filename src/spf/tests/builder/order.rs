@@ -0,0 +1,74 @@
+use crate::mechanism::{Kind, Mechanism, Qualifier};
+use crate::{Builder, Parsed, Spf, SpfBuilder, SpfError};
+
+#[test]
+fn parse_keeps_order() {
+    let input = "v=spf1 -include:bad.com include:good.com ip4:1.2.3.4 -all";
+    let spf: SpfBuilder<Parsed> = input.parse().unwrap();
+    assert_eq!(spf.to_string(), input);
+    let built = spf.build().unwrap();
+    assert_eq!(built.to_string(), input);
+    assert_eq!(built.all().unwrap().to_string(), "-all");
+}
+#[test]
+fn from_spf_keeps_order() {
+    let input = "v=spf1 mx a:example.com ip6:2001:db8::/32 ~all";
+    let spf: Spf<String> = input.parse().unwrap();
+    let builder: SpfBuilder<Builder> = spf.into();
+    assert_eq!(builder.to_string(), input);
+}
+#[test]
+fn append_before_all() {
+    let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+    spf.append_mechanism(Mechanism::all())
+        .append_mechanism(Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap())
+        .append_mechanism(Mechanism::mx(Qualifier::Pass));
+    assert_eq!(spf.to_string(), "v=spf1 mx -all redirect=_spf.example.com");
+    spf.append_mechanism(Mechanism::all_with_qualifier(Qualifier::SoftFail));
+    assert_eq!(spf.terms()[1].to_string(), "~all");
+    assert_eq!(spf.terms().len(), 3);
+}
+#[test]
+fn insert() {
+    let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+    spf.append_mechanism(Mechanism::include(Qualifier::Pass, "good.com").unwrap())
+        .append_mechanism(Mechanism::all());
+    let good = Mechanism::include(Qualifier::Pass, "good.com").unwrap();
+    let idx = spf.position(&good).unwrap();
+    spf.insert_before(idx, Mechanism::include(Qualifier::Fail, "bad.com").unwrap())
+        .unwrap()
+        .insert_after(
+            idx + 1,
+            Mechanism::ip_from_string("ip4:192.0.2.0/24").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        spf.to_string(),
+        "v=spf1 -include:bad.com include:good.com ip4:192.0.2.0/24 -all"
+    );
+    assert_eq!(spf.ip4().unwrap()[0].as_network().prefix(), 24);
+}
+#[test]
+fn insert_errors() {
+    let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+    let err = spf
+        .insert_before(0, Mechanism::a(Qualifier::Pass))
+        .unwrap_err();
+    assert_eq!(err, SpfError::IndexOutOfRange(0));
+    spf.append_mechanism(Mechanism::all());
+    let err = spf
+        .insert_after(0, Mechanism::all_with_qualifier(Qualifier::Neutral))
+        .unwrap_err();
+    assert_eq!(err, SpfError::ModifierMayOccurOnlyOnce(Kind::All, 1));
+}
+#[test]
+fn move_term() {
+    let mut spf: SpfBuilder<Builder> = SpfBuilder::new();
+    spf.append_mechanism(Mechanism::a(Qualifier::Pass))
+        .append_mechanism(Mechanism::mx(Qualifier::Pass))
+        .append_mechanism(Mechanism::all());
+    spf.move_term(0, 1).unwrap();
+    assert_eq!(spf.to_string(), "v=spf1 mx a -all");
+    assert!(spf.move_term(3, 0).unwrap_err().is_index_out_of_range());
+    assert_eq!(spf.to_string(), "v=spf1 mx a -all");
+}
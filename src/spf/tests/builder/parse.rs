@@ -34,16 +34,47 @@ mod redirect {
     }
     mod invalid {
         use super::*;
+        use crate::mechanism::Kind;
         use crate::Parsed;
 
         #[test]
         fn redirect_x2() {
             let input = "v=spf1 redirect=_spf.google.com redirect=_spf.example.com";
             let spf = input.parse::<SpfBuilder<Parsed>>().unwrap_err();
-            assert!(matches!(spf, SpfError::ModifierMayOccurOnlyOnce(_)))
+            assert_eq!(spf, SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 1));
         }
     }
 }
+mod modifiers {
+    use super::*;
+    use crate::mechanism::Kind;
+    use crate::Parsed;
+
+    #[test]
+    fn exp_and_unknown() {
+        let input = "v=spf1 a -all exp=explain.example.com moo=cow";
+        let spf: SpfBuilder<Parsed> = input.parse().unwrap();
+        assert_eq!(spf.exp().unwrap().raw(), "explain.example.com");
+        assert_eq!(spf.unknown_modifiers().unwrap().len(), 1);
+        assert_eq!(spf.to_string(), input);
+    }
+    #[test]
+    fn exp_x2() {
+        let input = "v=spf1 a exp=a.example.com exp=b.example.com";
+        let err = input.parse::<SpfBuilder<Parsed>>().unwrap_err();
+        assert_eq!(err, SpfError::ModifierMayOccurOnlyOnce(Kind::Exp, 2));
+    }
+    #[test]
+    fn case_insensitive() {
+        let spf: SpfBuilder<Parsed> = "v=spf1 IP4:192.0.2.0/24 Include:example.com ~ALL"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:192.0.2.0/24 include:example.com ~all"
+        );
+    }
+}
 mod valid_spf_from_str {
     use crate::spf::SpfError;
     use crate::SpfBuilder;
@@ -73,7 +104,7 @@ mod valid_spf_from_str {
         let spf: SpfBuilder<_> = input.parse().unwrap();
         assert_eq!(spf.includes().is_none(), true);
         assert_eq!(spf.ip4().is_none(), true);
-        assert_eq!(!spf.ip6().is_none(), true);
+        assert!(spf.ip6().is_some());
         assert_eq!(spf.ip6().unwrap().len(), 6);
         assert_eq!(spf.ip6().unwrap()[0].to_string(), "ip6:2001:4860:4000::/36");
         assert_eq!(
@@ -169,7 +200,9 @@ mod invalid_ip {
         let error = spf.unwrap_err();
         assert_eq!(
             error,
-            InvalidMechanism(InvalidIPNetwork(InvalidAddr("203.32.10.0/33".to_string())))
+            InvalidMechanism(InvalidIPNetwork(
+                InvalidAddr("203.32.10.0/33".to_string()).into()
+            ))
         );
         assert_eq!(error.is_invalid_ip_addr(), true);
         assert_eq!(error.to_string(), "invalid address: 203.32.10.0/33");
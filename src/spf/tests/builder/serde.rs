@@ -1,7 +1,6 @@
 use crate::Parsed;
 #[cfg(test)]
 use crate::SpfBuilder;
-use serde_json;
 
 #[test]
 fn spf_a() {
@@ -10,7 +9,7 @@ fn spf_a() {
 
     let spf_as_json = serde_json::to_string(&spf).unwrap();
     assert_eq!(spf_as_json,
-               "{\"version\":\"v=spf1\",\"redirect\":null,\"a\":[{\"kind\":\"A\",\"qualifier\":\"Pass\",\"rrdata\":null}],\"mx\":null,\"include\":null,\"ip4\":null,\"ip6\":null,\"ptr\":null,\"exists\":null,\"all\":{\"kind\":\"All\",\"qualifier\":\"SoftFail\",\"rrdata\":null}}");
+               "{\"version\":\"v=spf1\",\"terms\":[{\"kind\":\"A\",\"qualifier\":\"Pass\",\"rrdata\":null},{\"kind\":\"All\",\"qualifier\":\"SoftFail\",\"rrdata\":null}]}");
     let spf_from_json: SpfBuilder<_> = serde_json::from_str(&spf_as_json).unwrap();
     assert_eq!(spf_from_json, spf);
 }
@@ -22,7 +21,7 @@ fn test_a_mechanism_colon_slash() {
     let spf = input.parse::<SpfBuilder<Parsed>>().unwrap();
     let spf_as_json = serde_json::to_string(&spf).unwrap();
     assert_eq!(spf_as_json,
-               "{\"version\":\"v=spf1\",\"redirect\":null,\"a\":[{\"kind\":\"A\",\"qualifier\":\"SoftFail\",\"rrdata\":\"example.com/24\"}],\"mx\":null,\"include\":null,\"ip4\":null,\"ip6\":null,\"ptr\":null,\"exists\":null,\"all\":{\"kind\":\"All\",\"qualifier\":\"SoftFail\",\"rrdata\":null}}");
+               "{\"version\":\"v=spf1\",\"terms\":[{\"kind\":\"A\",\"qualifier\":\"SoftFail\",\"rrdata\":\"example.com/24\"},{\"kind\":\"All\",\"qualifier\":\"SoftFail\",\"rrdata\":null}]}");
     let spf_from_json: SpfBuilder<_> = serde_json::from_str(&spf_as_json).unwrap();
     assert_eq!(spf_from_json, spf);
 }
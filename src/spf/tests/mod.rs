@@ -1,3 +1,5 @@
 #[cfg(feature = "builder")]
 mod builder;
+#[cfg(feature = "serde")]
+mod output;
 mod string;
@@ -0,0 +1,183 @@
+use crate::lint::{Level, LintConfig, Linter, Severity};
+use crate::output::{Location, Output};
+use crate::Spf;
+use serde_json::{json, Value};
+
+fn spf(s: &str) -> Spf<String> {
+    s.parse().unwrap()
+}
+
+#[test]
+fn sarif_rules() {
+    let mut output = Output::new();
+    output
+        .add_report(&spf("v=spf1 redirect=a.com -all").validation_report(), None)
+        .add_report(
+            &spf("v=spf1 a redirect=b.com -all").validation_report(),
+            None,
+        );
+    let sarif = serde_json::to_value(output.sarif()).unwrap();
+    assert_eq!(
+        sarif["$schema"],
+        "https://json.schemastore.org/sarif-2.1.0.json"
+    );
+    let driver = &sarif["runs"][0]["tool"]["driver"];
+    assert_eq!(driver["name"], "decon-spf");
+    // Only the rules which were reported are described, once each.
+    assert_eq!(
+        driver["rules"],
+        json!([{
+            "id": "redirect-with-all",
+            "shortDescription": {
+                "text": "Any `redirect` modifier MUST be ignored when there is an `all` \
+                         mechanism in the record, regardless of the relative ordering."
+            },
+            "helpUri": "https://datatracker.ietf.org/doc/html/rfc7208#section-5.1",
+            "defaultConfiguration": { "level": "warning" }
+        }])
+    );
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[1],
+        json!({
+            "ruleId": "redirect-with-all",
+            "ruleIndex": 0,
+            "level": "warning",
+            "message": { "text": "`redirect` is ignored because `all` is present" },
+            "properties": { "record": "v=spf1 a redirect=b.com -all", "index": 1 }
+        })
+    );
+}
+#[test]
+fn sarif_location() {
+    let mut output = Output::new();
+    let location = Location::new("example.com.zone")
+        .with_line(7)
+        .with_owner("example.com.");
+    output.add_report(
+        &spf("v=spf1 a exp=a.com exp=b.com").validation_report(),
+        Some(location),
+    );
+    let sarif = serde_json::to_value(output.sarif()).unwrap();
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["locations"],
+        json!([{
+            "physicalLocation": {
+                "artifactLocation": { "uri": "example.com.zone" },
+                "region": { "startLine": 7 }
+            },
+            "logicalLocations": [{ "name": "example.com.", "kind": "resource" }]
+        }])
+    );
+    // No region is given without a line.
+    let mut output = Output::new();
+    output.add_report(
+        &spf("v=spf1 a exp=a.com exp=b.com").validation_report(),
+        Some(Location::new("policy.txt")),
+    );
+    let sarif = serde_json::to_value(output.sarif()).unwrap();
+    assert_eq!(
+        sarif["runs"][0]["results"][0]["locations"],
+        json!([{ "physicalLocation": { "artifactLocation": { "uri": "policy.txt" } } }])
+    );
+}
+#[test]
+fn sarif_linter_levels() {
+    let mut config = LintConfig::new();
+    config
+        .set_severity("neutral-all", Severity::Error)
+        .set("missing-all", Level::Allow);
+    let linter = Linter::with_config(config);
+    let spf = spf("v=spf1 mx ?all");
+    let mut output = Output::new();
+    output
+        .add_linter(&linter)
+        .add_findings(&spf.to_string(), &linter.check(&spf), None);
+    let sarif = serde_json::to_value(output.sarif()).unwrap();
+    let rule = &sarif["runs"][0]["tool"]["driver"]["rules"][0];
+    assert_eq!(rule["id"], "neutral-all");
+    assert_eq!(rule["defaultConfiguration"]["level"], "error");
+    assert_eq!(sarif["runs"][0]["results"][0]["level"], "error");
+    assert_eq!(output.max_severity(), Some(Severity::Error));
+}
+#[test]
+fn report_and_linter() {
+    let spf = spf("v=spf1 ptr redirect=a.com -all");
+    let linter = Linter::new();
+    let mut output = Output::new();
+    output.add_findings(
+        &spf.to_string(),
+        spf.validation_report().iter().chain(&linter.check(&spf)),
+        None,
+    );
+    let rules: Vec<_> = output.findings().map(|f| f.rule()).collect();
+    let expected: &[&str] = match cfg!(feature = "ptr") {
+        true => &["deprecated-ptr", "redirect-with-all"],
+        false => &["redirect-with-all"],
+    };
+    assert_eq!(rules, expected);
+}
+#[test]
+fn json_schema() {
+    let mut output = Output::new();
+    output
+        .add_report(&spf("v=spf1 a -all").validation_report(), None)
+        .add_report(
+            &spf("v=spf1 redirect=a.com -all").validation_report(),
+            Some(Location::new("a.zone").with_line(2)),
+        );
+    assert_eq!(
+        serde_json::to_value(output.json()).unwrap(),
+        json!({
+            "version": 1,
+            "records": [
+                { "source": "v=spf1 a -all", "location": null, "findings": [] },
+                {
+                    "source": "v=spf1 redirect=a.com -all",
+                    "location": { "uri": "a.zone", "line": 2, "owner": null },
+                    "findings": [{
+                        "rule": "redirect-with-all",
+                        "severity": "warning",
+                        "index": 0,
+                        "message": "`redirect` is ignored because `all` is present",
+                        "reference": "https://datatracker.ietf.org/doc/html/rfc7208#section-5.1"
+                    }]
+                }
+            ],
+            "summary": { "errors": 0, "warnings": 1, "infos": 0 }
+        })
+    );
+}
+#[test]
+fn empty() {
+    let output = Output::default();
+    assert_eq!(output.max_severity(), None);
+    let sarif: Value = serde_json::to_value(output.sarif()).unwrap();
+    assert_eq!(sarif["runs"][0]["results"], json!([]));
+    assert_eq!(sarif["runs"][0]["tool"]["driver"]["rules"], json!([]));
+}
+#[test]
+#[cfg(feature = "zone")]
+fn zone_records() {
+    use crate::zone::ZoneScanner;
+    let zone = "$ORIGIN example.com.\n\
+                @    TXT \"v=spf1 redirect=a.com -all\"\n\
+                mail TXT \"v=spf1 foo -all\"\n";
+    let mut output = Output::new();
+    for record in ZoneScanner::new(zone) {
+        output.add_zone_record("example.com.zone", &record);
+    }
+    let json = serde_json::to_value(output.json()).unwrap();
+    assert_eq!(
+        json["records"][0]["location"],
+        json!({ "uri": "example.com.zone", "line": 2, "owner": "example.com." })
+    );
+    let invalid = &json["records"][1];
+    assert_eq!(invalid["location"]["owner"], "mail.example.com.");
+    assert_eq!(invalid["source"], "");
+    assert_eq!(invalid["findings"][0]["rule"], "invalid-spf");
+    assert_eq!(invalid["findings"][0]["severity"], "error");
+}
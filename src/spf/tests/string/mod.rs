@@ -26,7 +26,7 @@ mod a {
             assert_eq!(spf.source, input);
             assert_eq!(spf.version, "v=spf1");
             assert_eq!(spf.version(), "v=spf1");
-            assert_eq!(spf.is_v1(), true);
+            assert!(spf.is_v1());
             assert_eq!(spf.mechanisms.len(), 2);
             assert_eq!(spf.all().unwrap().to_string(), "-all");
         }
@@ -159,8 +159,28 @@ mod ip {
                 assert_eq!(spf.mechanisms.len(), 2);
                 assert_eq!(spf.mechanisms[0].to_string(), "ip4:203.32.160.10/27");
             }
+
+            #[test]
+            fn followed_by_all() {
+                let input = "v=spf1 ip4:203.32.160.10 ip4:203.32.160.11 -all";
+                let spf: Spf<String> = input.parse().unwrap();
+                assert_eq!(spf.all_idx, 2);
+                assert_eq!(spf.all().unwrap().to_string(), "-all");
+            }
+
+            #[test]
+            fn followed_by_redirect() {
+                let input = "v=spf1 ip4:203.32.160.10 a redirect=_spf.example.com";
+                let spf: Spf<String> = input.parse().unwrap();
+                assert_eq!(spf.redirect_idx, 2);
+                assert_eq!(
+                    spf.redirect().unwrap().to_string(),
+                    "redirect=_spf.example.com"
+                );
+            }
         }
 
+        #[cfg(feature = "std")]
         mod invalid {
             use super::*;
             use crate::mechanism::MechanismError::InvalidIPNetwork;
@@ -174,7 +194,7 @@ mod ip {
                 assert_eq!(
                     spf,
                     SpfError::InvalidMechanism(InvalidIPNetwork(
-                        "203.32.160.10/34".parse::<IpNetwork>().unwrap_err()
+                        "203.32.160.10/34".parse::<IpNetwork>().unwrap_err().into()
                     ))
                 );
             }
@@ -227,11 +247,250 @@ mod redirect {
         fn redirect_x2() {
             let input = "v=spf1 redirect=example.com redirect=test.com";
             let spf: SpfError = input.parse::<Spf<String>>().unwrap_err();
-            assert_eq!(spf, SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect));
+            assert_eq!(spf, SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 1));
         }
     }
 }
 
+mod grammar {
+    use super::*;
+    use crate::mechanism::{Kind, MechanismError};
+    use crate::{Scope, SpfError};
+
+    #[test]
+    fn case_insensitive() {
+        let spf: Spf<String> = "V=SPF1 INCLUDE:_spf.example.com MX:example.com -ALL"
+            .parse()
+            .unwrap();
+        assert!(spf.is_v1());
+        assert_eq!(spf.mechanisms[0].kind(), &Kind::Include);
+        assert_eq!(spf.mechanisms[1].kind(), &Kind::MX);
+        assert_eq!(spf.all().unwrap().to_string(), "-all");
+    }
+    #[test]
+    fn version_is_a_complete_term() {
+        let err = "v=spf10 -all".parse::<Spf<String>>().unwrap_err();
+        assert_eq!(err, SpfError::InvalidVersion);
+        let err = "spf2.0/foo -all".parse::<Spf<String>>().unwrap_err();
+        assert_eq!(err, SpfError::InvalidVersion);
+    }
+    #[test]
+    fn spf2() {
+        let spf: Spf<String> = "spf2.0/pra a -all".parse().unwrap();
+        assert_eq!(spf.version(), "spf2.0/pra");
+        assert_eq!(spf.mechanisms.len(), 2);
+    }
+    #[test]
+    fn spf2_scopes() {
+        let spf: Spf<String> = "SPF2.0/MFROM,PRA a -all".parse().unwrap();
+        assert!(spf.is_v2());
+        assert!(!spf.is_v1());
+        assert_eq!(spf.version().scopes(), [Scope::MFrom, Scope::Pra]);
+        let err = "spf2.0/pra,pra a -all".parse::<Spf<String>>().unwrap_err();
+        assert_eq!(err, SpfError::InvalidVersion);
+    }
+    #[test]
+    fn exists_with_include_label() {
+        let spf: Spf<String> = "v=spf1 exists:%{i}.include.example.com -all"
+            .parse()
+            .unwrap();
+        assert_eq!(spf.mechanisms[0].kind(), &Kind::Exists);
+        assert_eq!(spf.lookup_count(), 1);
+        let err = "v=spf1 exists:%{i}.include:x -all"
+            .parse::<Spf<String>>()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat(
+                "exists:%{i}.include:x".to_string()
+            ))
+        );
+    }
+    #[test]
+    fn modifiers() {
+        let input = "v=spf1 mx -all exp=explain._spf.%{d} moo=cow";
+        let spf: Spf<String> = input.parse().unwrap();
+        assert_eq!(spf.mechanisms[2].kind(), &Kind::Exp);
+        assert_eq!(spf.mechanisms[3].kind(), &Kind::UnknownModifier);
+        assert_eq!(spf.mechanisms[3].to_string(), "moo=cow");
+        assert_eq!(spf.lookup_count(), 1);
+    }
+}
+
+mod txt_rdata {
+    use super::*;
+    use crate::SpfError;
+
+    #[test]
+    fn character_strings() {
+        let rdata = ["v=spf1 ip4:192.0.2.0/24 ip4:198.51", ".100.0/24 -all"];
+        let spf = Spf::from_character_strings(&rdata).unwrap();
+        assert_eq!(spf.iter().count(), 3);
+        assert_eq!(spf.mechanisms[1].raw(), "198.51.100.0/24");
+    }
+    #[test]
+    fn character_strings_invalid_spf() {
+        let rdata = ["v=spf1 ", "foo -all"];
+        assert!(Spf::from_character_strings(&rdata).is_err());
+    }
+    #[test]
+    fn presentation_format() {
+        let input = r#""v=spf1 include:_spf.example.com " "include:_spf.example.net ~all""#;
+        let spf = Spf::from_presentation_format(input).unwrap();
+        assert_eq!(spf.iter().count(), 3);
+        assert_eq!(spf.all().unwrap().to_string(), "~all");
+    }
+    #[test]
+    fn presentation_format_error() {
+        let err = Spf::from_presentation_format(r#""v=spf1 -all" x""#).unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::InvalidTxtRdata("unexpected quote in unquoted string".to_string())
+        );
+    }
+    #[test]
+    fn publish_long_record() {
+        let input = format!("v=spf1 {}-all", "ip4:192.0.2.0/24 ".repeat(25));
+        let spf: Spf<String> = input.parse().unwrap();
+        let strings = spf.to_character_strings();
+        assert_eq!(strings.len(), 2);
+        assert!(strings.iter().all(|s| s.len() <= 255));
+
+        let presentation = spf.to_presentation_format();
+        assert_eq!(presentation.matches('"').count(), 4);
+        assert_eq!(Spf::from_presentation_format(&presentation).unwrap(), spf);
+        assert_eq!(Spf::from_character_strings(&strings).unwrap(), spf);
+    }
+}
+
+mod rrset {
+    use super::*;
+    use crate::mechanism::MechanismError;
+    use crate::SpfError;
+
+    #[test]
+    fn no_spf_record() {
+        let rrset = [
+            "google-site-verification=abc123",
+            "v=spf1-all",
+            "v=DKIM1; p=abc",
+        ];
+        assert_eq!(Spf::from_txt_rrset(rrset), Ok(None));
+        assert_eq!(Spf::from_txt_rrset(Vec::<String>::new()), Ok(None));
+    }
+    #[test]
+    fn single_spf_record() {
+        let rrset = vec![
+            "MS=ms12345678".to_string(),
+            "V=SPF1 mx -all".to_string(),
+            "spf2.0/mfrom,pra mx -all".to_string(),
+        ];
+        let spf = Spf::from_txt_rrset(&rrset).unwrap().unwrap();
+        assert_eq!(spf.to_string(), "V=SPF1 mx -all");
+    }
+    #[test]
+    fn version_only() {
+        let spf = Spf::from_txt_rrset(["v=spf1"]).unwrap().unwrap();
+        assert_eq!(spf.iter().count(), 0);
+    }
+    #[test]
+    fn multiple_spf_records() {
+        let rrset = ["v=spf1 a -all", "site-verification", "v=spf1 mx ~all"];
+        let err = Spf::from_txt_rrset(rrset).unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::MultipleSpfRecords(vec![
+                "v=spf1 a -all".to_string(),
+                "v=spf1 mx ~all".to_string()
+            ])
+        );
+    }
+    #[test]
+    fn invalid_spf_record() {
+        let err = Spf::from_txt_rrset(["v=spf1 foo -all"]).unwrap_err();
+        assert_eq!(
+            err,
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat("foo".to_string()))
+        );
+    }
+}
+
+mod lenient {
+    use super::*;
+    use crate::mechanism::{Kind, MechanismError};
+    use crate::SpfError;
+
+    #[test]
+    fn valid() {
+        let input = "v=spf1 a mx -all";
+        let (spf, errors) = Spf::parse_lenient(input);
+        assert!(errors.is_empty());
+        assert_eq!(errors.source(), "");
+        assert_eq!(spf, input.parse::<Spf<String>>().unwrap());
+    }
+
+    #[test]
+    fn reports_every_invalid_term() {
+        let input = "v=spf1 a: mx include:_spf.example.com ip4:203.32.160.10/34 mx/ -all";
+        let (spf, errors) = Spf::parse_lenient(input);
+        assert_eq!(errors.source(), input);
+        assert_eq!(errors.errors().len(), 3);
+        assert_eq!(
+            errors.errors()[0],
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat("a:".to_string()))
+        );
+        assert!(errors.errors()[1].is_invalid_ip_addr());
+        assert_eq!(
+            errors.errors()[2],
+            SpfError::InvalidMechanism(MechanismError::InvalidMechanismFormat("mx/".to_string()))
+        );
+        let terms: Vec<String> = spf.iter().map(|m| m.to_string()).collect();
+        assert_eq!(terms, ["mx", "include:_spf.example.com", "-all"]);
+        assert_eq!(spf.lookup_count(), 2);
+        assert_eq!(spf.all().unwrap().to_string(), "-all");
+    }
+
+    #[test]
+    fn duplicate_redirect() {
+        let input = "v=spf1 redirect=_spf.example.com redirect=_spf.example.net";
+        let (spf, errors) = Spf::parse_lenient(input);
+        assert_eq!(
+            errors.errors(),
+            &vec![SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 1)]
+        );
+        assert_eq!(spf.iter().count(), 1);
+        assert_eq!(spf.redirect().unwrap().raw(), "_spf.example.com");
+        assert_eq!(spf.lookup_count(), 1);
+    }
+
+    #[test]
+    fn invalid_version() {
+        let (spf, errors) = Spf::parse_lenient("v=spf3 a:/ -all");
+        assert_eq!(errors.errors().len(), 2);
+        assert_eq!(errors.errors()[0], SpfError::InvalidVersion);
+        assert_eq!(spf.iter().count(), 1);
+        assert!(spf.validate().unwrap_err().errors()[0].version_is_invalid());
+    }
+
+    #[test]
+    fn many_lookups() {
+        let input = format!("v=spf1 {}-all", "a ".repeat(300));
+        let (spf, errors) = Spf::parse_lenient(&input);
+        assert_eq!(errors.errors().len(), 1);
+        assert!(errors.errors()[0].is_source_length_exceeded());
+        assert_eq!(spf.iter().count(), 301);
+        assert_eq!(spf.lookup_count(), u8::MAX);
+    }
+
+    #[test]
+    fn nothing_valid() {
+        let (spf, errors) = Spf::parse_lenient("v=spf1 a: mx:");
+        assert_eq!(errors.errors().len(), 2);
+        assert_eq!(spf.all(), None);
+        assert_eq!(spf.redirect(), None);
+    }
+}
+
 #[cfg(feature = "builder")]
 mod spf_to_spf_builder {
     use crate::{mechanism::Mechanism, mechanism::Qualifier, Builder, Spf, SpfBuilder};
@@ -258,12 +517,10 @@ mod iter {
     fn basic() {
         let input = "v=spf1 a mx -all";
         let spf: Spf<String> = input.parse().unwrap();
-        let m_list = vec!["a", "mx", "-all"];
+        let m_list = ["a", "mx", "-all"];
         assert_eq!(spf.version(), "v=spf1");
-        let mut idx: usize = 0;
-        for m in spf.iter() {
+        for (idx, m) in spf.iter().enumerate() {
             assert_eq!(m.to_string(), m_list[idx]);
-            idx = idx + 1;
         }
     }
 }
@@ -271,7 +528,6 @@ mod iter {
 #[cfg(feature = "serde")]
 mod serde {
     use super::*;
-    use serde_json;
 
     #[test]
     fn basic() {
@@ -282,3 +538,277 @@ mod serde {
                    "{\"source\":\"v=spf1 a -all\",\"version\":\"v=spf1\",\"redirect_idx\":0,\"has_redirect\":false,\"all_idx\":1,\"lookup_count\":1,\"mechanisms\":[{\"kind\":\"A\",\"qualifier\":\"Pass\",\"rrdata\":null},{\"kind\":\"All\",\"qualifier\":\"Fail\",\"rrdata\":null}]}");
     }
 }
+
+mod edit {
+    use super::*;
+    use crate::mechanism::{Kind, Mechanism, Qualifier};
+    use crate::SpfError;
+
+    // An edited record must be the same as the record parsed from its rendered form.
+    fn assert_consistent(spf: &Spf<String>) {
+        let parsed: Spf<String> = spf.to_string().parse().unwrap();
+        assert_eq!(spf, &parsed);
+    }
+
+    #[test]
+    fn insert() {
+        let mut spf: Spf<String> = "v=spf1  MX -all".parse().unwrap();
+        spf.insert(0, Mechanism::a(Qualifier::Pass)).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a mx -all");
+        assert_consistent(&spf);
+        spf.insert(3, Mechanism::exp("explain.example.com").unwrap())
+            .unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a mx -all exp=explain.example.com");
+        assert_eq!(spf.all().unwrap().to_string(), "-all");
+        assert_consistent(&spf);
+        assert_eq!(
+            spf.insert(6, Mechanism::a(Qualifier::Pass)).unwrap_err(),
+            SpfError::IndexOutOfRange(6)
+        );
+    }
+    #[test]
+    fn insert_redirect() {
+        let mut spf: Spf<String> = "v=spf1 a".parse().unwrap();
+        let redirect = Mechanism::redirect(Qualifier::Pass, "_spf.example.com").unwrap();
+        spf.insert(1, redirect.clone()).unwrap();
+        assert_eq!(spf.redirect(), Some(&redirect));
+        assert_eq!(spf.lookup_count(), 2);
+        assert_consistent(&spf);
+        assert_eq!(
+            spf.insert(0, redirect).unwrap_err(),
+            SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 0)
+        );
+    }
+    #[test]
+    fn many_lookups() {
+        let mut spf = Spf::default();
+        for idx in 0..300 {
+            spf.insert(idx, Mechanism::a(Qualifier::Pass)).unwrap();
+        }
+        assert_eq!(spf.lookup_count(), u8::MAX);
+    }
+    #[test]
+    fn remove() {
+        let mut spf: Spf<String> = "v=spf1 include:a.example.com mx -all".parse().unwrap();
+        assert_eq!(spf.remove(0).unwrap().to_string(), "include:a.example.com");
+        assert_eq!(spf.lookup_count(), 1);
+        assert_consistent(&spf);
+        assert_eq!(spf.remove(1).unwrap().to_string(), "-all");
+        assert_eq!(spf.all(), None);
+        assert_consistent(&spf);
+        assert_eq!(spf.remove(1).unwrap_err(), SpfError::IndexOutOfRange(1));
+    }
+    #[test]
+    fn replace() {
+        let mut spf: Spf<String> = "v=spf1 a redirect=a.example.com".parse().unwrap();
+        let redirect = Mechanism::redirect(Qualifier::Pass, "b.example.com").unwrap();
+        // The redirect being replaced does not count.
+        spf.replace(1, redirect.clone()).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a redirect=b.example.com");
+        assert_eq!(
+            spf.replace(0, redirect).unwrap_err(),
+            SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 0)
+        );
+        spf.replace(1, Mechanism::<String>::new(Kind::All, Qualifier::Fail))
+            .unwrap();
+        assert_eq!(spf.redirect(), None);
+        assert_eq!(spf.all().unwrap().to_string(), "-all");
+        assert_consistent(&spf);
+    }
+    #[test]
+    fn move_term() {
+        let mut spf: Spf<String> = "v=spf1 -all a redirect=a.example.com".parse().unwrap();
+        spf.move_term(0, 2).unwrap();
+        assert_eq!(spf.to_string(), "v=spf1 a redirect=a.example.com -all");
+        assert_eq!(spf.all().unwrap().to_string(), "-all");
+        assert_eq!(
+            spf.redirect().unwrap().to_string(),
+            "redirect=a.example.com"
+        );
+        assert_consistent(&spf);
+        assert_eq!(
+            spf.move_term(0, 3).unwrap_err(),
+            SpfError::IndexOutOfRange(3)
+        );
+    }
+    #[test]
+    fn all_qualifier() {
+        let mut spf: Spf<String> = "v=spf1 mx ?all".parse().unwrap();
+        assert!(spf.set_all_qualifier(Qualifier::SoftFail));
+        assert_eq!(spf.all().unwrap().qualifier(), &Qualifier::SoftFail);
+        assert_eq!(spf.to_string(), "v=spf1 mx ~all");
+        assert_consistent(&spf);
+        let mut spf: Spf<String> = "v=spf1 mx".parse().unwrap();
+        assert!(!spf.set_all_qualifier(Qualifier::Fail));
+        assert_eq!(spf.to_string(), "v=spf1 mx");
+    }
+    #[test]
+    fn first_all() {
+        // Only the first `all` is evaluated.
+        let mut spf: Spf<String> = "v=spf1 mx ?all -all".parse().unwrap();
+        assert_eq!(spf.all().unwrap().to_string(), "?all");
+        assert!(spf.set_all_qualifier(Qualifier::SoftFail));
+        assert_eq!(spf.to_string(), "v=spf1 mx ~all -all");
+        spf.remove(0).unwrap();
+        assert_eq!(spf.all().unwrap().to_string(), "~all");
+        assert_consistent(&spf);
+    }
+    #[test]
+    #[cfg(feature = "builder")]
+    fn built() {
+        use crate::spf::builder::{Builder, SpfBuilder};
+        let mut builder = SpfBuilder::<Builder>::new();
+        builder.append_mechanism(Mechanism::a(Qualifier::Pass));
+        let mut spf = builder.build().unwrap();
+        spf.insert(1, Mechanism::all()).unwrap();
+        assert!(spf.built());
+        assert_eq!(spf.to_string(), "v=spf1 a -all");
+        assert_eq!(spf.all().unwrap().to_string(), "-all");
+    }
+}
+
+mod diff {
+    use super::*;
+    use crate::diff::Change;
+    use crate::mechanism::Qualifier;
+
+    fn diff(old: &str, new: &str) -> crate::diff::Diff {
+        let old: Spf<String> = old.parse().unwrap();
+        old.diff(&new.parse().unwrap())
+    }
+
+    #[test]
+    fn same_meaning() {
+        let diff = diff(
+            "v=spf1 MX  a:Mail.example.com -all",
+            "v=spf1 mx a:Mail.example.com -all",
+        );
+        assert!(diff.is_empty());
+        assert!(diff.added_coverage().is_empty());
+        assert_eq!(diff.lookup_delta(), 0);
+        assert_eq!(diff.to_string(), "");
+    }
+    #[test]
+    fn added_and_removed() {
+        let diff = diff(
+            "v=spf1 a include:_spf.example.com -all",
+            "v=spf1 include:_spf.example.com include:_spf.example.net mx -all",
+        );
+        assert_eq!(diff.changes().len(), 3);
+        assert!(
+            matches!(&diff.changes()[0], Change::Removed { index: 0, term } if term.to_string() == "a")
+        );
+        assert!(matches!(&diff.changes()[1], Change::Added { index: 1, .. }));
+        assert!(
+            matches!(&diff.changes()[2], Change::Added { index: 2, term } if term.to_string() == "mx")
+        );
+        assert_eq!(diff.lookups_before(), 2);
+        assert_eq!(diff.lookups_after(), 3);
+        assert_eq!(diff.lookup_delta(), 1);
+        assert!(diff.to_string().ends_with("lookups 2 -> 3\n"));
+    }
+    #[test]
+    fn all_qualifier() {
+        let diff1 = diff("v=spf1 mx ~all", "v=spf1 mx");
+        assert_eq!(
+            diff1.changes(),
+            [Change::All {
+                from: Some(Qualifier::SoftFail),
+                to: None
+            }]
+        );
+        assert_eq!(diff1.to_string(), "removed `~all`\n");
+        let diff2 = diff("v=spf1 mx", "v=spf1 mx all");
+        assert_eq!(diff2.to_string(), "added `all`\n");
+    }
+    #[test]
+    fn requalified() {
+        let diff = diff(
+            "v=spf1 ?a:mail.example.com mx -all",
+            "v=spf1 a:mail.example.com -mx -all",
+        );
+        assert_eq!(
+            diff.to_string(),
+            "`?a:mail.example.com` is now `a:mail.example.com`\n`mx` is now `-mx`\n"
+        );
+    }
+    #[test]
+    fn reordered() {
+        // `mx` and `a` may match the same host.
+        let diff1 = diff("v=spf1 -mx a -all", "v=spf1 a -mx -all");
+        assert_eq!(diff1.changes().len(), 1);
+        assert!(matches!(
+            &diff1.changes()[0],
+            Change::Reordered {
+                index: 0,
+                other: 1,
+                ..
+            }
+        ));
+        assert_eq!(diff1.to_string(), "`a` now precedes `-mx`\n");
+        // With the same qualifier the result is the same.
+        assert!(diff("v=spf1 mx a -all", "v=spf1 a mx -all").is_empty());
+        // Modifiers are not evaluated in order.
+        assert!(diff(
+            "v=spf1 exp=explain.example.com redirect=_spf.example.com",
+            "v=spf1 redirect=_spf.example.com exp=explain.example.com"
+        )
+        .is_empty());
+    }
+    #[test]
+    fn coverage() {
+        let diff1 = diff(
+            "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 -all",
+            "v=spf1 ip4:192.0.2.0/25 ip4:198.51.100.0/24 ip6:2001:db8::/32 -all",
+        );
+        assert_eq!(diff1.added_coverage(), ["198.51.100.0/24"]);
+        assert_eq!(diff1.removed_coverage(), ["192.0.2.128/25"]);
+        assert!(diff1
+            .to_string()
+            .ends_with("authorizes 198.51.100.0/24\nno longer authorizes 192.0.2.128/25\n"));
+        // An earlier network with another qualifier takes precedence.
+        let diff2 = diff(
+            "v=spf1 ip4:192.0.2.0/24 -all",
+            "v=spf1 -ip4:192.0.2.0/26 ip4:192.0.2.0/24 -all",
+        );
+        assert_eq!(diff2.removed_coverage(), ["192.0.2.0/26"]);
+        assert!(diff2.added_coverage().is_empty());
+        // Terms after `all` are never evaluated.
+        let diff3 = diff("v=spf1 -all", "v=spf1 -all ip6:2001:db8::/32");
+        assert!(diff3.added_coverage().is_empty());
+    }
+    #[test]
+    fn letter_case() {
+        assert!(diff(
+            "v=spf1 a:Mail.Example.com ip6:2001:DB8::/32 -all",
+            "v=spf1 a:mail.example.com ip6:2001:db8::/32 -all"
+        )
+        .is_empty());
+        // The case of a macro letter is significant.
+        let diff = diff(
+            "v=spf1 exists:%{i}.example.com",
+            "v=spf1 exists:%{I}.example.com",
+        );
+        assert_eq!(diff.changes().len(), 2);
+    }
+    #[test]
+    fn first_all() {
+        // Only the first `all` is evaluated.
+        let diff1 = diff(
+            "v=spf1 ip4:192.0.2.0/24 -all ~all",
+            "v=spf1 ip4:192.0.2.0/24 -all",
+        );
+        assert!(diff1.is_empty());
+        let diff2 = diff("v=spf1 -all ip4:192.0.2.0/24 ~all", "v=spf1 ~all");
+        assert_eq!(
+            diff2.to_string(),
+            "`-all` is now `~all`\nremoved `ip4:192.0.2.0/24`\n"
+        );
+        assert!(diff2.removed_coverage().is_empty());
+    }
+    #[test]
+    fn version() {
+        let diff = diff("v=spf1 mx -all", "spf2.0/pra mx -all");
+        assert_eq!(diff.to_string(), "`v=spf1` is now `spf2.0/pra`\n");
+    }
+}
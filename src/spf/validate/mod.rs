@@ -1,36 +1,140 @@
-#[cfg(feature = "builder")]
-use crate::SpfBuilder;
+mod report;
 #[cfg(test)]
 mod tests;
 #[cfg(feature = "builder")]
 mod validate_builder;
 mod validate_string;
 
-use crate::core::{self, spf_check_whitespace};
+use crate::core;
+use crate::lint::rules::{
+    DeprecatedPtr, InvalidVersion, LookupLimit, ModifierNotLast, RecordLength, RedirectWithAll,
+    RepeatedModifier, RepeatedUnknownModifier, Whitespace,
+};
+use crate::lint::{Record, Rule};
+use crate::mechanism::{Kind, Mechanism};
+use crate::prelude::*;
+use crate::spf::errors::SpfErrors;
 use crate::spf::SpfError;
 
+pub use crate::spf::validate::report::ValidationReport;
+
+/// The validation of a record. Every check is a built-in lint [`Rule`] run over the [`Record`]
+/// of the implementor, so a record gets the same verdict whether it was parsed, built or
+/// deserialized.
 pub trait Validate {
-    fn validate_version(&self) -> Result<(), SpfError> {
-        Ok(())
+    /// The record to be checked.
+    fn record(&self) -> Record<'_>;
+    /// Run every check and describe each problem found. A version or length error stops
+    /// validation and is the only finding reported.
+    fn validate_report(&self) -> ValidationReport {
+        let record = self.record();
+        let mut report = ValidationReport::new(&record);
+        for check in &CHECKS {
+            if report.run(check, &record) && check.stops {
+                break;
+            }
+        }
+        report
     }
-    fn validate_length(&self) -> Result<(), SpfError>;
-    fn validate_ptr(&self) -> Result<(), SpfError> {
-        Ok(())
+    /// Run every check. A version or length error stops validation and is the only error
+    /// returned.
+    fn validate_all(&self) -> Result<(), SpfErrors> {
+        self.validate_report().into_result()
     }
-    fn validate_redirect_all(&self) -> Result<(), SpfError>;
-    fn validate_lookup_count(&self) -> Result<(), SpfError>;
 }
 
-/// Checks that the spf record has the minimum start string of "v=spf1" or
-/// "spf2.0"
+/// A built-in rule run by validation and the [`SpfError`] given for each of its findings.
+pub(crate) struct Check {
+    pub(crate) rule: &'static dyn Rule,
+    /// A finding of the rule stops validation.
+    pub(crate) stops: bool,
+    /// The error of a finding, given the terms of the record and the index of the finding.
+    pub(crate) error: fn(&[Mechanism<String>], Option<usize>) -> Option<SpfError>,
+}
+
+pub(crate) const INVALID_VERSION: Check = Check {
+    rule: &InvalidVersion,
+    stops: true,
+    error: |_, _| Some(SpfError::InvalidVersion),
+};
+pub(crate) const RECORD_LENGTH: Check = Check {
+    rule: &RecordLength,
+    stops: true,
+    error: |_, _| Some(SpfError::SourceLengthExceeded),
+};
+pub(crate) const DEPRECATED_PTR: Check = Check {
+    rule: &DeprecatedPtr,
+    stops: false,
+    error: |_, _| Some(SpfError::DeprecatedPtrDetected),
+};
+pub(crate) const LOOKUP_LIMIT: Check = Check {
+    rule: &LookupLimit,
+    stops: false,
+    error: |_, _| Some(SpfError::LookupLimitExceeded),
+};
+pub(crate) const REDIRECT_WITH_ALL: Check = Check {
+    rule: &RedirectWithAll,
+    stops: false,
+    error: |_, _| Some(SpfError::RedirectWithAllMechanism),
+};
+pub(crate) const REPEATED_MODIFIER: Check = Check {
+    rule: &RepeatedModifier,
+    stops: false,
+    error: repeated_modifier,
+};
+pub(crate) const REPEATED_UNKNOWN_MODIFIER: Check = Check {
+    rule: &RepeatedUnknownModifier,
+    stops: false,
+    error: repeated_modifier,
+};
+pub(crate) const MODIFIER_NOT_LAST: Check = Check {
+    rule: &ModifierNotLast,
+    stops: false,
+    error: |terms, index| {
+        let idx = index?;
+        match terms.get(idx)?.kind() {
+            Kind::Redirect => Some(SpfError::RedirectNotFinalMechanism(idx)),
+            kind => Some(SpfError::ModifierNotFinal(*kind, idx)),
+        }
+    },
+};
+pub(crate) const WHITESPACE: Check = Check {
+    rule: &Whitespace,
+    stops: false,
+    error: |_, _| Some(SpfError::WhiteSpaceSyntaxError),
+};
+
+/// The checks made by validation, in the order they are run.
+pub(crate) const CHECKS: [Check; 9] = [
+    INVALID_VERSION,
+    RECORD_LENGTH,
+    DEPRECATED_PTR,
+    LOOKUP_LIMIT,
+    REDIRECT_WITH_ALL,
+    REPEATED_MODIFIER,
+    REPEATED_UNKNOWN_MODIFIER,
+    MODIFIER_NOT_LAST,
+    WHITESPACE,
+];
+
+fn repeated_modifier(terms: &[Mechanism<String>], index: Option<usize>) -> Option<SpfError> {
+    let idx = index?;
+    Some(SpfError::ModifierMayOccurOnlyOnce(
+        *terms.get(idx)?.kind(),
+        idx,
+    ))
+}
+
+/// The number of DNS lookups required to evaluate `terms`.
+pub(crate) fn lookup_count(terms: &[Mechanism<String>]) -> usize {
+    terms.iter().filter(|m| m.kind().is_lookup()).count()
+}
+
+/// Checks that the spf record starts with a complete version term of "v=spf1" or
+/// "spf2.0/" and its scopes.
 /// Returns Ok() or and [`InvalidVersion`](SpfError::InvalidVersion)
 pub(crate) fn check_start_of_spf(spf_string: &str) -> Result<(), SpfError> {
-    if spf_string.starts_with(core::SPF1)
-        || spf_string.starts_with(core::SPF2_PRA)
-        || spf_string.starts_with(core::SPF2_MFROM)
-        || spf_string.starts_with(core::SPF2_PRA_MFROM)
-        || spf_string.starts_with(core::SPF2_MFROM_PRA)
-    {
+    if core::parser::is_version(core::parser::split_record(spf_string).0) {
         Ok(())
     } else {
         Err(SpfError::InvalidVersion)
@@ -41,21 +145,36 @@ pub(crate) fn check_start_of_spf(spf_string: &str) -> Result<(), SpfError> {
 fn valid_versions() {
     let input = vec![
         core::SPF1,
-        core::SPF2_PRA,
-        core::SPF2_MFROM,
-        core::SPF2_PRA_MFROM,
-        core::SPF2_MFROM_PRA,
+        "spf2.0/pra",
+        "spf2.0/mfrom",
+        "spf2.0/pra,mfrom",
+        "spf2.0/mfrom,pra",
     ];
     for v in input.into_iter() {
         assert_eq!(check_start_of_spf(v), Ok(()))
     }
 }
 
+#[test]
+fn invalid_versions() {
+    let input = vec![
+        "v=spf10 -all",
+        "v=spf1a",
+        "spf2.0/foo a",
+        "spf2.0 a",
+        " v=spf1",
+    ];
+    for v in input.into_iter() {
+        assert_eq!(check_start_of_spf(v), Err(SpfError::InvalidVersion))
+    }
+}
+
 /// Checks for incorrect white spacing.
 ///
 /// Returns Ok() if there are no issues. [`WhiteSpaceSyntaxError`](SpfError::WhiteSpaceSyntaxError) on error.
+#[cfg(feature = "builder")]
 pub(crate) fn check_whitespaces(spf_string: &str) -> Result<(), SpfError> {
-    if spf_check_whitespace(spf_string) {
+    if core::spf_check_whitespace(spf_string) {
         return Err(SpfError::WhiteSpaceSyntaxError);
     };
     Ok(())
@@ -70,43 +189,3 @@ pub(crate) fn check_spf_length(spf_string: &str) -> Result<(), SpfError> {
     };
     Ok(())
 }
-
-#[cfg(feature = "ptr")]
-#[cfg(feature = "builder")]
-#[allow(dead_code)]
-pub(crate) fn check_ptr(spf: &SpfBuilder) -> Result<(), SpfError> {
-    match spf.ptr() {
-        Some(_) => Err(SpfError::DeprecatedPtrDetected),
-        None => Ok(()),
-    }
-}
-#[cfg(feature = "builder")]
-#[allow(dead_code)]
-/// Redirect should be the only mechanism present. Any additional values are not permitted.
-/// This is wrong need to re-read rfc
-pub(crate) fn check_redirect_all(spf: &SpfBuilder) -> Result<(), SpfError> {
-    if spf.redirect().is_some() && spf.all().is_some() {
-        return Err(SpfError::RedirectWithAllMechanism);
-    }
-    Ok(())
-}
-
-#[cfg(feature = "builder")]
-#[allow(dead_code)]
-pub(crate) fn check_lookup_count(spf: &SpfBuilder) -> usize {
-    let mut lookup_count: usize = 0;
-
-    if spf.redirect().is_some() {
-        lookup_count += 1;
-    }
-    if let Some(a) = spf.a() {
-        lookup_count += a.len();
-    }
-    if let Some(mx) = spf.mx() {
-        lookup_count += mx.len();
-    }
-    if let Some(includes) = spf.includes() {
-        lookup_count += includes.len();
-    }
-    lookup_count
-}
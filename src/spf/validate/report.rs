@@ -0,0 +1,166 @@
+use crate::lint::{self, Finding, Record, Severity};
+use crate::prelude::*;
+use crate::spf::errors::SpfErrors;
+use crate::spf::validate::Check;
+use crate::SpfError;
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// The result of validating a record.
+///
+/// Each problem is a [`Finding`] of one of the built-in [rules](crate::lint::rules) which make
+/// up validation. It carries its severity, a stable rule id, the index of the term, a message
+/// and a link to the RFC. The [`SpfError`] of each finding is also kept.
+///
+/// # Example:
+/// ```
+/// use decon_spf::lint::Severity;
+/// use decon_spf::Spf;
+/// let spf: Spf<String> = "v=spf1 -all redirect=_spf.example.com exp=a.example.com exp=b.example.com"
+///     .parse()
+///     .unwrap();
+/// let report = spf.validation_report();
+/// assert!(report.has_errors());
+/// assert_eq!(report.len(), 2);
+///
+/// let warning = report.warnings().next().unwrap();
+/// assert_eq!(warning.rule(), "redirect-with-all");
+/// assert_eq!(warning.index(), Some(1));
+///
+/// let error = report.errors().next().unwrap();
+/// assert_eq!(error.rule(), "repeated-modifier");
+/// assert_eq!(error.index(), Some(3));
+/// assert_eq!(
+///     error.to_string(),
+///     "error[repeated-modifier]: `exp=b.example.com` repeats the `exp` modifier"
+/// );
+/// assert_eq!(report.max_severity(), Some(Severity::Error));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ValidationReport {
+    source: String,
+    findings: Vec<Finding>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    spf_errors: Vec<SpfError>,
+}
+
+impl ValidationReport {
+    pub(crate) fn new(record: &Record<'_>) -> Self {
+        Self {
+            source: match record.source() {
+                Some(source) => source.to_string(),
+                None => record.to_string(),
+            },
+            findings: Vec::new(),
+            spf_errors: Vec::new(),
+        }
+    }
+    // Run the rule of `check` over `record` with its default severity, unless it is disabled
+    // by default. Returns `true` if anything was found.
+    pub(crate) fn run(&mut self, check: &Check, record: &Record<'_>) -> bool {
+        let rule = check.rule;
+        if !rule.enabled_by_default() {
+            return false;
+        }
+        let start = self.findings.len();
+        lint::run(rule, rule.default_severity(), record, &mut self.findings);
+        for finding in &self.findings[start..] {
+            if let Some(error) = (check.error)(record.terms(), finding.index()) {
+                self.spf_errors.push(error);
+            }
+        }
+        self.findings.len() > start
+    }
+    /// The record which was validated. A record which was built is given as it is displayed.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+    /// Every finding, in the order the checks were made.
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+    /// Iterate over every finding.
+    pub fn iter(&self) -> core::slice::Iter<'_, Finding> {
+        self.findings.iter()
+    }
+    /// Iterate over the findings of `severity`.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Finding> {
+        self.iter().filter(move |f| f.severity() == severity)
+    }
+    /// Iterate over the findings with a severity of [`Error`](Severity::Error).
+    pub fn errors(&self) -> impl Iterator<Item = &Finding> {
+        self.with_severity(Severity::Error)
+    }
+    /// Iterate over the findings with a severity of [`Warning`](Severity::Warning).
+    pub fn warnings(&self) -> impl Iterator<Item = &Finding> {
+        self.with_severity(Severity::Warning)
+    }
+    /// Iterate over the findings with a severity of [`Info`](Severity::Info).
+    pub fn infos(&self) -> impl Iterator<Item = &Finding> {
+        self.with_severity(Severity::Info)
+    }
+    /// Returns `true` if any finding is an [`Error`](Severity::Error).
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+    /// Returns `true` if any finding is a [`Warning`](Severity::Warning).
+    pub fn has_warnings(&self) -> bool {
+        self.warnings().next().is_some()
+    }
+    /// The highest severity of any finding. `None` if there are no findings.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.iter().map(|f| f.severity()).max()
+    }
+    /// Returns `true` if nothing was found.
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+    /// The number of findings.
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+    /// The [`SpfError`] of each finding which has one, in the same order as
+    /// [findings()](Self::findings).
+    pub fn spf_errors(&self) -> &[SpfError] {
+        &self.spf_errors
+    }
+    /// `Ok` if nothing was found, otherwise the findings as [`SpfErrors`].
+    pub fn into_result(self) -> Result<(), SpfErrors> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(self.into()),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ValidationReport {
+    type Item = &'a Finding;
+    type IntoIter = core::slice::Iter<'a, Finding>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for finding in self {
+            writeln!(f, "{}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ValidationReport> for SpfErrors {
+    fn from(report: ValidationReport) -> Self {
+        let mut errors = SpfErrors::new();
+        errors.register_source(report.source);
+        for error in report.spf_errors {
+            errors.register_error(error);
+        }
+        errors
+    }
+}
@@ -65,3 +65,288 @@ mod validate {
         }
     */
 }
+
+#[cfg(feature = "builder")]
+mod unified {
+    use crate::mechanism::Kind;
+    use crate::spf::builder::{Builder, SpfBuilder};
+    use crate::{Spf, SpfError};
+
+    // The errors found for `s` when it is parsed, built and (with serde) deserialized.
+    fn verdicts(s: &str) -> Vec<Vec<SpfError>> {
+        let errors = |r: Result<(), crate::SpfErrors>| match r {
+            Ok(()) => Vec::new(),
+            Err(e) => e.errors().clone(),
+        };
+        let spf: Spf<String> = s.parse().unwrap();
+        let builder = SpfBuilder::<Builder>::from(spf.clone());
+        #[allow(unused_mut)]
+        let mut verdicts = vec![errors(spf.validate()), errors(builder.validate())];
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&spf).unwrap();
+            let spf: Spf<String> = serde_json::from_str(&json).unwrap();
+            verdicts.push(errors(spf.validate()));
+            let json = serde_json::to_string(&builder).unwrap();
+            let builder: SpfBuilder<Builder> = serde_json::from_str(&json).unwrap();
+            verdicts.push(errors(builder.validate()));
+        }
+        verdicts
+    }
+    fn assert_verdict(s: &str, expected: &[SpfError]) {
+        for verdict in verdicts(s) {
+            assert_eq!(verdict, expected, "{}", s);
+        }
+    }
+
+    #[test]
+    fn lookup_count() {
+        // `exists` and `redirect` count as lookups. Ten is within the limit.
+        let ten = "v=spf1 a mx include:a.com include:b.com include:c.com exists:d.com \
+                   exists:e.com a:f.com mx:g.com redirect=h.com";
+        assert_verdict(ten, &[]);
+        let eleven = ten.replace("redirect=", "exists:i.com redirect=");
+        assert_verdict(&eleven, &[SpfError::LookupLimitExceeded]);
+        let spf: Spf<String> = eleven.parse().unwrap();
+        assert_eq!(spf.lookup_count(), 11);
+        assert_eq!(
+            SpfBuilder::<Builder>::from(spf).build(),
+            Err(SpfError::LookupLimitExceeded)
+        );
+    }
+    #[test]
+    fn redirect() {
+        assert_verdict(
+            "v=spf1 redirect=a.com -all",
+            &[SpfError::RedirectWithAllMechanism],
+        );
+        assert_verdict(
+            "v=spf1 a redirect=a.com mx",
+            &[SpfError::RedirectNotFinalMechanism(1)],
+        );
+        // A modifier may follow `redirect`.
+        assert_verdict("v=spf1 a redirect=a.com exp=b.com", &[]);
+    }
+    #[test]
+    fn repeated_modifiers() {
+        assert_verdict(
+            "v=spf1 a mx -all exp=a.com exp=b.com EXP=c.com",
+            &[
+                SpfError::ModifierMayOccurOnlyOnce(Kind::Exp, 4),
+                SpfError::ModifierMayOccurOnlyOnce(Kind::Exp, 5),
+            ],
+        );
+        assert_verdict(
+            "v=spf1 a -all moo=cow MOO=calf foo=cow",
+            &[SpfError::ModifierMayOccurOnlyOnce(Kind::UnknownModifier, 3)],
+        );
+        assert_verdict(
+            "v=spf1 redirect=a.com mx exp=b.com",
+            &[SpfError::RedirectNotFinalMechanism(0)],
+        );
+    }
+    #[test]
+    fn modifier_position() {
+        assert_verdict(
+            "v=spf1 exp=x.example.com a -all",
+            &[SpfError::ModifierNotFinal(Kind::Exp, 0)],
+        );
+        assert_verdict(
+            "v=spf1 a moo=cow mx -all",
+            &[SpfError::ModifierNotFinal(Kind::UnknownModifier, 1)],
+        );
+        assert_verdict(
+            "v=spf1 a redirect=a.com exp=b.com mx",
+            &[
+                SpfError::RedirectNotFinalMechanism(1),
+                SpfError::ModifierNotFinal(Kind::Exp, 2),
+            ],
+        );
+        // Modifiers may follow `all` and each other.
+        assert_verdict("v=spf1 a -all exp=x.example.com moo=cow", &[]);
+        let spf: Spf<String> = "v=spf1 exp=x.example.com a -all".parse().unwrap();
+        assert_eq!(
+            SpfBuilder::<Builder>::from(spf).build(),
+            Err(SpfError::ModifierNotFinal(Kind::Exp, 0))
+        );
+    }
+    #[test]
+    #[cfg(feature = "serde")]
+    fn repeated_redirect_deserialized() {
+        let builder: SpfBuilder<Builder> = "v=spf1 a redirect=a.com"
+            .parse::<Spf<String>>()
+            .unwrap()
+            .into();
+        let mut value = serde_json::to_value(&builder).unwrap();
+        let terms = value["terms"].as_array_mut().unwrap();
+        terms.insert(1, terms[1].clone());
+        let builder: SpfBuilder<Builder> = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            builder.to_string(),
+            "v=spf1 a redirect=a.com redirect=a.com"
+        );
+        let errors = builder.validate().unwrap_err();
+        assert_eq!(
+            errors.errors(),
+            &[SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 2)]
+        );
+        assert_eq!(errors.errors()[0].index(), Some(2));
+        assert_eq!(
+            builder.build(),
+            Err(SpfError::ModifierMayOccurOnlyOnce(Kind::Redirect, 2))
+        );
+    }
+    #[test]
+    #[cfg(feature = "ptr")]
+    fn ptr() {
+        assert_verdict("v=spf1 ptr -all", &[SpfError::DeprecatedPtrDetected]);
+    }
+    #[test]
+    fn length() {
+        // A built record is measured as it is displayed.
+        let mut builder = SpfBuilder::<Builder>::new();
+        builder.append_mechanism(
+            crate::mechanism::Mechanism::a(crate::mechanism::Qualifier::Pass)
+                .with_rrdata(format!("{}.example.com", "a".repeat(60)))
+                .unwrap(),
+        );
+        for i in 0..8 {
+            let name = format!("{}{}.example.com", "b".repeat(50), i);
+            builder.append_mechanism(
+                crate::mechanism::Mechanism::include(crate::mechanism::Qualifier::Pass, &name)
+                    .unwrap(),
+            );
+        }
+        let errors = builder.validate().unwrap_err();
+        assert_eq!(errors.errors(), &[SpfError::SourceLengthExceeded]);
+        assert_eq!(errors.source(), &builder.to_string());
+        assert_eq!(builder.build(), Err(SpfError::SourceLengthExceeded));
+    }
+    #[test]
+    fn whitespace() {
+        let spf: Spf<String> = "v=spf1 a  mx -all".parse().unwrap();
+        assert_eq!(
+            spf.validate().unwrap_err().errors(),
+            &[SpfError::WhiteSpaceSyntaxError]
+        );
+        // The builder holds terms, which never contain white space.
+        assert!(SpfBuilder::<Builder>::from(spf).validate().is_ok());
+    }
+}
+
+mod report {
+    use crate::lint::Severity;
+    use crate::mechanism::Kind;
+    use crate::{Spf, SpfError, ValidationReport};
+
+    fn report(s: &str) -> ValidationReport {
+        s.parse::<Spf<String>>().unwrap().validation_report()
+    }
+
+    #[test]
+    fn checks() {
+        use crate::spf::validate::{CHECKS, MODIFIER_NOT_LAST, REPEATED_MODIFIER};
+        let stopping: Vec<_> = CHECKS
+            .iter()
+            .filter(|c| c.stops)
+            .map(|c| c.rule.id())
+            .collect();
+        assert_eq!(stopping, ["invalid-version", "record-length"]);
+        // A finding which does not name a term of the record has no error.
+        assert_eq!((MODIFIER_NOT_LAST.error)(&[], Some(3)), None);
+        assert_eq!((REPEATED_MODIFIER.error)(&[], None), None);
+    }
+    #[test]
+    fn empty() {
+        let report = report("v=spf1 a mx -all");
+        assert!(report.is_empty());
+        assert!(!report.has_errors());
+        assert!(!report.has_warnings());
+        assert_eq!(report.max_severity(), None);
+        assert_eq!(report.source(), "v=spf1 a mx -all");
+        assert!(report.into_result().is_ok());
+    }
+    #[test]
+    fn severities() {
+        let report = report("v=spf1 a -all moo=cow redirect=a.com moo=calf exp=b.com exp=c.com");
+        let found: Vec<_> = report
+            .iter()
+            .map(|f| (f.rule(), f.severity(), f.index()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("redirect-with-all", Severity::Warning, Some(3)),
+                ("repeated-modifier", Severity::Error, Some(6)),
+                ("repeated-unknown-modifier", Severity::Warning, Some(4)),
+            ]
+        );
+        assert_eq!(report.warnings().count(), 2);
+        assert_eq!(report.errors().count(), 1);
+        assert_eq!(report.infos().count(), 0);
+        assert_eq!(report.with_severity(Severity::Warning).count(), 2);
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+        assert_eq!(
+            report.findings()[2].message(),
+            "`moo=calf` repeats the `moo` modifier"
+        );
+        assert_eq!(
+            report.spf_errors()[1],
+            SpfError::ModifierMayOccurOnlyOnce(Kind::Exp, 6)
+        );
+    }
+    #[test]
+    fn lookup_limit() {
+        let report = report(
+            "v=spf1 a mx include:a.com include:b.com include:c.com exists:d.com \
+             exists:e.com a:f.com mx:g.com exists:i.com redirect=h.com",
+        );
+        let finding = report.errors().next().unwrap();
+        assert_eq!(finding.rule(), "lookup-limit");
+        assert_eq!(finding.index(), None);
+        assert_eq!(
+            finding.message(),
+            "11 DNS lookups are required, the limit is 10"
+        );
+        assert_eq!(
+            finding.reference(),
+            "https://datatracker.ietf.org/doc/html/rfc7208#section-4.6.4"
+        );
+    }
+    #[test]
+    #[cfg(feature = "ptr")]
+    fn ptr() {
+        let report = report("v=spf1 a ptr -all");
+        let finding = report.warnings().next().unwrap();
+        assert_eq!(finding.rule(), "deprecated-ptr");
+        assert_eq!(finding.index(), Some(1));
+    }
+    #[test]
+    fn into_spf_errors() {
+        let spf: Spf<String> = "v=spf1 a redirect=a.com mx".parse().unwrap();
+        let report = spf.validation_report();
+        assert_eq!(
+            report.to_string(),
+            "warning[modifier-not-last]: `redirect=a.com` is followed by a mechanism\n"
+        );
+        let errors = report.into_result().unwrap_err();
+        assert_eq!(errors.source(), "v=spf1 a redirect=a.com mx");
+        assert_eq!(errors.errors(), spf.validate().unwrap_err().errors());
+    }
+    #[test]
+    #[cfg(feature = "builder")]
+    fn built() {
+        let spf: Spf<String> = "v=spf1 redirect=a.com -all".parse().unwrap();
+        let builder = crate::SpfBuilder::<crate::Builder>::from(spf.clone());
+        assert_eq!(builder.validation_report(), spf.validation_report());
+    }
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize() {
+        let json = serde_json::to_value(report("v=spf1 redirect=a.com -all")).unwrap();
+        assert_eq!(json["source"], "v=spf1 redirect=a.com -all");
+        assert_eq!(json["findings"][0]["rule"], "redirect-with-all");
+        assert_eq!(json["findings"][0]["severity"], "Warning");
+        assert!(json.get("spf_errors").is_none());
+    }
+}
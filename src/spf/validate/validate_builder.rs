@@ -1,40 +1,66 @@
-use crate::core::{DNS_LOOKUP_LIMIT, MAX_SPF_STRING_LENGTH};
-use crate::spf::validate::Validate;
+use crate::lint::Record;
+use crate::prelude::*;
+use crate::spf::validate::{
+    Check, Validate, ValidationReport, DEPRECATED_PTR, LOOKUP_LIMIT, MODIFIER_NOT_LAST,
+    RECORD_LENGTH, REDIRECT_WITH_ALL, REPEATED_MODIFIER, REPEATED_UNKNOWN_MODIFIER,
+};
 use crate::{SpfBuilder, SpfError};
 
 impl<State> Validate for SpfBuilder<State> {
-    fn validate_length(&self) -> Result<(), SpfError> {
-        let mut length = 0;
-        length += self.version().len();
-        for _m in self.iter() {
-            length += 1 + _m.raw().len();
-        }
-        match length > MAX_SPF_STRING_LENGTH {
-            true => Err(SpfError::SourceLengthExceeded),
-            false => Ok(()),
-        }
+    fn record(&self) -> Record<'_> {
+        Record::from(self)
     }
+}
 
-    #[cfg(feature = "ptr")]
-    fn validate_ptr(&self) -> Result<(), SpfError> {
-        match self.ptr().is_none() {
-            true => Ok(()),
-            false => Err(SpfError::DeprecatedPtrDetected),
-        }
+// The checks made by `build()`, each of which runs a built-in rule.
+impl<State> SpfBuilder<State> {
+    /// The record must not exceed 512 octets.
+    /// See [`RecordLength`](crate::lint::rules::RecordLength).
+    pub(crate) fn validate_length(&self) -> Result<(), SpfError> {
+        first_error(&self.record(), &RECORD_LENGTH)
     }
-
-    fn validate_redirect_all(&self) -> Result<(), SpfError> {
-        if self.redirect().is_some() && self.all().is_some() {
-            Err(SpfError::RedirectWithAllMechanism)
-        } else {
-            Ok(())
-        }
+    /// `ptr` should not be used. Only checked with the `ptr` feature.
+    /// See [`DeprecatedPtr`](crate::lint::rules::DeprecatedPtr).
+    pub(crate) fn validate_ptr(&self) -> Result<(), SpfError> {
+        first_error(&self.record(), &DEPRECATED_PTR)
+    }
+    /// A `redirect` is ignored when `all` is present.
+    /// See [`RedirectWithAll`](crate::lint::rules::RedirectWithAll).
+    pub(crate) fn validate_redirect_all(&self) -> Result<(), SpfError> {
+        first_error(&self.record(), &REDIRECT_WITH_ALL)
     }
-    fn validate_lookup_count(&self) -> Result<(), SpfError> {
-        match self.get_lookup_count() <= DNS_LOOKUP_LIMIT {
-            true => Ok(()),
-            false => Err(SpfError::LookupLimitExceeded),
+    /// `redirect` and `exp` must not occur more than once, and neither may an unknown modifier
+    /// with the same name. Every modifier should follow every mechanism, except a `redirect`
+    /// which is ignored because `all` is present. Every offending occurrence is returned in
+    /// record order.
+    pub(crate) fn validate_modifiers(&self) -> Vec<SpfError> {
+        let record = self.record();
+        let mut report = ValidationReport::new(&record);
+        for check in [
+            REPEATED_MODIFIER,
+            REPEATED_UNKNOWN_MODIFIER,
+            MODIFIER_NOT_LAST,
+        ] {
+            report.run(&check, &record);
         }
+        let mut errors = report.spf_errors().to_vec();
+        errors.sort_by_key(|e| e.index());
+        errors
+    }
+    /// Evaluating the record must not require more than 10 DNS lookups.
+    /// See [`LookupLimit`](crate::lint::rules::LookupLimit).
+    pub(crate) fn validate_lookup_count(&self) -> Result<(), SpfError> {
+        first_error(&self.record(), &LOOKUP_LIMIT)
+    }
+}
+
+// The error of the first finding of `check`.
+fn first_error(record: &Record<'_>, check: &Check) -> Result<(), SpfError> {
+    let mut report = ValidationReport::new(record);
+    report.run(check, record);
+    match report.spf_errors().first() {
+        Some(e) => Err(e.clone()),
+        None => Ok(()),
     }
 }
 
@@ -71,7 +97,7 @@ mod tests {
     fn test_validate_version() {
         let mut spf: SpfBuilder<Builder> = SpfBuilder::default();
         spf.set_v1();
-        assert!(spf.validate_version().is_ok());
+        assert!(spf.validate_all().is_ok());
     }
     #[test]
     fn test_validate_lookup_count_below_10() {
@@ -86,7 +112,7 @@ mod tests {
         spf.append_mechanism("mx:e.test.com".parse::<Mechanism<String>>().unwrap());
         spf.append_mechanism("mx:f.test.com".parse::<Mechanism<String>>().unwrap());
         spf.append_mechanism("mx:g.test.com".parse::<Mechanism<String>>().unwrap());
-        assert!(matches!(spf.validate_lookup_count(), Ok(_)));
+        assert!(spf.validate_lookup_count().is_ok());
         let spf = spf.build().unwrap();
         assert_eq!(spf.lookup_count(), 9);
         assert_eq!(spf.source, "");
@@ -111,7 +137,7 @@ mod tests {
         spf.append_mechanism("mx:f.test.com".parse::<Mechanism<String>>().unwrap());
         spf.append_mechanism("mx:g.test.com".parse::<Mechanism<String>>().unwrap());
         spf.append_mechanism("mx:h.test.com".parse::<Mechanism<String>>().unwrap());
-        assert!(matches!(spf.validate_lookup_count(), Err(_)));
+        assert!(spf.validate_lookup_count().is_err());
     }
     #[test]
     fn test_length_ok() {
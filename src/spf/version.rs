@@ -0,0 +1,210 @@
+//! The version section of an Spf record.
+//!
+//! [RFC7208 Section 4.5](https://datatracker.ietf.org/doc/html/rfc7208#section-4.5) requires a
+//! record to begin with `v=spf1`. The Sender ID records of
+//! [RFC4406 Section 3](https://datatracker.ietf.org/doc/html/rfc4406#section-3) begin with
+//! `spf2.0/` followed by a list of scopes.
+use crate::core::parser;
+use crate::prelude::*;
+use crate::SpfError;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A Sender ID scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// The Purported Responsible Address. `pra`
+    Pra,
+    /// The `MAIL FROM` address. `mfrom`
+    MFrom,
+}
+
+impl Scope {
+    /// Returns the scope as it appears in a record.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Pra => "pra",
+            Scope::MFrom => "mfrom",
+        }
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The version of an Spf record.
+///
+/// # Example:
+/// ```
+/// use decon_spf::{Scope, Version};
+/// let v1: Version = "v=spf1".parse().unwrap();
+/// assert!(v1.is_v1());
+/// assert_eq!(v1, "v=spf1");
+///
+/// let v2: Version = "spf2.0/mfrom,pra".parse().unwrap();
+/// assert!(v2.is_v2());
+/// assert_eq!(v2.scopes(), [Scope::MFrom, Scope::Pra]);
+/// assert!(v2.has_scope(Scope::Pra));
+/// assert_eq!(v2.to_string(), "spf2.0/mfrom,pra");
+///
+/// assert!("v=spf10".parse::<Version>().is_err());
+/// assert!("spf2.0/foo".parse::<Version>().is_err());
+/// assert!("spf2.0/pra,pra".parse::<Version>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Version {
+    /// `v=spf1`
+    #[default]
+    V1,
+    /// `spf2.0/` and one or more distinct scopes, in the order they were given.
+    V2 {
+        /// The scopes the record applies to.
+        scopes: Vec<Scope>,
+    },
+}
+
+impl Version {
+    /// Create a `spf2.0` version. The scopes must not be empty or contain duplicates.
+    ///
+    /// # Example:
+    /// ```
+    /// use decon_spf::{Scope, Version};
+    /// let v2 = Version::v2(&[Scope::Pra]).unwrap();
+    /// assert_eq!(v2.to_string(), "spf2.0/pra");
+    /// assert!(Version::v2(&[]).is_err());
+    /// ```
+    pub fn v2(scopes: &[Scope]) -> Result<Self, SpfError> {
+        let unique = scopes
+            .iter()
+            .enumerate()
+            .all(|(i, scope)| !scopes[..i].contains(scope));
+        if scopes.is_empty() || !unique {
+            return Err(SpfError::InvalidVersion);
+        }
+        Ok(Version::V2 {
+            scopes: scopes.to_vec(),
+        })
+    }
+    /// Returns `true` if the version is `v=spf1`
+    pub fn is_v1(&self) -> bool {
+        matches!(self, Version::V1)
+    }
+    /// Returns `true` if the version is `spf2.0`
+    pub fn is_v2(&self) -> bool {
+        matches!(self, Version::V2 { .. })
+    }
+    /// The scopes of a `spf2.0` version. This is empty for `v=spf1`
+    pub fn scopes(&self) -> &[Scope] {
+        match self {
+            Version::V1 => &[],
+            Version::V2 { scopes } => scopes,
+        }
+    }
+    /// Returns `true` if the version is `spf2.0` and includes `scope`.
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes().contains(&scope)
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Version::V1 => write!(f, "{}", crate::core::SPF1),
+            Version::V2 { scopes } => {
+                write!(f, "{}", parser::SPF2_PREFIX)?;
+                for (i, scope) in scopes.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", scope)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = SpfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse_version(s).ok_or(SpfError::InvalidVersion)
+    }
+}
+
+/// Compares against the text of a version term without regard to case.
+impl PartialEq<str> for Version {
+    fn eq(&self, other: &str) -> bool {
+        parser::parse_version(other).as_ref() == Some(self)
+    }
+}
+
+impl PartialEq<&str> for Version {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for Version {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_insensitive() {
+        assert_eq!("V=SPF1".parse::<Version>().unwrap(), Version::V1);
+        let v2: Version = "SPF2.0/PRA,MFROM".parse().unwrap();
+        assert_eq!(v2.scopes(), [Scope::Pra, Scope::MFrom]);
+        assert_eq!(v2, "spf2.0/pra,mfrom");
+        assert_ne!(v2, "spf2.0/mfrom,pra");
+    }
+    #[test]
+    fn display() {
+        for s in ["v=spf1", "spf2.0/pra", "spf2.0/mfrom,pra"] {
+            assert_eq!(s.parse::<Version>().unwrap().to_string(), s);
+        }
+    }
+    #[test]
+    fn v2_duplicate_scope() {
+        assert_eq!(
+            Version::v2(&[Scope::MFrom, Scope::MFrom]),
+            Err(SpfError::InvalidVersion)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let v2 = Version::v2(&[Scope::Pra, Scope::MFrom]).unwrap();
+        let json = serde_json::to_string(&v2).unwrap();
+        assert_eq!(json, "\"spf2.0/pra,mfrom\"");
+        assert_eq!(serde_json::from_str::<Version>(&json).unwrap(), v2);
+        assert!(serde_json::from_str::<Version>("\"spf2.0/\"").is_err());
+    }
+}
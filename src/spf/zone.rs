@@ -0,0 +1,524 @@
+//! Scan a zone (master) file for Spf records.
+//!
+//! [`ZoneScanner`] reads a zone file written in the format given in
+//! [RFC1035 Section 5](https://datatracker.ietf.org/doc/html/rfc1035#section-5) and yields a
+//! [`ZoneRecord`] for every TXT record which begins with `v=spf1`. No network access is
+//! required. This makes it possible to check every Spf record in a zone before it is published.
+//!
+//! The following is supported:
+//! - `$ORIGIN` and `$TTL` directives. `$INCLUDE` and `$GENERATE` are skipped.
+//! - `@`, relative names, and lines starting with white space reusing the previous owner.
+//! - TTL and class given in either order. TTL may use BIND units such as `1h30m`.
+//! - Multi-string TXT RDATA, escapes, comments and `( )` continuation over several lines.
+//!
+//! # Example:
+//! ```
+//! use decon_spf::zone::ZoneScanner;
+//! let zone = r#"
+//! $ORIGIN example.com.
+//! $TTL 3600
+//! @       IN  TXT ( "v=spf1 ip4:192.0.2.0/24 "
+//!                   "include:_spf.example.net -all" )
+//! mail        TXT "v=spf1 a -all"
+//! www         A   192.0.2.10
+//! bad         TXT "v=spf1 foo -all"
+//! "#;
+//! let records: Vec<_> = ZoneScanner::new(zone).collect();
+//! assert_eq!(records.len(), 3);
+//! assert_eq!(records[0].owner(), "example.com.");
+//! assert_eq!(records[0].line(), 4);
+//! assert_eq!(records[0].ttl(), Some(3600));
+//! assert!(records[0].spf().is_ok());
+//! assert_eq!(records[1].owner(), "mail.example.com.");
+//! assert!(records[2].spf().is_err());
+//!
+//! // Validate every record found.
+//! for record in ZoneScanner::new(zone) {
+//!     if let Ok(spf) = record.spf() {
+//!         spf.validate().ok();
+//!     }
+//! }
+//! ```
+use crate::core::{parser, txt};
+use crate::prelude::*;
+use crate::{Spf, SpfError, Version};
+
+/// An Spf record found in a zone file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneRecord {
+    owner: String,
+    line: usize,
+    ttl: Option<u32>,
+    spf: Result<Spf<String>, SpfError>,
+}
+
+impl ZoneRecord {
+    /// The fully qualified owner name of the record. If no `$ORIGIN` is known relative names
+    /// are returned unchanged.
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+    /// The line, starting at 1, on which the record starts.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    /// The TTL of the record, if one is given or set with `$TTL`.
+    pub fn ttl(&self) -> Option<u32> {
+        self.ttl
+    }
+    /// The result of parsing the TXT RDATA as a `Spf<String>`.
+    pub fn spf(&self) -> &Result<Spf<String>, SpfError> {
+        &self.spf
+    }
+    /// Returns the owner name and parse result.
+    pub fn into_parts(self) -> (String, Result<Spf<String>, SpfError>) {
+        (self.owner, self.spf)
+    }
+}
+
+// A single logical entry of the zone file. This may cover several lines.
+#[derive(Debug, Default)]
+struct Entry {
+    line: usize,
+    // The entry starts with white space. The owner is the previous owner.
+    blank_owner: bool,
+    tokens: Vec<String>,
+    error: Option<&'static str>,
+}
+
+/// Yields a [`ZoneRecord`] for every Spf record found in a zone file.
+///
+/// See the [module](crate::zone) documentation for an example.
+#[derive(Debug, Clone)]
+pub struct ZoneScanner<'a> {
+    zone: &'a str,
+    pos: usize,
+    line: usize,
+    origin: Option<String>,
+    default_ttl: Option<u32>,
+    last_ttl: Option<u32>,
+    last_owner: Option<String>,
+}
+
+impl<'a> ZoneScanner<'a> {
+    /// Create a scanner for the contents of a zone file.
+    pub fn new(zone: &'a str) -> Self {
+        Self {
+            zone,
+            pos: 0,
+            line: 1,
+            origin: None,
+            default_ttl: None,
+            last_ttl: None,
+            last_owner: None,
+        }
+    }
+    /// Set the origin used until the zone file gives an `$ORIGIN`.
+    ///
+    /// ```
+    /// use decon_spf::zone::ZoneScanner;
+    /// let zone = "@ TXT \"v=spf1 -all\"";
+    /// let record = ZoneScanner::new(zone).with_origin("example.com").next().unwrap();
+    /// assert_eq!(record.owner(), "example.com.");
+    /// ```
+    pub fn with_origin(mut self, origin: &str) -> Self {
+        let mut origin = origin.to_string();
+        if !origin.ends_with('.') {
+            origin.push('.');
+        }
+        self.origin = Some(origin);
+        self
+    }
+
+    // Read the next logical entry. Returns `None` at the end of the zone.
+    fn next_entry(&mut self) -> Option<Entry> {
+        let bytes = self.zone.as_bytes();
+        let mut entry = Entry::default();
+        let mut depth = 0;
+        let mut token: Option<String> = None;
+        loop {
+            let c = match bytes.get(self.pos) {
+                Some(c) => *c,
+                None => {
+                    if depth > 0 {
+                        entry.error = Some("unbalanced parentheses");
+                    }
+                    entry.tokens.extend(token.take());
+                    break;
+                }
+            };
+            if entry.tokens.is_empty() && token.is_none() && depth == 0 {
+                if c != b'\n' {
+                    entry.line = self.line;
+                }
+                if c == b' ' || c == b'\t' {
+                    entry.blank_owner = true;
+                }
+            }
+            match c {
+                b'\n' => {
+                    self.pos += 1;
+                    self.line += 1;
+                    entry.tokens.extend(token.take());
+                    if depth == 0 && !entry.tokens.is_empty() {
+                        break;
+                    }
+                    if depth == 0 {
+                        entry = Entry::default();
+                    }
+                }
+                b';' => {
+                    while bytes.get(self.pos).is_some_and(|c| *c != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                b'(' | b')' => {
+                    self.pos += 1;
+                    entry.tokens.extend(token.take());
+                    if c == b'(' {
+                        depth += 1;
+                    } else if depth == 0 {
+                        entry.error = Some("unbalanced parentheses");
+                    } else {
+                        depth -= 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => {
+                    self.pos += 1;
+                    entry.tokens.extend(token.take());
+                }
+                b'"' => {
+                    // Quoted strings are kept with their quotes and escapes so that they can
+                    // be read as character-strings.
+                    let start = self.pos;
+                    self.pos += 1;
+                    loop {
+                        match bytes.get(self.pos) {
+                            None => {
+                                entry.error = Some("unterminated quoted string");
+                                break;
+                            }
+                            Some(b'"') => {
+                                self.pos += 1;
+                                break;
+                            }
+                            Some(b'\\') => self.pos = self.escape_end(),
+                            Some(b'\n') => {
+                                self.line += 1;
+                                self.pos += 1;
+                            }
+                            Some(_) => self.pos += 1,
+                        }
+                    }
+                    let end = self.pos.min(bytes.len());
+                    token
+                        .get_or_insert_with(String::new)
+                        .push_str(&self.zone[start..end]);
+                }
+                b'\\' => {
+                    let end = self.escape_end();
+                    token
+                        .get_or_insert_with(String::new)
+                        .push_str(&self.zone[self.pos..end]);
+                    self.pos = end;
+                }
+                _ => {
+                    let start = self.pos;
+                    while bytes.get(self.pos).is_some_and(|c| {
+                        !c.is_ascii_whitespace() && !matches!(c, b'"' | b';' | b'(' | b')' | b'\\')
+                    }) {
+                        self.pos += 1;
+                    }
+                    token
+                        .get_or_insert_with(String::new)
+                        .push_str(&self.zone[start..self.pos]);
+                }
+            }
+        }
+        if entry.tokens.is_empty() {
+            None
+        } else {
+            Some(entry)
+        }
+    }
+
+    // The position after the character escaped by the `\` at the current position. An escaped
+    // newline is counted as a line.
+    fn escape_end(&mut self) -> usize {
+        let escaped = self.zone[self.pos + 1..].chars().next();
+        if escaped == Some('\n') {
+            self.line += 1;
+        }
+        self.pos + 1 + escaped.map_or(0, char::len_utf8)
+    }
+
+    // Make `name` fully qualified using the current origin.
+    fn qualify(&self, name: &str) -> String {
+        match (name, &self.origin) {
+            ("@", Some(origin)) => origin.clone(),
+            (name, _) if name.ends_with('.') => name.to_string(),
+            (name, Some(origin)) if origin == "." => format!("{}.", name),
+            (name, Some(origin)) => format!("{}.{}", name, origin),
+            (name, None) => name.to_string(),
+        }
+    }
+
+    // Handle `$ORIGIN` and `$TTL`. Other directives are ignored.
+    fn directive(&mut self, entry: &Entry) {
+        let value = entry.tokens.get(1);
+        if entry.tokens[0].eq_ignore_ascii_case("$ORIGIN") {
+            if let Some(origin) = value {
+                self.origin = Some(self.qualify(origin));
+            }
+        } else if entry.tokens[0].eq_ignore_ascii_case("$TTL") {
+            if let Some(ttl) = value.and_then(|v| parse_ttl(v)) {
+                self.default_ttl = Some(ttl);
+            }
+        }
+    }
+
+    // Returns the `ZoneRecord` if the entry is an Spf TXT record.
+    fn record(&mut self, entry: Entry) -> Option<ZoneRecord> {
+        let mut tokens = entry.tokens.iter();
+        let owner = if entry.blank_owner {
+            self.last_owner.clone()?
+        } else {
+            self.qualify(tokens.next()?)
+        };
+        self.last_owner = Some(owner.clone());
+
+        let mut ttl = None;
+        let rr_type = loop {
+            let token = tokens.next()?;
+            if let Some(value) = parse_ttl(token) {
+                ttl = Some(value);
+            } else if !["IN", "CH", "HS", "CS"]
+                .iter()
+                .any(|class| class.eq_ignore_ascii_case(token))
+            {
+                break token;
+            }
+        };
+        // RFC2308: Records without a TTL use $TTL. Otherwise, the last TTL given.
+        let ttl = ttl.or(self.default_ttl).or(self.last_ttl);
+        self.last_ttl = ttl;
+        if !rr_type.eq_ignore_ascii_case("TXT") {
+            return None;
+        }
+
+        let rdata = tokens.map(String::as_str).collect::<Vec<_>>().join(" ");
+        let spf = match entry.error {
+            Some(reason) => Err(SpfError::InvalidTxtRdata(reason.to_string())),
+            None => txt::parse_presentation(&rdata).and_then(|strings| txt::concatenate(&strings)),
+        };
+        let spf = match spf {
+            Ok(record) if !is_spf(&record) => return None,
+            Ok(record) => record.parse(),
+            // Only report broken TXT data if it looks like an Spf record.
+            Err(_) if !rdata.to_ascii_lowercase().contains(crate::core::SPF1) => return None,
+            Err(e) => Err(e),
+        };
+        Some(ZoneRecord {
+            owner,
+            line: entry.line,
+            ttl,
+            spf,
+        })
+    }
+}
+
+impl Iterator for ZoneScanner<'_> {
+    type Item = ZoneRecord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entry) = self.next_entry() {
+            if entry.tokens[0].starts_with('$') && !entry.blank_owner {
+                self.directive(&entry);
+            } else if let Some(record) = self.record(entry) {
+                return Some(record);
+            }
+        }
+        None
+    }
+}
+
+fn is_spf(record: &str) -> bool {
+    parser::parse_version(parser::split_record(record).0) == Some(Version::V1)
+}
+
+// A TTL is either a number of seconds or a BIND style value such as `1w2d3h4m5s`.
+fn parse_ttl(s: &str) -> Option<u32> {
+    if !s.as_bytes().first()?.is_ascii_digit() {
+        return None;
+    }
+    if let Ok(seconds) = s.parse::<u32>() {
+        return Some(seconds);
+    }
+    let mut total: u32 = 0;
+    let mut value: Option<u32> = None;
+    for c in s.chars() {
+        match c.to_digit(10) {
+            Some(d) => value = Some(value.unwrap_or(0).checked_mul(10)?.checked_add(d)?),
+            None => {
+                let unit = match c.to_ascii_lowercase() {
+                    'w' => 604_800,
+                    'd' => 86_400,
+                    'h' => 3_600,
+                    'm' => 60,
+                    's' => 1,
+                    _ => return None,
+                };
+                total = total.checked_add(value.take()?.checked_mul(unit)?)?;
+            }
+        }
+    }
+    match value {
+        Some(_) => None,
+        None => Some(total),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(zone: &str) -> Vec<ZoneRecord> {
+        ZoneScanner::new(zone).collect()
+    }
+
+    #[test]
+    fn ttl_values() {
+        assert_eq!(parse_ttl("3600"), Some(3600));
+        assert_eq!(parse_ttl("1h30m"), Some(5400));
+        assert_eq!(parse_ttl("1W"), Some(604_800));
+        assert_eq!(parse_ttl("1h30"), None);
+        assert_eq!(parse_ttl("IN"), None);
+        assert_eq!(parse_ttl("h1"), None);
+    }
+    #[test]
+    fn origin_and_relative_names() {
+        let zone = "$ORIGIN example.com.\n\
+                    @ TXT \"v=spf1 -all\"\n\
+                    mail TXT \"v=spf1 a -all\"\n\
+                    other.example.net. TXT \"v=spf1 mx -all\"\n\
+                    $ORIGIN sub\n\
+                    host TXT \"v=spf1 -all\"\n";
+        let owners: Vec<String> = scan(zone).iter().map(|r| r.owner().to_string()).collect();
+        assert_eq!(
+            owners,
+            [
+                "example.com.",
+                "mail.example.com.",
+                "other.example.net.",
+                "host.sub.example.com."
+            ]
+        );
+    }
+    #[test]
+    fn without_origin() {
+        let records = scan("mail TXT \"v=spf1 -all\"");
+        assert_eq!(records[0].owner(), "mail");
+    }
+    #[test]
+    fn previous_owner() {
+        let zone = "example.com. 300 IN A 192.0.2.1\n\
+                    \t\tIN 600 TXT \"v=spf1 a -all\"\n";
+        let records = scan(zone);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].owner(), "example.com.");
+        assert_eq!(records[0].ttl(), Some(600));
+        assert_eq!(records[0].line(), 2);
+    }
+    #[test]
+    fn ttl_defaults() {
+        let zone = "a. 300 TXT \"v=spf1 -all\"\n\
+                    b. TXT \"v=spf1 -all\"\n\
+                    $TTL 1d\n\
+                    c. TXT \"v=spf1 -all\"\n";
+        let ttls: Vec<Option<u32>> = scan(zone).iter().map(|r| r.ttl()).collect();
+        assert_eq!(ttls, [Some(300), Some(300), Some(86400)]);
+    }
+    #[test]
+    fn multi_string_and_parentheses() {
+        let zone = "example.com. IN TXT ( \"v=spf1 ip4:192.0.2.0/24 \" ; first\n\
+                    \t\"ip4:198.51.100.0/24\" ; second\n\
+                    \t\" -all\" )\n\
+                    next. TXT \"v=spf1 -all\"\n";
+        let records = scan(zone);
+        assert_eq!(records.len(), 2);
+        let spf = records[0].spf().as_ref().unwrap();
+        assert_eq!(
+            spf.to_string(),
+            "v=spf1 ip4:192.0.2.0/24 ip4:198.51.100.0/24 -all"
+        );
+        assert_eq!(records[1].line(), 4);
+    }
+    #[test]
+    fn escapes_and_semicolons() {
+        let zone = r#"example.com. TXT "v=spf1 exists:%{i}.example.com \"x;y\" -all""#;
+        let record = &scan(zone)[0];
+        assert!(record.spf().is_err());
+        let zone = "example.com. TXT \"v=spf1\\032-all\"";
+        assert!(scan(zone)[0].spf().is_ok());
+    }
+    #[test]
+    fn multibyte_escapes() {
+        let zone = "a. TXT v=spf1\\\u{e9}\n\
+                    b. TXT \"v=spf1 a:\\\u{e9}.example.com -all\"\n\
+                    c. TXT \"v=spf1 -all\"\n\
+                    d. TXT \"v=spf1 -all\\";
+        let records = scan(zone);
+        let owners: Vec<&str> = records.iter().map(|r| r.owner()).collect();
+        assert_eq!(owners, ["b.", "c.", "d."]);
+        assert!(records[0].spf().is_err());
+        assert_eq!(records[1].line(), 3);
+        assert!(records[2].spf().is_err());
+    }
+    #[test]
+    fn escaped_newlines() {
+        let zone = "a. TXT \"v=spf1 \\\n-all\"\n\
+                    b. TXT v=spf1\\\n-all\n\
+                    c. TXT \"v=spf1 -all\"\n";
+        // `b` is not an Spf record once the escaped newline is read.
+        let lines: Vec<usize> = scan(zone).iter().map(|r| r.line()).collect();
+        assert_eq!(lines, [1, 5]);
+    }
+    #[test]
+    fn non_spf_records_skipped() {
+        let zone = "example.com. TXT \"google-site-verification=abc\"\n\
+                    example.com. TXT \"spf2.0/pra a -all\"\n\
+                    example.com. MX 10 mail.example.com.\n\
+                    txt.example.com. A 192.0.2.1\n";
+        assert!(scan(zone).is_empty());
+    }
+    #[test]
+    fn invalid_records_reported() {
+        let zone = "a. TXT \"v=spf1 foo -all\"\n\
+                    b. TXT ( \"v=spf1 -all\"\n";
+        let records = scan(zone);
+        assert_eq!(records.len(), 2);
+        assert!(records[0].spf().is_err());
+        assert_eq!(
+            records[1].spf(),
+            &Err(SpfError::InvalidTxtRdata(
+                "unbalanced parentheses".to_string()
+            ))
+        );
+    }
+    #[test]
+    fn unterminated_quote() {
+        let records = scan("a. TXT \"v=spf1 -all\n");
+        assert_eq!(
+            records[0].spf(),
+            &Err(SpfError::InvalidTxtRdata(
+                "unterminated quoted string".to_string()
+            ))
+        );
+    }
+    #[test]
+    fn into_parts() {
+        let (owner, spf) = scan("a. TXT \"v=spf1 -all\"").remove(0).into_parts();
+        assert_eq!(owner, "a.");
+        assert!(spf.is_ok());
+    }
+}